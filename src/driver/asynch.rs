@@ -0,0 +1,597 @@
+//! Async MAX7219 driver on top of `embedded-hal-async`
+//!
+//! [`AsyncMax7219`] mirrors the register-level API of [`Max7219`](crate::Max7219)
+//! for executors such as Embassy, awaiting each SPI transfer instead of blocking.
+//! Both drivers build their frames with the same code, so they send the same
+//! bytes for the same calls.
+
+use embedded_hal_async::spi::SpiDevice;
+
+use super::{
+    frame,
+    init::InitConfig,
+    max7219::{ChainOrder, DEFAULT_INITIAL_INTENSITY},
+};
+use crate::{
+    MAX_DISPLAYS, Result,
+    error::Error,
+    registers::{DecodeMode, Register},
+};
+
+/// Async driver for the MAX7219 LED display controller.
+///
+/// Communicates over SPI using the embedded-hal-async `SpiDevice` trait.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut driver = AsyncMax7219::new(spi).with_device_count(4)?;
+/// driver.init().await?;
+/// driver.write_raw_digit(0, 0, 0b1010_1010).await?;
+/// ```
+pub struct AsyncMax7219<SPI> {
+    spi: SPI,
+    buffer: [[u8; 2]; MAX_DISPLAYS],
+    device_count: usize,
+    /// Intensity written to every device by [`Self::init`], if any.
+    initial_intensity: Option<u8>,
+    /// End of the chain that device `0` refers to.
+    chain_order: ChainOrder,
+}
+
+impl<SPI> AsyncMax7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Creates a new async MAX7219 driver instance with the given SPI interface.
+    ///
+    /// The same SPI requirements as for [`Max7219::new`](crate::Max7219::new)
+    /// apply: Mode 0 and at most 10 MHz. Defaults to a single device.
+    pub fn new(spi: SPI) -> Self {
+        Self {
+            spi,
+            buffer: [[0; 2]; MAX_DISPLAYS],
+            device_count: 1,
+            initial_intensity: Some(DEFAULT_INITIAL_INTENSITY),
+            chain_order: ChainOrder::FarthestFirst,
+        }
+    }
+
+    /// Returns the number of MAX7219 devices managed by this driver.
+    pub fn device_count(&self) -> usize {
+        self.device_count
+    }
+
+    /// Sets the number of daisy-chained devices to control.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if `count > MAX_DISPLAYS`.
+    pub fn with_device_count(mut self, count: usize) -> Result<Self> {
+        if count > MAX_DISPLAYS {
+            return Err(Error::InvalidDeviceCount);
+        }
+        self.device_count = count;
+        Ok(self)
+    }
+
+    /// Sets the intensity [`Self::init`] writes to every device, like
+    /// [`Max7219::with_initial_intensity`](crate::Max7219::with_initial_intensity).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` if the level is above `0x0F`.
+    pub fn with_initial_intensity(mut self, intensity: Option<u8>) -> Result<Self> {
+        if intensity.is_some_and(|level| level > 0x0F) {
+            return Err(Error::InvalidIntensity);
+        }
+        self.initial_intensity = intensity;
+        Ok(self)
+    }
+
+    /// Sets which end of the chain device index `0` refers to, like
+    /// [`Max7219::with_chain_order`](crate::Max7219::with_chain_order).
+    pub fn with_chain_order(mut self, order: ChainOrder) -> Self {
+        self.chain_order = order;
+        self
+    }
+
+    /// Releases the SPI device.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Initializes all configured displays, see [`Max7219::init`](crate::Max7219::init).
+    pub async fn init(&mut self) -> Result<()> {
+        self.noop_sync().await?;
+        let config = InitConfig::new().initial_intensity(self.initial_intensity);
+        for (register, data) in frame::init_sequence(&config) {
+            self.write_all(register, data).await?;
+        }
+        Ok(())
+    }
+
+    /// Clocks one no-op per device through the chain, see
+    /// [`Max7219::noop_sync`](crate::Max7219::noop_sync).
+    pub async fn noop_sync(&mut self) -> Result<()> {
+        self.write_all(Register::NoOp, 0x00).await
+    }
+
+    /// Writes a value to a register of one device, sending no-ops to the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range, or an SPI
+    /// error if the transfer fails.
+    pub(crate) async fn write_device_register(
+        &mut self,
+        device_index: usize,
+        register: Register,
+        data: u8,
+    ) -> Result<()> {
+        frame::check_device_index(device_index, self.device_count)?;
+        let ops = frame::device_ops::<MAX_DISPLAYS>(device_index, register, data);
+        self.write_all_registers(frame::active(&ops, self.device_count))
+            .await
+    }
+
+    /// Writes `ops[i]` to device `i` in one transaction.
+    ///
+    /// `ops[0]` goes to the device furthest from the MCU, or the nearest with
+    /// [`ChainOrder::NearestFirst`]. There must be exactly one op per device.
+    pub(crate) async fn write_all_registers(&mut self, ops: &[(Register, u8)]) -> Result<()> {
+        debug_assert_eq!(ops.len(), self.device_count, "one op per device");
+
+        let frame = frame::encode_frame(&mut self.buffer, ops, self.device_count, self.chain_order);
+        self.spi.write(frame).await?;
+        Ok(())
+    }
+
+    /// Writes `data` to `register` of every device in one transaction.
+    async fn write_all(&mut self, register: Register, data: u8) -> Result<()> {
+        let ops = [(register, data); MAX_DISPLAYS];
+        self.write_all_registers(frame::active(&ops, self.device_count))
+            .await
+    }
+
+    /// Powers on all displays by writing `0x01` to the Shutdown register.
+    pub async fn power_on(&mut self) -> Result<()> {
+        self.write_all(Register::Shutdown, 0x01).await
+    }
+
+    /// Powers off all displays by writing `0x00` to the Shutdown register.
+    pub async fn power_off(&mut self) -> Result<()> {
+        self.write_all(Register::Shutdown, 0x00).await
+    }
+
+    /// Powers on a single display.
+    pub async fn power_on_device(&mut self, device_index: usize) -> Result<()> {
+        self.write_device_register(device_index, Register::Shutdown, 0x01)
+            .await
+    }
+
+    /// Powers off a single display.
+    pub async fn power_off_device(&mut self, device_index: usize) -> Result<()> {
+        self.write_device_register(device_index, Register::Shutdown, 0x00)
+            .await
+    }
+
+    /// Enables or disables display test mode on one device.
+    pub async fn test_device(&mut self, device_index: usize, enable: bool) -> Result<()> {
+        self.write_device_register(device_index, Register::DisplayTest, enable.into())
+            .await
+    }
+
+    /// Enables or disables display test mode on all devices in one SPI transaction.
+    pub async fn test_all(&mut self, enable: bool) -> Result<()> {
+        self.write_all(Register::DisplayTest, enable.into()).await
+    }
+
+    /// Sets how many digits (1 to 8) one device scans.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidScanLimit` if the value is not in the range 1 to 8.
+    pub async fn set_device_scan_limit(&mut self, device_index: usize, limit: u8) -> Result<()> {
+        let value = frame::scan_limit_value(limit)?;
+        self.write_device_register(device_index, Register::ScanLimit, value)
+            .await
+    }
+
+    /// Sets the scan limit (1 to 8) of all devices in one go.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidScanLimit` if the value is not in the range 1 to 8.
+    pub async fn set_scan_limit_all(&mut self, limit: u8) -> Result<()> {
+        let value = frame::scan_limit_value(limit)?;
+        self.write_all(Register::ScanLimit, value).await
+    }
+
+    /// Sets which digits of one device use Code B decoding.
+    pub async fn set_device_decode_mode(
+        &mut self,
+        device_index: usize,
+        mode: DecodeMode,
+    ) -> Result<()> {
+        self.write_device_register(device_index, Register::DecodeMode, mode.value())
+            .await
+    }
+
+    /// Sets the decode mode of all devices in one go.
+    pub async fn set_decode_mode_all(&mut self, mode: DecodeMode) -> Result<()> {
+        self.write_all(Register::DecodeMode, mode.value()).await
+    }
+
+    /// Clears every digit of one device.
+    pub async fn clear_display(&mut self, device_index: usize) -> Result<()> {
+        for digit_register in Register::digits() {
+            self.write_device_register(device_index, digit_register, 0x00)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Clears every digit of every device, one transaction per digit.
+    pub async fn clear_all(&mut self) -> Result<()> {
+        for digit_register in Register::digits() {
+            self.write_all(digit_register, 0x00).await?;
+        }
+        Ok(())
+    }
+
+    /// Writes a raw value to one digit register (0 to 7) of one device.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDigit` if `digit > 7`, or
+    /// `Error::InvalidDeviceIndex` if the index is out of range.
+    pub async fn write_raw_digit(
+        &mut self,
+        device_index: usize,
+        digit: u8,
+        value: u8,
+    ) -> Result<()> {
+        let digit_register = Register::try_digit(digit)?;
+        self.write_device_register(device_index, digit_register, value)
+            .await
+    }
+
+    /// Sets the intensity (0 to 15) of one device.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` if the level is above `0x0F`.
+    pub async fn set_intensity(&mut self, device_index: usize, intensity: u8) -> Result<()> {
+        if intensity > 0x0F {
+            return Err(Error::InvalidIntensity);
+        }
+        self.write_device_register(device_index, Register::Intensity, intensity)
+            .await
+    }
+
+    /// Sets the intensity (0 to 15) of all devices in one transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` if the level is above `0x0F`.
+    pub async fn set_intensity_all(&mut self, intensity: u8) -> Result<()> {
+        if intensity > 0x0F {
+            return Err(Error::InvalidIntensity);
+        }
+        self.write_all(Register::Intensity, intensity).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NUM_DIGITS;
+    use crate::test_support::block_on;
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction};
+
+    /// One transaction per frame.
+    fn frames(frames: &[Vec<u8>]) -> Vec<Transaction<u8>> {
+        frames
+            .iter()
+            .flat_map(|frame| {
+                [
+                    Transaction::transaction_start(),
+                    Transaction::write_vec(frame.clone()),
+                    Transaction::transaction_end(),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = AsyncMax7219::new(&mut spi);
+        assert_eq!(driver.device_count(), 1);
+        spi.done();
+    }
+
+    #[test]
+    fn test_with_device_count() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = AsyncMax7219::new(&mut spi).with_device_count(4).unwrap();
+        assert_eq!(driver.device_count(), 4);
+
+        let result = AsyncMax7219::new(&mut spi).with_device_count(MAX_DISPLAYS + 1);
+        assert!(matches!(result, Err(Error::InvalidDeviceCount)));
+        spi.done();
+    }
+
+    #[test]
+    fn test_power_on_off() {
+        let mut spi = SpiMock::new(&frames(&[
+            vec![
+                Register::Shutdown.addr(),
+                0x01,
+                Register::Shutdown.addr(),
+                0x01,
+            ],
+            vec![
+                Register::Shutdown.addr(),
+                0x00,
+                Register::Shutdown.addr(),
+                0x00,
+            ],
+        ]));
+        let mut driver = AsyncMax7219::new(&mut spi).with_device_count(2).unwrap();
+
+        block_on(driver.power_on()).unwrap();
+        block_on(driver.power_off()).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_chain_order_nearest_first() {
+        let shutdown = Register::Shutdown.addr();
+        let mut spi = SpiMock::new(&frames(&[vec![0x00, 0x00, 0x00, 0x00, shutdown, 0x00]]));
+        let mut driver = AsyncMax7219::new(&mut spi)
+            .with_device_count(3)
+            .unwrap()
+            .with_chain_order(ChainOrder::NearestFirst);
+
+        block_on(driver.power_off_device(0)).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_power_device() {
+        let mut spi = SpiMock::new(&frames(&[
+            vec![
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+                Register::Shutdown.addr(),
+                0x00,
+                0x00,
+                0x00,
+            ],
+            vec![
+                Register::Shutdown.addr(),
+                0x01,
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+            ],
+        ]));
+        let mut driver = AsyncMax7219::new(&mut spi).with_device_count(4).unwrap();
+
+        block_on(driver.power_off_device(2)).unwrap();
+        block_on(driver.power_on_device(0)).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_power_device_invalid_index() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = AsyncMax7219::new(&mut spi);
+
+        let result = block_on(driver.power_on_device(1));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 1, count: 1 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_test_mode() {
+        let mut spi = SpiMock::new(&frames(&[
+            vec![
+                Register::DisplayTest.addr(),
+                0x01,
+                Register::DisplayTest.addr(),
+                0x01,
+            ],
+            vec![Register::DisplayTest.addr(), 0x00, 0x00, 0x00],
+        ]));
+        let mut driver = AsyncMax7219::new(&mut spi).with_device_count(2).unwrap();
+
+        block_on(driver.test_all(true)).unwrap();
+        block_on(driver.test_device(0, false)).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_scan_limit() {
+        let mut spi = SpiMock::new(&frames(&[
+            vec![Register::ScanLimit.addr(), 3],
+            vec![Register::ScanLimit.addr(), 7],
+        ]));
+        let mut driver = AsyncMax7219::new(&mut spi);
+
+        block_on(driver.set_scan_limit_all(4)).unwrap();
+        block_on(driver.set_device_scan_limit(0, 8)).unwrap();
+        for limit in [0, 9] {
+            assert_eq!(
+                block_on(driver.set_scan_limit_all(limit)),
+                Err(Error::InvalidScanLimit)
+            );
+            assert_eq!(
+                block_on(driver.set_device_scan_limit(0, limit)),
+                Err(Error::InvalidScanLimit)
+            );
+        }
+        spi.done();
+    }
+
+    #[test]
+    fn test_decode_mode() {
+        let all = DecodeMode::AllDigits.value();
+        let mut spi = SpiMock::new(&frames(&[
+            vec![
+                Register::DecodeMode.addr(),
+                all,
+                Register::DecodeMode.addr(),
+                all,
+            ],
+            vec![
+                0x00,
+                0x00,
+                Register::DecodeMode.addr(),
+                DecodeMode::Digits0To3.value(),
+            ],
+        ]));
+        let mut driver = AsyncMax7219::new(&mut spi).with_device_count(2).unwrap();
+
+        block_on(driver.set_decode_mode_all(DecodeMode::AllDigits)).unwrap();
+        block_on(driver.set_device_decode_mode(1, DecodeMode::Digits0To3)).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_clear_display_and_all() {
+        let mut expected: Vec<Vec<u8>> = Register::digits()
+            .map(|digit_register| vec![0x00, 0x00, digit_register.addr(), 0x00])
+            .collect();
+        expected.extend(
+            Register::digits().map(|digit_register| {
+                vec![digit_register.addr(), 0x00, digit_register.addr(), 0x00]
+            }),
+        );
+        let mut spi = SpiMock::new(&frames(&expected));
+        let mut driver = AsyncMax7219::new(&mut spi).with_device_count(2).unwrap();
+
+        block_on(driver.clear_display(1)).unwrap();
+        block_on(driver.clear_all()).unwrap();
+        assert_eq!(
+            block_on(driver.clear_display(2)),
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_raw_digit() {
+        let mut spi = SpiMock::new(&frames(&[vec![Register::Digit3.addr(), 0b1010_1010]]));
+        let mut driver = AsyncMax7219::new(&mut spi);
+
+        block_on(driver.write_raw_digit(0, 3, 0b1010_1010)).unwrap();
+        assert_eq!(
+            block_on(driver.write_raw_digit(0, 8, 0x00)),
+            Err(Error::InvalidDigit { digit: 8 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_intensity() {
+        let mut spi = SpiMock::new(&frames(&[
+            vec![Register::Intensity.addr(), 0x0A, 0x00, 0x00],
+            vec![
+                Register::Intensity.addr(),
+                0x05,
+                Register::Intensity.addr(),
+                0x05,
+            ],
+        ]));
+        let mut driver = AsyncMax7219::new(&mut spi).with_device_count(2).unwrap();
+
+        block_on(driver.set_intensity(0, 0x0A)).unwrap();
+        block_on(driver.set_intensity_all(0x05)).unwrap();
+        assert_eq!(
+            block_on(driver.set_intensity(0, 0x10)),
+            Err(Error::InvalidIntensity)
+        );
+        assert_eq!(
+            block_on(driver.set_intensity_all(0x10)),
+            Err(Error::InvalidIntensity)
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_registers() {
+        let mut spi = SpiMock::new(&frames(&[
+            vec![Register::Shutdown.addr(), 0x01, 0x00, 0x00],
+            vec![
+                Register::Intensity.addr(),
+                0x01,
+                Register::Intensity.addr(),
+                0x01,
+            ],
+        ]));
+        let mut driver = AsyncMax7219::new(&mut spi).with_device_count(2).unwrap();
+
+        block_on(driver.write_device_register(0, Register::Shutdown, 0x01)).unwrap();
+        block_on(
+            driver.write_all_registers(&[(Register::Intensity, 0x01), (Register::Intensity, 0x01)]),
+        )
+        .unwrap();
+        assert_eq!(
+            block_on(driver.write_device_register(2, Register::Shutdown, 0x01)),
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_init() {
+        let mut expected = vec![
+            vec![Register::NoOp.addr(), 0x00],
+            vec![Register::Shutdown.addr(), 0x01],
+            vec![Register::DisplayTest.addr(), 0x00],
+            vec![Register::ScanLimit.addr(), NUM_DIGITS - 1],
+            vec![Register::DecodeMode.addr(), DecodeMode::NoDecode.value()],
+            vec![Register::Intensity.addr(), DEFAULT_INITIAL_INTENSITY],
+        ];
+        expected.extend(Register::digits().map(|digit_register| vec![digit_register.addr(), 0x00]));
+        let mut spi = SpiMock::new(&frames(&expected));
+        let mut driver = AsyncMax7219::new(&mut spi);
+
+        block_on(driver.init()).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_init_without_initial_intensity() {
+        let mut expected = vec![
+            vec![Register::NoOp.addr(), 0x00],
+            vec![Register::Shutdown.addr(), 0x01],
+            vec![Register::DisplayTest.addr(), 0x00],
+            vec![Register::ScanLimit.addr(), NUM_DIGITS - 1],
+            vec![Register::DecodeMode.addr(), DecodeMode::NoDecode.value()],
+        ];
+        expected.extend(Register::digits().map(|digit_register| vec![digit_register.addr(), 0x00]));
+        let mut spi = SpiMock::new(&frames(&expected));
+        let mut driver = AsyncMax7219::new(&mut spi)
+            .with_initial_intensity(None)
+            .unwrap();
+
+        block_on(driver.init()).unwrap();
+        assert!(matches!(
+            AsyncMax7219::new(&mut spi).with_initial_intensity(Some(0x10)),
+            Err(Error::InvalidIntensity)
+        ));
+        spi.done();
+    }
+}
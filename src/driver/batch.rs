@@ -0,0 +1,284 @@
+//! Grouping several register writes into a single validated batch
+//!
+//! The MAX7219 latches the 16 bits sitting in its shift register when chip select
+//! rises, so every chain frame must travel in its own SPI transaction. Sending two
+//! frames inside one transaction would shift the first one straight out of the
+//! chain before it is latched. A [`Batch`] therefore cannot merge frames into one
+//! transaction; instead it saves traffic by packing writes that target different
+//! devices into the same frame, and it validates every write before anything is
+//! put on the bus.
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{MAX_DISPLAYS, Max7219, Result, driver::frame, error::Error, registers::Register};
+
+/// Maximum number of chain frames a single [`Batch`] can hold.
+pub const BATCH_CAPACITY: usize = 16;
+
+/// One `(register, data)` pair per device in the chain.
+type Frame<const N: usize> = [(Register, u8); N];
+
+/// A queue of register writes collected by [`Max7219::batch`].
+///
+/// Writes are kept in the order they were queued. A single-device write is merged
+/// into the most recent frame when that frame has not addressed the device yet, so
+/// updating the same register on every device costs one transaction instead of one
+/// per device.
+///
+/// `N` is the longest chain of the driver that created the batch.
+pub struct Batch<const N: usize = MAX_DISPLAYS> {
+    frames: [Frame<N>; BATCH_CAPACITY],
+    len: usize,
+    device_count: usize,
+}
+
+impl<const N: usize> Batch<N> {
+    const EMPTY_FRAME: Frame<N> = [(Register::NoOp, 0x00); N];
+
+    fn new(device_count: usize) -> Self {
+        Self {
+            frames: [Self::EMPTY_FRAME; BATCH_CAPACITY],
+            len: 0,
+            device_count,
+        }
+    }
+
+    /// Returns the number of chain frames queued so far.
+    ///
+    /// Each frame becomes one SPI transaction when the batch is sent.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been queued.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Queues a write of `data` to `register` on a single device.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range, or
+    /// `Error::BatchFull` if a new frame is needed and the batch already holds
+    /// [`BATCH_CAPACITY`] frames.
+    pub fn write(&mut self, device_index: usize, register: Register, data: u8) -> Result<()> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                count: self.device_count,
+            });
+        }
+
+        let reuse_last = frame::active(&self.frames, self.len)
+            .last()
+            .and_then(|last| last.get(device_index))
+            .is_some_and(|&(register, _)| register == Register::NoOp);
+        let frame = if reuse_last {
+            self.last_frame_mut()
+        } else {
+            Some(self.push_frame()?)
+        };
+
+        if let Some(op) = frame.and_then(|frame| frame.get_mut(device_index)) {
+            *op = (register, data);
+        }
+        Ok(())
+    }
+
+    /// Queues a write of `data` to `register` on every device in the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::BatchFull` if the batch already holds [`BATCH_CAPACITY`] frames.
+    pub fn write_all(&mut self, register: Register, data: u8) -> Result<()> {
+        *self.push_frame()? = [(register, data); N];
+        Ok(())
+    }
+
+    /// Appends an empty frame and returns it.
+    fn push_frame(&mut self) -> Result<&mut Frame<N>> {
+        let frame = self.frames.get_mut(self.len).ok_or(Error::BatchFull)?;
+        *frame = Self::EMPTY_FRAME;
+        self.len += 1;
+        Ok(frame)
+    }
+
+    /// The most recently queued frame, `None` if the batch is empty.
+    fn last_frame_mut(&mut self) -> Option<&mut Frame<N>> {
+        let last = self.len.checked_sub(1)?;
+        self.frames.get_mut(last)
+    }
+}
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Collects register writes in a [`Batch`] and sends them once the closure returns.
+    ///
+    /// Nothing is written if the closure returns an error, so a bad device index or
+    /// an overfull batch leaves the displays untouched. Once validated, each queued
+    /// frame is sent as its own SPI transaction, in the order it was queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error produced by the closure, or an SPI error if one of
+    /// the transfers fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.batch(|b| {
+    ///     b.write_all(Register::Shutdown, 0x01)?;
+    ///     b.write(0, Register::Intensity, 0x02)?;
+    ///     b.write(1, Register::Intensity, 0x0F)?; // shares a frame with device 0
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub fn batch<F>(&mut self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Batch<N>) -> Result<()>,
+    {
+        let mut batch = Batch::new(self.device_count());
+        f(&mut batch)?;
+
+        let count = self.device_count();
+        for ops in batch.frames.iter().take(batch.len) {
+            self.write_all_registers(frame::active(ops, count))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::{spi::Mock as SpiMock, spi::Transaction};
+
+    #[test]
+    fn test_batch_merges_device_writes() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::Intensity.addr(),
+                0x02,
+                Register::Intensity.addr(),
+                0x0F,
+            ]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        driver
+            .batch(|b| {
+                b.write(0, Register::Intensity, 0x02)?;
+                b.write(1, Register::Intensity, 0x0F)?;
+                assert_eq!(b.len(), 1);
+                Ok(())
+            })
+            .unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_batch_keeps_order() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Shutdown.addr(), 0x01, 0x00, 0x00]),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::DisplayTest.addr(),
+                0x00,
+                Register::DisplayTest.addr(),
+                0x00,
+            ]),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Digit0.addr(), 0xAA, 0x00, 0x00]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        driver
+            .batch(|b| {
+                b.write(0, Register::Shutdown, 0x01)?;
+                b.write_all(Register::DisplayTest, 0x00)?;
+                b.write(0, Register::Digit0, 0xAA)
+            })
+            .unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_batch_same_device_needs_new_frame() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Digit0.addr(), 0x01]),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Digit1.addr(), 0x02]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi);
+
+        driver
+            .batch(|b| {
+                b.write(0, Register::Digit0, 0x01)?;
+                b.write(0, Register::Digit1, 0x02)
+            })
+            .unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_batch_error_sends_nothing() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi);
+
+        let result = driver.batch(|b| {
+            b.write_all(Register::Shutdown, 0x01)?;
+            b.write(1, Register::Intensity, 0x05)
+        });
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 1, count: 1 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_batch_full() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi);
+
+        let result = driver.batch(|b| {
+            for _ in 0..BATCH_CAPACITY {
+                b.write_all(Register::Digit0, 0x00)?;
+            }
+            assert_eq!(b.len(), BATCH_CAPACITY);
+            b.write_all(Register::Digit0, 0x00)
+        });
+        assert_eq!(result, Err(Error::BatchFull));
+        spi.done();
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi);
+
+        driver
+            .batch(|b| {
+                assert!(b.is_empty());
+                Ok(())
+            })
+            .unwrap();
+        spi.done();
+    }
+}
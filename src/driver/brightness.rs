@@ -0,0 +1,441 @@
+//! Brightness helpers on top of the intensity register
+//!
+//! A chain can mix module types, e.g. a few matrix modules followed by a
+//! 7-segment module. Each type looks its best at a different intensity, so a
+//! single `set_intensity_all` makes one of them too bright or too dim. A
+//! [`BrightnessController`] remembers which devices belong to which display and
+//! how bright each one should be relative to the others, and dims the whole
+//! chain from one master level.
+//!
+//! [`Max7219::auto_brightness_tick`] follows an ambient light reading instead,
+//! e.g. from an LDR on an ADC, mapped to a level through a [`BrightnessCurve`].
+
+use core::ops::Range;
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{Max7219, Result, driver::frame, error::Error, registers::Register};
+
+/// Maximum number of segments a [`BrightnessController`] can hold.
+pub const MAX_SEGMENTS: usize = 4;
+
+/// Maximum number of points of a [`BrightnessCurve`].
+pub const MAX_CURVE_POINTS: usize = 8;
+
+/// Devices `start..end` of the chain, at `weight` percent of the master level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Segment {
+    start: usize,
+    end: usize,
+    weight: u8,
+}
+
+/// Scales one master brightness into per-device intensities.
+///
+/// Each segment is a range of device indices with a weight in percent: at a
+/// master level of `p` percent, its devices get `p * weight / 100` percent of the
+/// full intensity `0x0F`, rounded to the nearest level. Devices outside every
+/// segment are left alone.
+///
+/// The MAX7219 cannot switch its LEDs off through the intensity register, so
+/// 0 percent means the dimmest level, not dark.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Four matrix modules, then a 7-segment module that looks brighter
+/// let brightness = BrightnessController::new()
+///     .with_segment(0..4, 100)?
+///     .with_segment(4..5, 40)?;
+///
+/// brightness.set_master_brightness(matrix.driver(), 60)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BrightnessController {
+    segments: [Option<Segment>; MAX_SEGMENTS],
+}
+
+impl BrightnessController {
+    /// Creates a controller without any segments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the devices in `devices` as a segment at `weight` percent of the
+    /// master level.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValueOutOfRange` if `weight > 100`, the range is empty or
+    /// overlaps an earlier segment, or the controller already holds
+    /// [`MAX_SEGMENTS`] segments.
+    pub fn with_segment(mut self, devices: Range<usize>, weight: u8) -> Result<Self> {
+        if weight > 100 || devices.is_empty() {
+            return Err(Error::ValueOutOfRange);
+        }
+        let overlaps = self
+            .segments()
+            .any(|segment| devices.start < segment.end && segment.start < devices.end);
+        if overlaps {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let slot = self
+            .segments
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(Error::ValueOutOfRange)?;
+        *slot = Some(Segment {
+            start: devices.start,
+            end: devices.end,
+            weight,
+        });
+        Ok(self)
+    }
+
+    /// Returns the intensity the device at `device_index` gets at a master level
+    /// of `percent`, or `None` if it belongs to no segment.
+    ///
+    /// Levels above 100 percent count as 100.
+    pub fn intensity(&self, device_index: usize, percent: u8) -> Option<u8> {
+        self.segments()
+            .find(|segment| (segment.start..segment.end).contains(&device_index))
+            .map(|segment| scaled_intensity(percent.min(100), segment.weight))
+    }
+
+    /// Writes the intensity of every segment for a master level of `percent`.
+    ///
+    /// All devices are updated in one SPI transaction; devices outside every
+    /// segment receive no-ops and keep their intensity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValueOutOfRange` if `percent > 100`,
+    /// `Error::InvalidDeviceIndex` if a segment reaches past the driver's device
+    /// count, or an SPI error.
+    pub fn set_master_brightness<SPI, const N: usize>(
+        &self,
+        driver: &mut Max7219<SPI, N>,
+        percent: u8,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        if percent > 100 {
+            return Err(Error::ValueOutOfRange);
+        }
+        let count = driver.device_count();
+        if let Some(segment) = self.segments().find(|segment| segment.end > count) {
+            return Err(Error::InvalidDeviceIndex {
+                index: segment.end - 1,
+                count,
+            });
+        }
+
+        let mut ops = [(Register::NoOp, 0x00); N];
+        for segment in self.segments() {
+            let level = scaled_intensity(percent, segment.weight);
+            for op in ops.iter_mut().take(segment.end).skip(segment.start) {
+                *op = (Register::Intensity, level);
+            }
+        }
+        driver.write_all_registers(frame::active(&ops, count))
+    }
+
+    fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.segments.iter().flatten().copied()
+    }
+}
+
+/// Maps an ambient light reading to an intensity level.
+///
+/// The curve is a list of `(reading, level)` points with rising readings.
+/// Readings between two points are interpolated linearly and rounded to the
+/// nearest level; readings outside the curve get the level of the closest end.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Dim quickly in the dark, and only reach full brightness in sunlight
+/// let curve = BrightnessCurve::new(&[(0, 0x00), (400, 0x04), (4095, 0x0F)])?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BrightnessCurve {
+    points: [(u16, u8); MAX_CURVE_POINTS],
+    len: usize,
+}
+
+impl BrightnessCurve {
+    /// Creates a curve from `(reading, level)` points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` if a level is above `0x0F`, or
+    /// `Error::ValueOutOfRange` if there are no points, more than
+    /// [`MAX_CURVE_POINTS`], or the readings do not strictly rise.
+    pub fn new(points: &[(u16, u8)]) -> Result<Self> {
+        if points.is_empty() || points.len() > MAX_CURVE_POINTS {
+            return Err(Error::ValueOutOfRange);
+        }
+        if points.iter().any(|&(_, level)| level > 0x0F) {
+            return Err(Error::InvalidIntensity);
+        }
+        if points
+            .windows(2)
+            .any(|pair| matches!(pair, [(low, _), (high, _)] if low >= high))
+        {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let mut curve = Self {
+            points: [(0, 0); MAX_CURVE_POINTS],
+            len: points.len(),
+        };
+        for (point, &given) in curve.points.iter_mut().zip(points) {
+            *point = given;
+        }
+        Ok(curve)
+    }
+
+    /// A straight line from the lowest level at `dark` to `0x0F` at `bright`.
+    ///
+    /// If `dark` is not below `bright`, the curve jumps from the lowest level to
+    /// `0x0F` at `bright`.
+    pub const fn linear(dark: u16, bright: u16) -> Self {
+        let mut points = [(0, 0); MAX_CURVE_POINTS];
+        if bright == 0 {
+            points[0] = (0, 0x0F);
+            return Self { points, len: 1 };
+        }
+        let dark = if dark < bright { dark } else { bright - 1 };
+        points[0] = (dark, 0x00);
+        points[1] = (bright, 0x0F);
+        Self { points, len: 2 }
+    }
+
+    /// Returns the intensity level for an ambient reading.
+    pub fn level(&self, reading: u16) -> u8 {
+        let points = self.points.get(..self.len).unwrap_or(&self.points);
+        let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+            return 0x00;
+        };
+        if reading <= first.0 {
+            return first.1;
+        }
+        if reading >= last.0 {
+            return last.1;
+        }
+
+        let segment = points.windows(2).find_map(|pair| match *pair {
+            [lower, upper] if upper.0 >= reading => Some((lower, upper)),
+            _ => None,
+        });
+        let Some(((x0, y0), (x1, y1))) = segment else {
+            return last.1;
+        };
+        let span = i32::from(x1 - x0);
+        let rise = (i32::from(y1) - i32::from(y0)) * i32::from(reading - x0);
+        // Round half away from zero so falling segments mirror rising ones
+        let step = (2 * rise + rise.signum() * span) / (2 * span);
+        (i32::from(y0) + step) as u8
+    }
+}
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Follows an ambient light reading, writing a new intensity only when it is
+    /// worth it.
+    ///
+    /// The reading is mapped to a level through `curve`. If every device is
+    /// already within `hysteresis` levels of it, nothing is sent, so a reading
+    /// that flickers around a step does not make the display flicker. Otherwise
+    /// the level is written to every device in one transaction. The current
+    /// levels come from the driver's record of its writes, see [`Self::intensity`].
+    ///
+    /// Returns whether a write occurred. Call it whenever a new reading is
+    /// available, e.g. once a second.
+    ///
+    /// # Errors
+    ///
+    /// Returns an SPI error if the write fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let curve = BrightnessCurve::linear(50, 3000);
+    /// loop {
+    ///     let ambient = adc.read(&mut ldr_pin)?;
+    ///     driver.auto_brightness_tick(ambient, &curve, 1)?;
+    ///     delay.delay_ms(1000);
+    /// }
+    /// ```
+    pub fn auto_brightness_tick(
+        &mut self,
+        ambient: u16,
+        curve: &BrightnessCurve,
+        hysteresis: u8,
+    ) -> Result<bool> {
+        let level = curve.level(ambient);
+        let settled = (0..self.device_count()).all(|device_index| {
+            self.intensity(device_index)
+                .is_some_and(|current| current.abs_diff(level) <= hysteresis)
+        });
+        if settled {
+            return Ok(false);
+        }
+        self.set_intensity_all(level)?;
+        Ok(true)
+    }
+}
+
+/// Intensity level for `weight` percent of `percent` percent, rounded.
+fn scaled_intensity(percent: u8, weight: u8) -> u8 {
+    let scaled = u32::from(percent) * u32::from(weight) * 0x0F;
+    ((scaled + 5_000) / 10_000) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::FrameRecorder;
+
+    fn controller() -> BrightnessController {
+        BrightnessController::new()
+            .with_segment(0..3, 100)
+            .unwrap()
+            .with_segment(3..4, 40)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scaled_intensity() {
+        assert_eq!(scaled_intensity(100, 100), 0x0F);
+        assert_eq!(scaled_intensity(0, 100), 0x00);
+        assert_eq!(scaled_intensity(60, 100), 9);
+        assert_eq!(scaled_intensity(60, 40), 4);
+        assert_eq!(scaled_intensity(100, 40), 6);
+    }
+
+    #[test]
+    fn test_set_master_brightness_weights_segments() {
+        let brightness = controller();
+        assert_eq!(brightness.intensity(1, 60), Some(9));
+        assert_eq!(brightness.intensity(3, 60), Some(4));
+        assert_eq!(brightness.intensity(4, 60), None);
+
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(5).unwrap();
+        brightness.set_master_brightness(&mut driver, 60).unwrap();
+        brightness.set_master_brightness(&mut driver, 100).unwrap();
+
+        // One transaction per call, and the unassigned device is left alone
+        assert_eq!(
+            recorder.frames(),
+            [
+                vec![
+                    (0, Register::Intensity, 9),
+                    (1, Register::Intensity, 9),
+                    (2, Register::Intensity, 9),
+                    (3, Register::Intensity, 4),
+                ],
+                vec![
+                    (0, Register::Intensity, 15),
+                    (1, Register::Intensity, 15),
+                    (2, Register::Intensity, 15),
+                    (3, Register::Intensity, 6),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_brightness_curve_levels() {
+        let curve = BrightnessCurve::new(&[(100, 0x02), (1100, 0x0C), (2100, 0x0F)]).unwrap();
+        assert_eq!(curve.level(0), 0x02);
+        assert_eq!(curve.level(100), 0x02);
+        assert_eq!(curve.level(600), 0x07);
+        assert_eq!(curve.level(1100), 0x0C);
+        assert_eq!(curve.level(1433), 0x0D);
+        assert_eq!(curve.level(u16::MAX), 0x0F);
+
+        let falling = BrightnessCurve::new(&[(0, 0x0F), (1000, 0x00)]).unwrap();
+        assert_eq!(falling.level(500), 0x07);
+        assert_eq!(falling.level(566), 0x07);
+        assert_eq!(falling.level(600), 0x06);
+
+        let linear = BrightnessCurve::linear(0, 4095);
+        assert_eq!(linear.level(0), 0x00);
+        assert_eq!(linear.level(2048), 0x08);
+        assert_eq!(linear.level(4095), 0x0F);
+        assert_eq!(BrightnessCurve::linear(10, 10).level(9), 0x00);
+        assert_eq!(BrightnessCurve::linear(10, 10).level(10), 0x0F);
+
+        assert_eq!(BrightnessCurve::new(&[]), Err(Error::ValueOutOfRange));
+        assert_eq!(
+            BrightnessCurve::new(&[(10, 0), (10, 5)]),
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            BrightnessCurve::new(&[(0, 0x10)]),
+            Err(Error::InvalidIntensity)
+        );
+    }
+
+    #[test]
+    fn test_auto_brightness_tick() {
+        let curve = BrightnessCurve::linear(0, 1500);
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+
+        // Levels 10, 10, 11, 12, 4, 4 and 5
+        let readings = [1000, 1020, 1100, 1200, 400, 380, 500];
+        let written: Vec<bool> = readings
+            .iter()
+            .map(|&ambient| driver.auto_brightness_tick(ambient, &curve, 1).unwrap())
+            .collect();
+        assert_eq!(written, [true, false, false, true, true, false, false]);
+        assert_eq!(driver.intensity(0), Some(4));
+
+        let levels: Vec<u8> = recorder.frames().iter().map(|frame| frame[0].2).collect();
+        assert_eq!(levels, [10, 12, 4]);
+        assert!(
+            recorder
+                .frames()
+                .iter()
+                .all(|frame| frame.len() == 2 && frame[0].1 == Register::Intensity)
+        );
+    }
+
+    #[test]
+    fn test_invalid_segments() {
+        assert_eq!(
+            controller().with_segment(2..5, 100),
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            BrightnessController::new().with_segment(0..0, 100),
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            BrightnessController::new().with_segment(0..1, 101),
+            Err(Error::ValueOutOfRange)
+        );
+
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+        assert_eq!(
+            controller().set_master_brightness(&mut driver, 50),
+            Err(Error::InvalidDeviceIndex { index: 3, count: 3 })
+        );
+        assert_eq!(
+            controller().set_master_brightness(&mut driver, 101),
+            Err(Error::ValueOutOfRange)
+        );
+        assert!(recorder.frames().is_empty());
+    }
+}
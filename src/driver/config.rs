@@ -0,0 +1,238 @@
+//! Complete settings of one device, for chains mixing module types
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{
+    Max7219, NUM_DIGITS, Result,
+    driver::{frame, max7219::DEFAULT_INITIAL_INTENSITY},
+    error::Error,
+    registers::{DecodeMode, Register},
+};
+
+/// What [`Max7219::configure_device`] writes to one device.
+///
+/// A chain can mix module types, e.g. a 4-digit seven-segment display decoding
+/// Code B next to LED matrices that scan all 8 rows raw. The default is the
+/// setup of [`Max7219::init`]: powered on, all 8 digits scanned without decoding
+/// at [`DEFAULT_INITIAL_INTENSITY`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let digits = DeviceConfig {
+///     decode_mode: DecodeMode::Digits0To3,
+///     scan_limit: 4,
+///     ..DeviceConfig::default()
+/// };
+/// driver.configure_all(&[digits, DeviceConfig::default(), DeviceConfig::default()])?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceConfig {
+    /// Intensity, `0x00` to `0x0F`, checked against the driver's
+    /// [`ClampPolicy`](crate::driver::ClampPolicy).
+    pub intensity: u8,
+    /// Number of digits scanned, 1 to 8.
+    pub scan_limit: u8,
+    /// Digits decoded as Code B.
+    pub decode_mode: DecodeMode,
+    /// Whether the device is on, or in shutdown.
+    pub power: bool,
+}
+
+impl DeviceConfig {
+    /// Creates the default configuration.
+    pub const fn new() -> Self {
+        Self {
+            intensity: DEFAULT_INITIAL_INTENSITY,
+            scan_limit: NUM_DIGITS,
+            decode_mode: DecodeMode::NoDecode,
+            power: true,
+        }
+    }
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Registers written for a configuration, in order. Shutdown comes last, so a
+/// device is only switched on once the rest of its setup is in place.
+const CONFIG_REGISTERS: [Register; 4] = [
+    Register::DecodeMode,
+    Register::ScanLimit,
+    Register::Intensity,
+    Register::Shutdown,
+];
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Applies a full configuration to one device, leaving the others as they are.
+    ///
+    /// Writes the decode mode, scan limit, intensity and power state, one
+    /// transaction each.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range,
+    /// `Error::InvalidScanLimit` or `Error::InvalidIntensity` for a bad setting,
+    /// all before anything is sent, or an SPI error.
+    pub fn configure_device(&mut self, device_index: usize, config: &DeviceConfig) -> Result<()> {
+        frame::check_device_index(device_index, self.device_count())?;
+        let values = self.config_values(config)?;
+        for (register, data) in CONFIG_REGISTERS.into_iter().zip(values) {
+            self.write_device_register(device_index, register, data)?;
+        }
+        Ok(())
+    }
+
+    /// Applies `configs[i]` to device `i`, one transaction per register for the
+    /// whole chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if `configs` does not have one entry
+    /// per device, `Error::InvalidScanLimit` or `Error::InvalidIntensity` for a
+    /// bad setting, all before anything is sent, or an SPI error.
+    pub fn configure_all(&mut self, configs: &[DeviceConfig]) -> Result<()> {
+        let count = self.device_count();
+        if configs.len() != count {
+            return Err(Error::InvalidDeviceCount);
+        }
+        let mut values = [[0u8; 4]; N];
+        for (device_values, config) in values.iter_mut().zip(configs) {
+            *device_values = self.config_values(config)?;
+        }
+
+        for (step, register) in CONFIG_REGISTERS.into_iter().enumerate() {
+            let mut ops = [(Register::NoOp, 0x00); N];
+            for (op, device_values) in ops.iter_mut().zip(&values).take(count) {
+                *op = (register, device_values.get(step).copied().unwrap_or(0x00));
+            }
+            self.write_all_registers(frame::active(&ops, count))?;
+        }
+        Ok(())
+    }
+
+    /// Checks `config`, returning the data of each of [`CONFIG_REGISTERS`].
+    fn config_values(&self, config: &DeviceConfig) -> Result<[u8; 4]> {
+        Ok([
+            config.decode_mode as u8,
+            frame::scan_limit_value(config.scan_limit)?,
+            self.checked_intensity(config.intensity)?,
+            u8::from(config.power),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::FrameRecorder;
+
+    const DIGITS: DeviceConfig = DeviceConfig {
+        intensity: 0x03,
+        scan_limit: 4,
+        decode_mode: DecodeMode::Digits0To3,
+        power: true,
+    };
+
+    const MATRIX: DeviceConfig = DeviceConfig {
+        intensity: 0x0A,
+        scan_limit: 8,
+        decode_mode: DecodeMode::NoDecode,
+        power: true,
+    };
+
+    #[test]
+    fn test_configure_all_mixed_chain() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        driver.configure_all(&[DIGITS, MATRIX]).unwrap();
+
+        assert_eq!(driver.decode_mode(0), Some(DecodeMode::Digits0To3));
+        assert_eq!(driver.scan_limit(0), Some(4));
+        assert_eq!(driver.decode_mode(1), Some(DecodeMode::NoDecode));
+        assert_eq!(driver.scan_limit(1), Some(8));
+        assert_eq!(driver.intensity(1), Some(0x0A));
+        assert!(driver.is_powered());
+
+        assert_eq!(
+            recorder.frames(),
+            [
+                vec![
+                    (0, Register::DecodeMode, 0x0F),
+                    (1, Register::DecodeMode, 0x00)
+                ],
+                vec![
+                    (0, Register::ScanLimit, 0x03),
+                    (1, Register::ScanLimit, 0x07)
+                ],
+                vec![
+                    (0, Register::Intensity, 0x03),
+                    (1, Register::Intensity, 0x0A)
+                ],
+                vec![(0, Register::Shutdown, 0x01), (1, Register::Shutdown, 0x01)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_configure_device() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let off = DeviceConfig {
+            power: false,
+            ..DIGITS
+        };
+        driver.configure_device(1, &off).unwrap();
+
+        assert_eq!(driver.scan_limit(1), Some(4));
+        assert!(!driver.is_device_powered(1));
+        assert_eq!(
+            recorder.writes(),
+            [
+                (1, Register::DecodeMode, 0x0F),
+                (1, Register::ScanLimit, 0x03),
+                (1, Register::Intensity, 0x03),
+                (1, Register::Shutdown, 0x00),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_configure_rejects_before_sending() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let bad_limit = DeviceConfig {
+            scan_limit: 0,
+            ..MATRIX
+        };
+        let bad_intensity = DeviceConfig {
+            intensity: 0x10,
+            ..MATRIX
+        };
+
+        assert_eq!(
+            driver.configure_all(&[DIGITS, bad_limit]),
+            Err(Error::InvalidScanLimit)
+        );
+        assert_eq!(
+            driver.configure_all(&[DIGITS]),
+            Err(Error::InvalidDeviceCount)
+        );
+        assert_eq!(
+            driver.configure_device(0, &bad_intensity),
+            Err(Error::InvalidIntensity)
+        );
+        assert_eq!(
+            driver.configure_device(2, &MATRIX),
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
+        assert!(recorder.frames().is_empty());
+    }
+}
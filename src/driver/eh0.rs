@@ -0,0 +1,181 @@
+//! Adapter for HALs that only implement the embedded-hal 0.2 traits
+//!
+//! [`Eh0SpiDevice`] combines an embedded-hal 0.2 blocking SPI `Write<u8>` bus with a
+//! chip-select `OutputPin` into an embedded-hal 1.0 [`SpiDevice`], so the regular
+//! [`Max7219`] driver can run on top of it unchanged.
+
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+use embedded_hal_0::{blocking::spi::Write, digital::v2::OutputPin};
+
+use crate::Max7219;
+
+/// Errors produced by [`Eh0SpiDevice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eh0Error<SpiE, PinE> {
+    /// The SPI bus reported an error while writing.
+    Spi(SpiE),
+    /// The chip-select pin could not be driven.
+    ChipSelect(PinE),
+    /// The requested operation needs more than a blocking `Write<u8>` bus.
+    ///
+    /// [`Max7219`] only ever issues writes, so this only happens when the device is
+    /// used directly for reads, transfers or delays.
+    Unsupported,
+}
+
+impl<SpiE, PinE> embedded_hal::spi::Error for Eh0Error<SpiE, PinE>
+where
+    SpiE: core::fmt::Debug,
+    PinE: core::fmt::Debug,
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Eh0Error::ChipSelect(_) => ErrorKind::ChipSelectFault,
+            Eh0Error::Spi(_) | Eh0Error::Unsupported => ErrorKind::Other,
+        }
+    }
+}
+
+/// An embedded-hal 1.0 [`SpiDevice`] built from an embedded-hal 0.2 SPI bus and a
+/// chip-select pin.
+///
+/// Chip select is driven low for the duration of each transaction and released
+/// afterwards, even if the write fails.
+pub struct Eh0SpiDevice<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+impl<SPI, CS> Eh0SpiDevice<SPI, CS> {
+    /// Wraps an embedded-hal 0.2 SPI bus and its chip-select pin.
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        Self { spi, cs }
+    }
+
+    /// Returns the wrapped SPI bus and chip-select pin.
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+}
+
+impl<SPI, CS> ErrorType for Eh0SpiDevice<SPI, CS>
+where
+    SPI: Write<u8>,
+    SPI::Error: core::fmt::Debug,
+    CS: OutputPin,
+    CS::Error: core::fmt::Debug,
+{
+    type Error = Eh0Error<SPI::Error, CS::Error>;
+}
+
+impl<SPI, CS> SpiDevice for Eh0SpiDevice<SPI, CS>
+where
+    SPI: Write<u8>,
+    SPI::Error: core::fmt::Debug,
+    CS: OutputPin,
+    CS::Error: core::fmt::Debug,
+{
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), Self::Error> {
+        self.cs.set_low().map_err(Eh0Error::ChipSelect)?;
+
+        let result = operations.iter_mut().try_for_each(|op| match op {
+            Operation::Write(words) => self.spi.write(words).map_err(Eh0Error::Spi),
+            _ => Err(Eh0Error::Unsupported),
+        });
+
+        // Always release chip select so the chip latches (or drops) the frame
+        let cs_result = self.cs.set_high().map_err(Eh0Error::ChipSelect);
+
+        result.and(cs_result)
+    }
+}
+
+impl<SPI, CS> Max7219<Eh0SpiDevice<SPI, CS>>
+where
+    SPI: Write<u8>,
+    SPI::Error: core::fmt::Debug,
+    CS: OutputPin,
+    CS::Error: core::fmt::Debug,
+{
+    /// Creates a driver from an embedded-hal 0.2 SPI bus and chip-select (LOAD) pin.
+    ///
+    /// This is a shorthand for `Max7219::new(Eh0SpiDevice::new(spi, cs))`; everything
+    /// else works exactly like a driver built on an embedded-hal 1.0 `SpiDevice`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut driver = Max7219::from_eh0(spi, cs).with_device_count(4)?;
+    /// driver.init()?;
+    /// ```
+    pub fn from_eh0(spi: SPI, cs: CS) -> Self {
+        Max7219::new(Eh0SpiDevice::new(spi, cs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Register};
+    use embedded_hal_mock::eh0::{
+        MockError,
+        digital::{Mock as PinMock, State, Transaction as PinTransaction},
+        spi::{Mock as SpiMock, Transaction as SpiTransaction},
+    };
+
+    #[test]
+    fn test_from_eh0_write() {
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::write(vec![Register::Shutdown.addr(), 0x01]),
+            SpiTransaction::write(vec![Register::Intensity.addr(), 0x03]),
+        ]);
+        let mut cs = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+
+        let mut driver = Max7219::from_eh0(spi.clone(), cs.clone());
+        driver.power_on().expect("power on");
+        driver.set_intensity(0, 0x03).expect("intensity");
+
+        spi.done();
+        cs.done();
+    }
+
+    #[test]
+    fn test_unsupported_operation_releases_chip_select() {
+        let mut spi = SpiMock::new(&[]);
+        let mut cs = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+
+        let mut device = Eh0SpiDevice::new(spi.clone(), cs.clone());
+        let mut read_buf = [0u8; 2];
+        let result = device.transaction(&mut [Operation::Read(&mut read_buf)]);
+        assert_eq!(result, Err(Eh0Error::Unsupported));
+
+        spi.done();
+        cs.done();
+    }
+
+    #[test]
+    fn test_chip_select_error_maps_to_spi_error() {
+        let mut spi = SpiMock::new(&[]);
+        let mut cs =
+            PinMock::new(&[PinTransaction::set(State::Low)
+                .with_error(MockError::Io(std::io::ErrorKind::Other))]);
+
+        let mut driver = Max7219::from_eh0(spi.clone(), cs.clone());
+        let result = driver.power_on();
+        assert_eq!(result, Err(Error::SpiError(ErrorKind::ChipSelectFault)));
+
+        spi.done();
+        cs.done();
+    }
+}
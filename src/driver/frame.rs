@@ -0,0 +1,110 @@
+//! Frame building and validation shared by the blocking and async drivers
+
+use super::{init::InitConfig, max7219::ChainOrder};
+use crate::{NUM_DIGITS, Result, error::Error, registers::Register};
+
+/// Fills `buffer` with a frame of exactly `packets` packets and returns it.
+///
+/// The packet of device `i`, placed in the frame by `order`, carries `ops[i]`;
+/// packets without an op are explicit no-ops, and ops beyond `packets` are
+/// dropped, so a device is never written with stale data from an earlier frame.
+pub(super) fn encode_frame<'b>(
+    buffer: &'b mut [[u8; 2]],
+    ops: &[(Register, u8)],
+    packets: usize,
+    order: ChainOrder,
+) -> &'b [u8] {
+    debug_assert!(packets <= buffer.len(), "frame longer than the chain limit");
+    debug_assert!(ops.len() <= packets, "more ops than packets");
+
+    for (i, packet) in buffer.iter_mut().enumerate() {
+        let device_index = if i < packets {
+            order.position(i, packets)
+        } else {
+            i
+        };
+        let (reg, data) = match ops.get(device_index) {
+            Some(&op) if i < packets => op,
+            _ => (Register::NoOp, 0x00),
+        };
+        packet[0] = reg.addr();
+        packet[1] = data;
+
+        #[cfg(feature = "log")]
+        if reg != Register::NoOp {
+            log::trace!("max7219 device {device_index}: {reg} <- {data:#04x}");
+        }
+    }
+
+    let buffer: &'b [[u8; 2]] = buffer;
+    let frame = active(buffer, packets).as_flattened();
+    debug_assert_eq!(frame.len(), packets * 2);
+    frame
+}
+
+/// Ops writing `register` of `device_index` only, with no-ops for the rest.
+pub(super) fn device_ops<const N: usize>(
+    device_index: usize,
+    register: Register,
+    data: u8,
+) -> [(Register, u8); N] {
+    let mut ops = [(Register::NoOp, 0x00); N];
+    if let Some(op) = ops.get_mut(device_index) {
+        *op = (register, data);
+    }
+    ops
+}
+
+/// The first `count` entries of `items`, one per device of the chain.
+///
+/// Empty rather than panicking if `count` is past the end of `items`.
+pub(super) fn active<T>(items: &[T], count: usize) -> &[T] {
+    items.get(..count).unwrap_or(&[])
+}
+
+/// Checks that `device_index` addresses one of `device_count` devices.
+pub(super) fn check_device_index(device_index: usize, device_count: usize) -> Result<()> {
+    if device_index >= device_count {
+        return Err(Error::InvalidDeviceIndex {
+            index: device_index,
+            count: device_count,
+        });
+    }
+    Ok(())
+}
+
+/// Converts a scan limit of 1 to 8 digits into its register value.
+pub(super) fn scan_limit_value(limit: u8) -> Result<u8> {
+    if !(1..=NUM_DIGITS).contains(&limit) {
+        return Err(Error::InvalidScanLimit);
+    }
+    Ok(limit - 1)
+}
+
+/// Register writes of the initialization sequence, in order.
+///
+/// `config` must have passed [`InitConfig::validate`].
+pub(super) fn init_sequence(config: &InitConfig) -> impl Iterator<Item = (Register, u8)> {
+    let clear = config.clear;
+    [
+        // A staggered power-on happens after the sequence
+        (
+            Register::Shutdown,
+            u8::from(config.powered_on && config.stagger_ms == 0),
+        ),
+        (Register::DisplayTest, 0x00),
+        (Register::ScanLimit, config.scan_limit - 1),
+        (Register::DecodeMode, config.decode_mode.value()),
+    ]
+    .into_iter()
+    .chain(
+        config
+            .initial_intensity
+            .map(|level| (Register::Intensity, level)),
+    )
+    .chain(
+        Register::digits()
+            .filter(move |_| clear)
+            .map(|digit_register| (digit_register, 0x00)),
+    )
+}
@@ -0,0 +1,118 @@
+//! Settings of the initialization sequence
+
+use crate::{
+    NUM_DIGITS, Result, driver::max7219::DEFAULT_INITIAL_INTENSITY, error::Error,
+    registers::DecodeMode,
+};
+
+/// What [`Max7219::init_with`](crate::Max7219::init_with) writes to every device.
+///
+/// The default is the sequence of [`Max7219::init`](crate::Max7219::init) on a
+/// driver with default settings: power on, display test off, all 8 digits
+/// scanned without decoding, [`DEFAULT_INITIAL_INTENSITY`] and every digit
+/// cleared. Each setter changes one step of it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Soft reset of a 4-digit Code B display, keeping what it shows
+/// let config = InitConfig::new()
+///     .skip_clear(true)
+///     .initial_intensity(Some(0x02))
+///     .decode_mode(DecodeMode::Digits0To3)
+///     .scan_limit(4);
+/// driver.init_with(&config)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InitConfig {
+    pub(super) clear: bool,
+    pub(super) initial_intensity: Option<u8>,
+    pub(super) decode_mode: DecodeMode,
+    pub(super) scan_limit: u8,
+    pub(super) powered_on: bool,
+    pub(super) stagger_ms: u32,
+}
+
+impl InitConfig {
+    /// Creates the default configuration.
+    pub const fn new() -> Self {
+        Self {
+            clear: true,
+            initial_intensity: Some(DEFAULT_INITIAL_INTENSITY),
+            decode_mode: DecodeMode::NoDecode,
+            scan_limit: NUM_DIGITS,
+            powered_on: true,
+            stagger_ms: 0,
+        }
+    }
+
+    /// Keeps what the digit registers hold instead of clearing them.
+    pub const fn skip_clear(mut self, skip: bool) -> Self {
+        self.clear = !skip;
+        self
+    }
+
+    /// Sets the intensity written during initialization, or leaves it alone with
+    /// `None`. Checked by `init_with`.
+    pub const fn initial_intensity(mut self, intensity: Option<u8>) -> Self {
+        self.initial_intensity = intensity;
+        self
+    }
+
+    /// Sets the decode mode.
+    pub const fn decode_mode(mut self, mode: DecodeMode) -> Self {
+        self.decode_mode = mode;
+        self
+    }
+
+    /// Sets how many digits are scanned, 1 to 8. Checked by `init_with`.
+    pub const fn scan_limit(mut self, limit: u8) -> Self {
+        self.scan_limit = limit;
+        self
+    }
+
+    /// Sets whether the devices are powered on, or left in shutdown to be switched
+    /// on later, e.g. once the first picture is drawn.
+    pub const fn powered_on(mut self, on: bool) -> Self {
+        self.powered_on = on;
+        self
+    }
+
+    /// Powers the devices on one at a time, `per_device_ms` apart, instead of all
+    /// at once; `0`, the default, disables this.
+    ///
+    /// Switching a long chain on at once draws a current spike that can brown out
+    /// a weak supply. With a stagger the rest of the sequence runs with the
+    /// devices in shutdown, then [`Max7219::power_on_staggered`](crate::Max7219::power_on_staggered)
+    /// switches them on. Waiting needs a delay provider, so only
+    /// [`Max7219::init_with_delay`](crate::Max7219::init_with_delay) staggers;
+    /// `init_with` powers the chain on at once. Ignored without
+    /// [`Self::powered_on`].
+    pub const fn stagger_power_on(mut self, per_device_ms: u32) -> Self {
+        self.stagger_ms = per_device_ms;
+        self
+    }
+
+    /// Checks the settings that have a limited range.
+    pub(super) fn validate(&self) -> Result<()> {
+        if self.initial_intensity.is_some_and(|level| level > 0x0F) {
+            return Err(Error::InvalidIntensity);
+        }
+        if !(1..=NUM_DIGITS).contains(&self.scan_limit) {
+            return Err(Error::InvalidScanLimit);
+        }
+        Ok(())
+    }
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,19 +1,164 @@
 //! Core MAX7219 driver implementation
 
-use embedded_hal::spi::SpiDevice;
+use core::ops::Range;
 
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::{frame, init::InitConfig};
 use crate::{
-    MAX_DISPLAYS, NUM_DIGITS, Result,
+    MAX_DISPLAYS, Result,
     error::Error,
     registers::{DecodeMode, Register},
 };
 
 /// Driver for the MAX7219 LED display controller.
 /// Communicates over SPI using the embedded-hal `SpiDevice` trait.
-pub struct Max7219<SPI> {
+///
+/// `N` is the longest chain the driver can address, [`MAX_DISPLAYS`] unless
+/// created with [`Self::new_chain`]. It only sizes the driver's buffers; the
+/// number of devices actually driven is set with [`Self::with_device_count`].
+pub struct Max7219<SPI, const N: usize = MAX_DISPLAYS> {
     spi: SPI,
-    buffer: [u8; MAX_DISPLAYS * 2],
+    buffer: [[u8; 2]; N],
     device_count: usize,
+    /// Last value written to each device's Shutdown register (`true` = powered on).
+    powered: [bool; N],
+    /// Last value written to each device's Display Test register (`true` = test mode).
+    display_test: [bool; N],
+    /// Last intensity written to each device (the chip powers up at 0).
+    intensity: [u8; N],
+    /// Last Scan Limit register value written to each device (the chip powers up at 0).
+    scan_limit: [u8; N],
+    /// Last Decode Mode register value written to each device (the chip powers up at 0).
+    decode_mode: [u8; N],
+    /// Intensity group of each device, see [`Self::assign_group`].
+    groups: [u8; N],
+    /// Called before every wait in blocking helpers, see [`Self::set_idle_hook`].
+    idle_hook: Option<fn()>,
+    /// Intensity written to every device by [`Self::init`], if any.
+    initial_intensity: Option<u8>,
+    /// How the intensity setters treat levels above `0x0F`.
+    clamp_policy: ClampPolicy,
+    /// End of the chain that device `0` refers to.
+    chain_order: ChainOrder,
+}
+
+/// Intensity [`Max7219::init`] writes unless configured otherwise.
+///
+/// The chip powers up at the minimum intensity, which is hard to see in daylight.
+/// This middle level is bright enough to confirm the wiring without drawing the
+/// full current of a level of `0x0F`.
+pub const DEFAULT_INITIAL_INTENSITY: u8 = 0x07;
+
+/// What the intensity setters do with a level above `0x0F`.
+///
+/// See [`Max7219::with_clamp_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClampPolicy {
+    /// Return `Error::InvalidIntensity` without writing anything.
+    #[default]
+    Error,
+    /// Write `0x0F` instead, for levels computed at runtime.
+    Clamp,
+}
+
+/// Which end of the chain device index `0` refers to.
+///
+/// See [`Max7219::with_chain_order`]. Only the indices change: the data for the
+/// device furthest from the MCU is always shifted out first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChainOrder {
+    /// Device `0` is the one furthest from the MCU, the one whose data is sent
+    /// first.
+    #[default]
+    FarthestFirst,
+    /// Device `0` is the one connected to the MCU, as on modules that read left
+    /// to right from their input.
+    NearestFirst,
+}
+
+impl ChainOrder {
+    /// Maps a device index to its position in a frame of `device_count`
+    /// packets, and back.
+    pub(crate) const fn position(self, device_index: usize, device_count: usize) -> usize {
+        match self {
+            ChainOrder::FarthestFirst => device_index,
+            ChainOrder::NearestFirst => device_count - 1 - device_index,
+        }
+    }
+
+    /// Indices of the devices plugged into the far end of a chain growing from
+    /// `old_count` to `count` devices.
+    pub(crate) fn added_devices(self, old_count: usize, count: usize) -> Range<usize> {
+        match self {
+            ChainOrder::FarthestFirst => 0..count.saturating_sub(old_count),
+            ChainOrder::NearestFirst => old_count..count.max(old_count),
+        }
+    }
+
+    /// Moves per-device `records` to the indices their devices get when the far
+    /// end of a chain of `old_count` devices grows or shrinks to `count` devices.
+    ///
+    /// Records of added devices are set to `empty`.
+    pub(crate) fn resize_records<T: Copy>(
+        self,
+        records: &mut [T],
+        old_count: usize,
+        count: usize,
+        empty: T,
+    ) {
+        if self == ChainOrder::FarthestFirst {
+            if count > old_count {
+                if let Some(kept) = records.get_mut(..count) {
+                    kept.rotate_right(count - old_count);
+                }
+            } else if let Some(kept) = records.get_mut(..old_count) {
+                kept.rotate_left(old_count - count);
+            }
+        }
+        let added = self.added_devices(old_count, count);
+        for record in records.iter_mut().take(added.end).skip(added.start) {
+            *record = empty;
+        }
+    }
+}
+
+impl<SPI, const N: usize> Max7219<SPI, N> {
+    /// Replaces the SPI device, keeping the configuration and the cached state.
+    ///
+    /// `f` may hand back something it took apart from the old device as well.
+    pub(super) fn map_spi<S, R>(self, f: impl FnOnce(SPI) -> (S, R)) -> (Max7219<S, N>, R) {
+        let (spi, rest) = f(self.spi);
+        let driver = Max7219 {
+            spi,
+            buffer: self.buffer,
+            device_count: self.device_count,
+            powered: self.powered,
+            display_test: self.display_test,
+            intensity: self.intensity,
+            scan_limit: self.scan_limit,
+            decode_mode: self.decode_mode,
+            groups: self.groups,
+            idle_hook: self.idle_hook,
+            initial_intensity: self.initial_intensity,
+            clamp_policy: self.clamp_policy,
+            chain_order: self.chain_order,
+        };
+        (driver, rest)
+    }
+
+    /// Returns the SPI device.
+    pub(super) fn spi(&self) -> &SPI {
+        &self.spi
+    }
+
+    /// Returns the SPI device mutably.
+    pub(super) fn spi_mut(&mut self) -> &mut SPI {
+        &mut self.spi
+    }
 }
 
 impl<SPI> Max7219<SPI>
@@ -26,13 +171,47 @@ where
     /// and data is read on the rising edge of the clock signal.
     ///
     /// Defaults to a single device (can be daisy-chained using `with_device_count`).
+    /// Chains of up to [`MAX_DISPLAYS`] devices are supported; use
+    /// [`Self::new_chain`] for longer ones.
     ///
     /// The SPI frequency must be 10 MHz or less, as required by the MAX7219 datasheet.
     pub fn new(spi: SPI) -> Self {
+        Self::new_chain(spi)
+    }
+}
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Creates a driver for chains of up to `N` devices, e.g. a strip of 12
+    /// modules.
+    ///
+    /// Works like [`Max7219::new`] otherwise, starting with a single device.
+    /// The display types such as `LedMatrix` take a driver for up to
+    /// [`MAX_DISPLAYS`] devices.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut driver = Max7219::<_, 12>::new_chain(spi).with_device_count(12)?;
+    /// driver.init()?;
+    /// ```
+    pub fn new_chain(spi: SPI) -> Self {
         Self {
             spi,
             device_count: 1, // Default to 1, use with_device_count to increase count
-            buffer: [0; MAX_DISPLAYS * 2],
+            buffer: [[0; 2]; N],
+            powered: [false; N], // The chip starts up in shutdown mode
+            display_test: [false; N],
+            intensity: [0; N],
+            scan_limit: [0; N],
+            decode_mode: [0; N],
+            groups: [0; N],
+            idle_hook: None,
+            initial_intensity: Some(DEFAULT_INITIAL_INTENSITY),
+            clamp_policy: ClampPolicy::Error,
+            chain_order: ChainOrder::FarthestFirst,
         }
     }
 
@@ -44,11 +223,43 @@ where
         self.device_count
     }
 
+    /// Consumes the driver and returns the SPI device.
+    ///
+    /// Use this to deinit the bus before going to sleep, or to hand it to another
+    /// driver. Nothing is sent, so the chips keep showing what they show; see
+    /// [`Self::shutdown_and_release`] to blank them first. A new driver built on
+    /// the device starts from the chip's power-up state in its records, so run
+    /// [`Self::init`] or [`Self::reapply_config`] on it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let spi = matrix.into_driver().release();
+    /// // ... sleep, then wake up
+    /// let mut matrix = Matrix4::from_spi(spi)?;
+    /// ```
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Powers every device off, then consumes the driver and returns the SPI device.
+    ///
+    /// # Errors
+    ///
+    /// If powering off fails, the driver is handed back with the SPI error, so the
+    /// caller can retry or [`Self::release`] it anyway.
+    pub fn shutdown_and_release(mut self) -> Result<SPI, (Self, Error)> {
+        match self.power_off() {
+            Ok(()) => Ok(self.spi),
+            Err(error) => Err((self, error)),
+        }
+    }
+
     /// Sets the number of daisy-chained devices to control.
     ///
     /// # Errors
     ///
-    /// Returns `Error::InvalidDisplayCount` if `count > MAX_DISPLAYS`.
+    /// Returns `Error::InvalidDeviceCount` if `count > N`.
     ///
     /// # Example
     ///
@@ -56,33 +267,437 @@ where
     /// let driver = Max7219::new(spi).with_device_count(4)?;
     /// ```
     pub fn with_device_count(mut self, count: usize) -> Result<Self> {
-        if count > MAX_DISPLAYS {
+        if count > N {
             return Err(Error::InvalidDeviceCount);
         }
         self.device_count = count;
         Ok(self)
     }
 
+    /// Sets the intensity [`Self::init`] writes to every device.
+    ///
+    /// Defaults to [`DEFAULT_INITIAL_INTENSITY`]. With `None`, `init` leaves the
+    /// intensity alone, so freshly powered chips stay at their minimum level as in
+    /// earlier releases.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` if the level is above `0x0F`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut driver = Max7219::new(spi).with_initial_intensity(Some(0x02))?;
+    /// driver.init()?;
+    /// ```
+    pub fn with_initial_intensity(mut self, intensity: Option<u8>) -> Result<Self> {
+        if intensity.is_some_and(|level| level > 0x0F) {
+            return Err(Error::InvalidIntensity);
+        }
+        self.initial_intensity = intensity;
+        Ok(self)
+    }
+
+    /// Sets how the intensity setters treat levels above `0x0F`.
+    ///
+    /// Applies to [`Self::set_intensity`], [`Self::set_intensity_all`],
+    /// [`Self::set_group_intensity`], [`Self::flash_brightness`] and the intensity
+    /// helpers of the display types. The default, [`ClampPolicy::Error`], rejects
+    /// such levels.
+    pub fn with_clamp_policy(mut self, policy: ClampPolicy) -> Self {
+        self.clamp_policy = policy;
+        self
+    }
+
+    /// Sets which end of the chain device index `0` refers to.
+    ///
+    /// Every method taking a device index, or a slice with one entry per device,
+    /// follows it, as do the driver's records and everything `LedMatrix` sends,
+    /// from `draw_text` to the framebuffer written by `flush`. The default,
+    /// [`ChainOrder::FarthestFirst`], matches the order the data is shifted out
+    /// in. [`Self::write_raw_frame`] is sent as is.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Device 0 is the module wired to the MCU
+    /// let mut driver = Max7219::new(spi)
+    ///     .with_device_count(4)?
+    ///     .with_chain_order(ChainOrder::NearestFirst);
+    /// driver.set_intensity(0, 0x0F)?;
+    /// ```
+    pub fn with_chain_order(mut self, order: ChainOrder) -> Self {
+        self.chain_order = order;
+        self
+    }
+
+    /// Returns which end of the chain device index `0` refers to.
+    pub fn chain_order(&self) -> ChainOrder {
+        self.chain_order
+    }
+
+    /// Applies the clamp policy to an intensity level.
+    pub(crate) fn checked_intensity(&self, intensity: u8) -> Result<u8> {
+        match self.clamp_policy {
+            _ if intensity <= 0x0F => Ok(intensity),
+            ClampPolicy::Clamp => Ok(0x0F),
+            ClampPolicy::Error => Err(Error::InvalidIntensity),
+        }
+    }
+
+    /// Installs a hook that blocking helpers call while they run, or removes it with `None`.
+    ///
+    /// Helpers such as [`Self::flash_brightness`], scrolling text or layouts can block
+    /// for seconds, long enough to trip an independent watchdog. The hook is called
+    /// right before every wait, so at least once per animation step, and the longest
+    /// stretch without a call is one step's SPI traffic plus its delay. Feed the
+    /// watchdog from it, for example through a `static` handle.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// fn feed_watchdog() {
+    ///     critical_section::with(|cs| WATCHDOG.borrow_ref_mut(cs).as_mut().unwrap().feed());
+    /// }
+    ///
+    /// driver.set_idle_hook(Some(feed_watchdog));
+    /// ```
+    pub fn set_idle_hook(&mut self, hook: Option<fn()>) {
+        self.idle_hook = hook;
+    }
+
+    /// Runs the idle hook, if one is installed. Call it right before each wait.
+    pub(crate) fn idle(&self) {
+        if let Some(hook) = self.idle_hook {
+            hook();
+        }
+    }
+
+    /// Returns `true` if every device in the chain was last told to power on.
+    ///
+    /// The MAX7219 cannot be read back, so this reflects the Shutdown register writes
+    /// made through this driver. A freshly created driver reports `false`, matching the
+    /// chip's power-up state, until [`Self::power_on`] or [`Self::init`] is called.
+    pub fn is_powered(&self) -> bool {
+        self.powered.iter().take(self.device_count).all(|&on| on)
+    }
+
+    /// Returns `true` if the device at `device_index` was last told to power on.
+    ///
+    /// Returns `false` for an out-of-range index.
+    pub fn is_device_powered(&self, device_index: usize) -> bool {
+        device_index < self.device_count && self.powered.get(device_index) == Some(&true)
+    }
+
+    /// Returns the intensity last written to `device_index`, or `None` for an
+    /// out-of-range index.
+    ///
+    /// Like [`Self::is_powered`], this reflects the writes made through this
+    /// driver. A device that has not been written yet reports the chip's power-up
+    /// level of 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Only dim the modules that are currently bright
+    /// for device_index in 0..driver.device_count() {
+    ///     if driver.intensity(device_index) > Some(0x08) {
+    ///         driver.set_intensity(device_index, 0x04)?;
+    ///     }
+    /// }
+    /// ```
+    pub fn intensity(&self, device_index: usize) -> Option<u8> {
+        frame::active(&self.intensity, self.device_count)
+            .get(device_index)
+            .copied()
+    }
+
+    /// Returns the scan limit last written to `device_index`, from 1 to 8 digits,
+    /// or `None` for an out-of-range index.
+    ///
+    /// A device that has not been written yet reports the chip's power-up limit of 1.
+    pub fn scan_limit(&self, device_index: usize) -> Option<u8> {
+        let value = frame::active(&self.scan_limit, self.device_count).get(device_index)?;
+        Some(value + 1)
+    }
+
+    /// Returns the decode mode last written to `device_index`.
+    ///
+    /// Returns `None` for an out-of-range index, or if a raw register value
+    /// without a [`DecodeMode`] variant was written, e.g. through [`Self::batch`].
+    /// A device that has not been written yet reports the chip's power-up mode,
+    /// [`DecodeMode::NoDecode`].
+    pub fn decode_mode(&self, device_index: usize) -> Option<DecodeMode> {
+        let value = *frame::active(&self.decode_mode, self.device_count).get(device_index)?;
+        DecodeMode::try_from(value).ok()
+    }
+
+    /// Records a successful register write in the shadow state of `device_index`.
+    fn track_write(&mut self, device_index: usize, register: Register, data: u8) {
+        match register {
+            Register::Shutdown => store(&mut self.powered, device_index, data & 0x01 != 0),
+            Register::DisplayTest => store(&mut self.display_test, device_index, data & 0x01 != 0),
+            Register::Intensity => store(&mut self.intensity, device_index, data),
+            Register::ScanLimit => store(&mut self.scan_limit, device_index, data & 0x07),
+            Register::DecodeMode => store(&mut self.decode_mode, device_index, data),
+            _ => {}
+        }
+    }
+
+    /// Returns the value last written to a control register of `device_index`.
+    ///
+    /// Digit and no-op registers are not cached and read as 0.
+    fn cached_register(&self, device_index: usize, register: Register) -> u8 {
+        let cached = match register {
+            Register::Shutdown => self.powered.get(device_index).map(|&on| u8::from(on)),
+            Register::DisplayTest => self.display_test.get(device_index).map(|&on| u8::from(on)),
+            Register::ScanLimit => self.scan_limit.get(device_index).copied(),
+            Register::DecodeMode => self.decode_mode.get(device_index).copied(),
+            Register::Intensity => self.intensity.get(device_index).copied(),
+            _ => None,
+        };
+        cached.unwrap_or(0x00)
+    }
+
+    /// Sends the cached power, display test, scan limit, decode mode and intensity
+    /// of every device again, one transaction per register.
+    ///
+    /// A brown-out resets the chips to shutdown with a default configuration while
+    /// the driver still believes in its last writes. This brings the chips back to
+    /// that state without touching the digit registers, so it is cheap enough to
+    /// call periodically or from a watchdog task. Digits lost in the brown-out
+    /// have to be redrawn separately, e.g. with `LedMatrix::flush`.
+    ///
+    /// Devices that were never configured through this driver get the chip's
+    /// power-up values, see [`Self::intensity`] and the other getters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an SPI error if a transfer fails, leaving the remaining registers
+    /// unsent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// loop {
+    ///     driver.reapply_config()?;
+    ///     delay.delay_ms(5_000);
+    /// }
+    /// ```
+    pub fn reapply_config(&mut self) -> Result<()> {
+        let count = self.device_count;
+        for register in [
+            Register::Shutdown,
+            Register::DisplayTest,
+            Register::ScanLimit,
+            Register::DecodeMode,
+            Register::Intensity,
+        ] {
+            let mut ops = [(Register::NoOp, 0x00); N];
+            for (device_index, op) in ops.iter_mut().enumerate().take(count) {
+                *op = (register, self.cached_register(device_index, register));
+            }
+            self.write_all_registers(frame::active(&ops, count))?;
+        }
+        Ok(())
+    }
+
     /// Initializes all configured displays.
+    ///
+    /// Starts with [`Self::noop_sync`], then powers the chain on, disables display
+    /// test mode, scans all 8 digits without decoding, sets the initial intensity
+    /// (see [`Self::with_initial_intensity`]) and clears every digit.
     pub fn init(&mut self) -> Result<()> {
-        self.power_on()?;
+        self.noop_sync()?;
+        self.init_with(&self.init_config())
+    }
+
+    /// Clocks one no-op per device through the chain.
+    ///
+    /// If the MCU resets in the middle of a transfer while the chain stays
+    /// powered, the shift registers are left holding part of a frame, and the
+    /// next write would be latched by the wrong devices. A full frame of no-ops
+    /// pushes that data out without changing any register. [`Self::init`] starts
+    /// with this; call it after an SPI error that may have cut a frame short.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if driver.set_intensity_all(level).is_err() {
+    ///     driver.noop_sync()?;
+    ///     driver.reapply_config()?;
+    /// }
+    /// ```
+    pub fn noop_sync(&mut self) -> Result<()> {
+        let ops = [(Register::NoOp, 0x00); N];
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
+
+    /// Initializes all configured displays with the steps of `config`.
+    ///
+    /// [`Self::init`] is the shorthand for the default configuration, with the
+    /// intensity of [`Self::with_initial_intensity`]. A
+    /// [stagger](InitConfig::stagger_power_on) needs [`Self::init_with_delay`];
+    /// here the chain is powered on at once. Use this to keep what the
+    /// display shows across a soft reset, to set the brightness before anything
+    /// lights up, or to set up Code B decoding for a 7-segment module.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` or `Error::InvalidScanLimit` before
+    /// anything is sent if the configuration is out of range, or an SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.init_with(&InitConfig::new().skip_clear(true).initial_intensity(Some(1)))?;
+    /// ```
+    pub fn init_with(&mut self, config: &InitConfig) -> Result<()> {
+        config.validate()?;
+        self.init_devices(0..self.device_count, &config.stagger_power_on(0))
+    }
+
+    /// Initializes all configured displays like [`Self::init_with`], powering
+    /// them on one at a time if `config` asks for a
+    /// [stagger](InitConfig::stagger_power_on).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::init_with`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.init_with_delay(&InitConfig::new().stagger_power_on(20), &mut delay)?;
+    /// ```
+    pub fn init_with_delay<D: DelayNs>(
+        &mut self,
+        config: &InitConfig,
+        delay: &mut D,
+    ) -> Result<()> {
+        config.validate()?;
+        self.init_devices(0..self.device_count, config)?;
+        if config.powered_on && config.stagger_ms > 0 {
+            self.power_on_staggered(delay, config.stagger_ms)?;
+        }
+        Ok(())
+    }
 
-        self.test_all(false)?;
-        self.set_scan_limit_all(NUM_DIGITS)?;
-        self.set_decode_mode_all(DecodeMode::NoDecode)?;
+    /// The configuration [`Self::init`] uses.
+    fn init_config(&self) -> InitConfig {
+        InitConfig::new().initial_intensity(self.initial_intensity)
+    }
+
+    /// Initializes all configured displays like [`Self::init`], but keeps what the
+    /// digit registers hold.
+    ///
+    /// Use this to take over a chain that already shows something, e.g. a splash
+    /// left by a bootloader. The power, display test, scan limit, decode mode and
+    /// initial intensity are still written; pass `None` to
+    /// [`Self::with_initial_intensity`] to keep the brightness as well.
+    pub fn init_preserve_display(&mut self) -> Result<()> {
+        let config = self.init_config().skip_clear(true);
+        self.init_devices(0..self.device_count, &config)
+    }
+
+    /// Runs the [`Self::init`] sequence on one device, e.g. a module swapped into a
+    /// running chain.
+    ///
+    /// The other devices only receive no-ops and keep what they show.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if `device_index` is out of range, or an
+    /// SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Module 5 was replaced, bring it up without blanking the sign
+    /// driver.init_device(5)?;
+    /// ```
+    pub fn init_device(&mut self, device_index: usize) -> Result<()> {
+        frame::check_device_index(device_index, self.device_count)?;
+        self.init_devices(device_index..device_index + 1, &self.init_config())
+    }
+
+    /// Changes the number of devices in the chain at runtime, e.g. after a module
+    /// was plugged in.
+    ///
+    /// Modules are expected to be plugged into, or removed from, the far end of
+    /// the chain, the output of the last module. With the default
+    /// [`ChainOrder::FarthestFirst`] that end is index `0`: the devices already
+    /// running move up by the number of added ones, or down by the number of
+    /// removed ones, and the driver's records move with them. With
+    /// [`ChainOrder::NearestFirst`] the added devices are `old_count..count`.
+    ///
+    /// When the chain grows, the added devices go through the [`Self::init`]
+    /// sequence; the other devices only receive no-ops and keep what they show.
+    /// Shrinking the chain sends nothing, the removed devices simply stop being
+    /// addressed.
+    ///
+    /// Only this runtime-sized driver and `SevenSegment` can do this. The device
+    /// count of an `LedMatrix` is part of its type, so a matrix has to be rebuilt
+    /// from the driver for a different chain length; do not call this through
+    /// `LedMatrix::driver`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if `count > N`, leaving the
+    /// count unchanged, or an SPI error from initializing the added devices.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.set_device_count(driver.device_count() + 1)?;
+    /// ```
+    pub fn set_device_count(&mut self, count: usize) -> Result<()> {
+        if count > N {
+            return Err(Error::InvalidDeviceCount);
+        }
 
-        self.clear_all()?;
-        // self.power_off()?;
-        // self.power_on()?;
+        let old_count = self.device_count;
+        let order = self.chain_order;
+        order.resize_records(&mut self.powered, old_count, count, false);
+        order.resize_records(&mut self.display_test, old_count, count, false);
+        order.resize_records(&mut self.intensity, old_count, count, 0);
+        order.resize_records(&mut self.scan_limit, old_count, count, 0);
+        order.resize_records(&mut self.decode_mode, old_count, count, 0);
+        order.resize_records(&mut self.groups, old_count, count, 0);
+        self.device_count = count;
+        if count <= old_count {
+            return Ok(());
+        }
+        self.init_devices(order.added_devices(old_count, count), &self.init_config())
+    }
 
+    /// Runs the initialization sequence on `devices`, sending no-ops to the rest.
+    fn init_devices(&mut self, devices: Range<usize>, config: &InitConfig) -> Result<()> {
+        for (register, data) in frame::init_sequence(config) {
+            self.write_to_devices(devices.clone(), register, data)?;
+        }
         Ok(())
     }
 
+    /// Writes `data` to `register` of every device in `devices` in one frame.
+    fn write_to_devices(
+        &mut self,
+        devices: Range<usize>,
+        register: Register,
+        data: u8,
+    ) -> Result<()> {
+        let mut ops = [(Register::NoOp, 0x00); N];
+        for op in ops.iter_mut().take(devices.end).skip(devices.start) {
+            *op = (register, data);
+        }
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
+
     /// Writes a value to a specific register of a device in the daisy chain.
     ///
     /// Each MAX7219 device expects a 16-bit packet: 1 byte for the register address
     /// and 1 byte for the data. To update one device in a daisy-chained series,
-    /// we prepare a full SPI buffer of `display_count * 2` bytes (2 bytes per display).
+    /// we prepare a full SPI buffer of `device_count * 2` bytes (2 bytes per device).
     ///
     /// This method writes the target register and data into the correct offset of
     /// the shared buffer corresponding to the selected device (`device_index`),
@@ -93,13 +708,14 @@ where
     ///
     /// # Arguments
     ///
-    /// * `device_index` - Index of the device in the chain (0 = furthest from MCU, N-1 = closest to MCU).
+    /// * `device_index` - Index of the device in the chain (0 = furthest from MCU, N-1 = closest to MCU,
+    ///   unless reversed with [`Self::with_chain_order`]).
     /// * `register` - The register to write to (e.g., `Register::Shutdown`, `Register::Digit0`, etc.).
     /// * `data` - The value to write to the register.
     ///
     /// # Errors
     ///
-    /// Returns `Error::InvalidDisplayIndex` if the index is out of range, or an SPI error
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range, or an SPI error
     /// if the transfer fails.
     pub(crate) fn write_device_register(
         &mut self,
@@ -107,19 +723,33 @@ where
         register: Register,
         data: u8,
     ) -> Result<()> {
-        if device_index >= self.device_count {
-            return Err(Error::InvalidDeviceIndex);
-        }
-
-        self.buffer = [0; MAX_DISPLAYS * 2];
-
-        let offset = device_index * 2; // 2 bytes(16 bits packet) per display
-        self.buffer[offset] = register as u8;
-        self.buffer[offset + 1] = data;
-
-        self.spi.write(&self.buffer[0..self.device_count * 2])?;
+        frame::check_device_index(device_index, self.device_count)?;
+        let ops = frame::device_ops::<N>(device_index, register, data);
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
 
-        Ok(())
+    /// Writes `data` to the register at `addr` of one device, for registers
+    /// poked outside the typed API, e.g. when replaying captured traffic.
+    ///
+    /// The other devices of the chain receive no-ops. The write goes through the
+    /// same path as the typed methods, so [`Self::is_powered`], the intensity and
+    /// the other recorded settings follow it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidRegister` if `addr` is not a MAX7219 register
+    /// (`0x0D`, `0x0E` or above `0x0F`), `Error::InvalidDeviceIndex` if the index
+    /// is out of range, or an SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Intensity 0x05 on the second device
+    /// driver.write_register(1, 0x0A, 0x05)?;
+    /// ```
+    pub fn write_register(&mut self, device_index: usize, addr: u8, data: u8) -> Result<()> {
+        let register = Register::try_from(addr)?;
+        self.write_device_register(device_index, register, data)
     }
 
     /// Write each (register, data) tuple to its corresponding MAX7219 device in the daisy chain.
@@ -127,6 +757,8 @@ where
     /// The number of tuples in `ops` must exactly match `self.device_count`.
     /// Convention: ops[0] = furthest device from MCU, ops[device_count-1] = nearest device
     /// Because The first one we send in the SPI gets pushed till the last device.
+    /// With [`ChainOrder::NearestFirst`] the ops are sent in reverse order instead,
+    /// so ops[0] goes to the nearest device.
     ///
     /// # Panics (only in debug builds)
     /// - If `ops.len() != self.device_count`.
@@ -134,45 +766,131 @@ where
     /// # Errors
     /// - Returns an SPI error if the write operation fails.
     pub(crate) fn write_all_registers(&mut self, ops: &[(Register, u8)]) -> Result<()> {
-        // clear the buffer: 2 bytes per device
-        self.buffer = [0; MAX_DISPLAYS * 2];
+        debug_assert_eq!(ops.len(), self.device_count, "one op per device");
 
-        for (i, &(reg, data)) in ops.iter().enumerate() {
-            let offset = i * 2;
-            self.buffer[offset] = reg as u8;
-            self.buffer[offset + 1] = data;
+        self.send_frame(ops, self.device_count, self.chain_order)?;
+
+        for (i, &(reg, data)) in ops.iter().enumerate().take(self.device_count) {
+            self.track_write(i, reg, data);
+        }
+
+        Ok(())
+    }
+
+    /// Sends exactly `ops.len()` packets in one transaction, regardless of the
+    /// configured device count.
+    ///
+    /// Used when the chain length is not known yet, e.g. while probing.
+    /// `ops[0]` is sent first, whatever the [`ChainOrder`].
+    pub(super) fn write_packets(&mut self, ops: &[(Register, u8)]) -> Result<()> {
+        self.send_frame(ops, ops.len(), ChainOrder::FarthestFirst)
+    }
+
+    /// Builds and sends a frame of exactly `packets` packets, see
+    /// [`frame::encode_frame`]. Every register write of the driver goes through here.
+    fn send_frame(
+        &mut self,
+        ops: &[(Register, u8)],
+        packets: usize,
+        order: ChainOrder,
+    ) -> Result<()> {
+        let frame = frame::encode_frame(&mut self.buffer, ops, packets, order);
+        self.spi.write(frame)?;
+        Ok(())
+    }
+
+    /// Sends `frame` as one transaction, byte for byte.
+    ///
+    /// This is an escape hatch for protocol debugging and for replaying frames
+    /// captured with a logic analyzer: the bytes are neither decoded nor checked
+    /// against [`Register`]. As with every chain frame, the first packet ends up
+    /// in the device furthest from the MCU when the frame covers the whole
+    /// chain. A shorter frame is sent as is, and the devices further out then
+    /// latch whatever older data is still in their shift registers.
+    ///
+    /// Raw writes are not tracked, so [`Self::is_powered`] and the intensity
+    /// bookkeeping may no longer match the chip afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidFrameLength` if the length is odd or longer than
+    /// two bytes per configured device.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Intensity 0x0F on the far device, display test on the near one
+    /// driver.write_raw_frame(&[0x0A, 0x0F, 0x0F, 0x01])?;
+    /// ```
+    pub fn write_raw_frame(&mut self, frame: &[u8]) -> Result<()> {
+        if !frame.len().is_multiple_of(2) || frame.len() > self.device_count * 2 {
+            return Err(Error::InvalidFrameLength);
         }
 
-        // send exactly device_count packets
-        let len = self.device_count * 2;
-        self.spi.write(&self.buffer[..len])?;
+        #[cfg(feature = "log")]
+        log::trace!("max7219 raw frame: {frame:02x?}");
 
+        self.spi.write(frame)?;
         Ok(())
     }
 
+    /// Changes the device count in place, after the caller has validated it.
+    ///
+    /// A count above `N` is clamped, so the shadow arrays always cover the chain.
+    pub(super) fn set_device_count_unchecked(&mut self, count: usize) {
+        self.device_count = count.min(N);
+    }
+
     // fn write_raw_register(&mut self, register: u8, data: u8) -> Result<(), SPI::Error> {
     //     self.spi.write(&[register, data])
     // }
 
     /// Powers on all displays by writing `0x01` to the Shutdown register.
     pub fn power_on(&mut self) -> Result<()> {
-        let ops = [(Register::Shutdown, 0x01); MAX_DISPLAYS];
+        let ops = [(Register::Shutdown, 0x01); N];
+
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
 
-        self.write_all_registers(&ops[..self.device_count])
+    /// Powers the devices on one at a time, from device `0`, waiting
+    /// `per_device_delay_ms` between two of them.
+    ///
+    /// Each device gets its own Shutdown write, with no-ops for the others, so a
+    /// long chain does not draw its full current at once. The idle hook, if any,
+    /// runs before every wait.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.power_on_staggered(&mut delay, 20)?;
+    /// ```
+    pub fn power_on_staggered<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        per_device_delay_ms: u32,
+    ) -> Result<()> {
+        for device_index in 0..self.device_count {
+            if device_index > 0 {
+                self.idle();
+                delay.delay_ms(per_device_delay_ms);
+            }
+            self.write_device_register(device_index, Register::Shutdown, 0x01)?;
+        }
+        Ok(())
     }
 
     /// Powers off all displays by writing `0x00` to the Shutdown register.
     pub fn power_off(&mut self) -> Result<()> {
-        let ops = [(Register::Shutdown, 0x00); MAX_DISPLAYS];
+        let ops = [(Register::Shutdown, 0x00); N];
 
-        self.write_all_registers(&ops[..self.device_count])
+        self.write_all_registers(frame::active(&ops, self.device_count))
     }
 
     /// Powers on a single device by writing `0x01` to the Shutdown register.
     ///
     /// # Arguments
     ///
-    /// * `device_index` - The index of the display to power on.
+    /// * `device_index` - The index of the device to power on.
     pub fn power_on_device(&mut self, device_index: usize) -> Result<()> {
         self.write_device_register(device_index, Register::Shutdown, 0x01)
     }
@@ -197,8 +915,47 @@ where
     /// Enable or disable display test mode on all devices in one SPI transaction.
     pub fn test_all(&mut self, enable: bool) -> Result<()> {
         let data = if enable { 0x01 } else { 0x00 };
-        let ops: [(Register, u8); MAX_DISPLAYS] = [(Register::DisplayTest, data); MAX_DISPLAYS];
-        self.write_all_registers(&ops[..self.device_count])
+        let ops: [(Register, u8); N] = [(Register::DisplayTest, data); N];
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
+
+    /// Lights every LED of each device in turn, for `dwell_ns` each, to check
+    /// the wiring of a chain.
+    ///
+    /// Device 0 goes into display test mode, stays lit for `dwell_ns`, and is
+    /// switched back before device 1 lights up, and so on. A dead module stays
+    /// dark, and a miswired chain lights up in the wrong order. The idle hook, if
+    /// any, runs before every wait.
+    ///
+    /// If a write fails, display test mode is switched off on the whole chain
+    /// before the error is returned, so no device is left lit.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Half a second per module at bring-up
+    /// driver.test_cycle(&mut delay, 500_000_000)?;
+    /// ```
+    pub fn test_cycle<D: DelayNs>(&mut self, delay: &mut D, dwell_ns: u32) -> Result<()> {
+        let mut result = Ok(());
+        for device_index in 0..self.device_count {
+            result = self.test_device(device_index, true);
+            if result.is_err() {
+                break;
+            }
+            self.idle();
+            delay.delay_ns(dwell_ns);
+            result = self.test_device(device_index, false);
+            if result.is_err() {
+                break;
+            }
+        }
+
+        if result.is_err() {
+            // Best effort: the first error is the one worth reporting
+            let _ = self.test_all(false);
+        }
+        result
     }
 
     /// Sets how many digits the MAX7219 should actively scan and display.
@@ -215,23 +972,17 @@ where
     /// # Errors
     /// Returns `Error::InvalidScanLimit` if the value is not in the range 1 to 8.
     pub fn set_device_scan_limit(&mut self, device_index: usize, limit: u8) -> Result<()> {
-        if !(1..=8).contains(&limit) {
-            return Err(Error::InvalidScanLimit);
-        }
-
-        self.write_device_register(device_index, Register::ScanLimit, limit - 1)
+        let value = frame::scan_limit_value(limit)?;
+        self.write_device_register(device_index, Register::ScanLimit, value)
     }
 
     /// Set scan‐limit on all devices in one go.
     ///
     /// `limit` must be in 1..=8. Internally sends `limit - 1` to each chip.
     pub fn set_scan_limit_all(&mut self, limit: u8) -> Result<()> {
-        if !(1..=8).contains(&limit) {
-            return Err(Error::InvalidScanLimit);
-        }
-        let val = limit - 1;
-        let ops: [(Register, u8); MAX_DISPLAYS] = [(Register::ScanLimit, val); MAX_DISPLAYS];
-        self.write_all_registers(&ops[..self.device_count])
+        let val = frame::scan_limit_value(limit)?;
+        let ops: [(Register, u8); N] = [(Register::ScanLimit, val); N];
+        self.write_all_registers(frame::active(&ops, self.device_count))
     }
 
     /// Code B decoding allows the MAX7219 to automatically convert values like `0-9`, `E`, `H`, `L`, etc.
@@ -250,8 +1001,8 @@ where
     /// Set decode‐mode on all devices in one go.
     pub fn set_decode_mode_all(&mut self, mode: DecodeMode) -> Result<()> {
         let byte = mode as u8;
-        let ops: [(Register, u8); MAX_DISPLAYS] = [(Register::DecodeMode, byte); MAX_DISPLAYS];
-        self.write_all_registers(&ops[..self.device_count])
+        let ops: [(Register, u8); N] = [(Register::DecodeMode, byte); N];
+        self.write_all_registers(frame::active(&ops, self.device_count))
     }
 
     /// Clears all digits by writing 0 to each digit register (DIG0 to DIG7).
@@ -270,13 +1021,49 @@ where
     /// Clears all digits on all connected MAX7219 displays.
     pub fn clear_all(&mut self) -> Result<()> {
         for digit_register in Register::digits() {
-            let ops = [(digit_register, 0x00); MAX_DISPLAYS];
-            self.write_all_registers(&ops[..self.device_count])?;
+            let ops = [(digit_register, 0x00); N];
+            self.write_all_registers(frame::active(&ops, self.device_count))?;
         }
 
         Ok(())
     }
 
+    /// Writes all 8 digit registers of every device, `frames[i]` going to device `i`.
+    ///
+    /// Sends exactly one frame per digit register, `Digit0` to `Digit7`, each
+    /// carrying that row of every device. This is what `LedMatrix::flush` sends,
+    /// but starting from row bytes, which makes it the cheapest way to show
+    /// prerendered animation frames. As everywhere in the driver, device `0` is
+    /// the furthest from the microcontroller, or the nearest with
+    /// [`ChainOrder::NearestFirst`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` before anything is sent if `frames` does
+    /// not have one entry per device, or the first SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// for frame in ANIMATION.chunks_exact(4) {
+    ///     driver.write_frame(frame)?;
+    ///     delay.delay_ms(33);
+    /// }
+    /// ```
+    pub fn write_frame(&mut self, frames: &[[u8; 8]]) -> Result<()> {
+        if frames.len() != self.device_count {
+            return Err(Error::InvalidDeviceCount);
+        }
+        for (row, digit_register) in Register::digits().enumerate() {
+            let mut ops = [(Register::NoOp, 0x00); N];
+            for (op, rows) in ops.iter_mut().zip(frames) {
+                *op = (digit_register, rows.get(row).copied().unwrap_or_default());
+            }
+            self.write_all_registers(frame::active(&ops, self.device_count))?;
+        }
+        Ok(())
+    }
+
     /// Writes a raw value to the specified digit register (DIG0 to DIG7).
     ///
     /// This function gives you low-level control over the display by sending a
@@ -342,7 +1129,8 @@ where
     ///
     /// # Arguments
     ///
-    /// - `device_index`: Index of the display in the daisy chain (0 = Furthest from the Microcontroller)
+    /// - `device_index`: Index of the device in the daisy chain (0 = Furthest from the Microcontroller,
+    ///   unless reversed with [`Self::with_chain_order`])
     /// - `digit`: Which digit register to write to (`Digit::D0` to `Digit::D7`)
     /// - `value`: The raw 8-bit data to send to the digit register
     pub fn write_raw_digit(&mut self, device_index: usize, digit: u8, value: u8) -> Result<()> {
@@ -350,31 +1138,190 @@ where
         self.write_device_register(device_index, digit_register, value)
     }
 
+    /// Writes all 8 digit registers of one device, `rows[0]` going to `Digit0`.
+    ///
+    /// Sends one frame per digit, in digit order, with no-ops for the other
+    /// devices. On an LED matrix this draws a whole 8x8 pattern; see
+    /// [`Self::write_raw_digit`] for how values map to segments or pixels.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` before anything is sent if the index is
+    /// out of range, or the first SPI error, leaving the later digits unwritten.
+    pub fn write_device_digits(&mut self, device_index: usize, rows: &[u8; 8]) -> Result<()> {
+        frame::check_device_index(device_index, self.device_count)?;
+        for (digit_register, &value) in Register::digits().zip(rows) {
+            self.write_device_register(device_index, digit_register, value)?;
+        }
+        Ok(())
+    }
+
     /// Sets the brightness intensity (0 to 15) for a specific device.
     ///
     /// # Arguments
     ///
-    /// - `device_index`: Index of the display in the daisy chain (0 = Furthest from the Microcontroller)
+    /// - `device_index`: Index of the device in the daisy chain (0 = Furthest from the Microcontroller,
+    ///   unless reversed with [`Self::with_chain_order`])
     /// - `intensity`: Brightness level from `0` to `15` (`0x00` to `0x0F`)
     pub fn set_intensity(&mut self, device_index: usize, intensity: u8) -> Result<()> {
-        if intensity > 0x0F {
-            return Err(Error::InvalidIntensity);
-        }
+        let intensity = self.checked_intensity(intensity)?;
         self.write_device_register(device_index, Register::Intensity, intensity)
     }
 
-    /// Set intensity for all displays
+    /// Puts a device into an intensity group.
+    ///
+    /// Groups let parts of the chain be dimmed together with
+    /// [`Self::set_group_intensity`]. Every device starts in group `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range.
+    pub fn assign_group(&mut self, device_index: usize, group: u8) -> Result<()> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                count: self.device_count,
+            });
+        }
+        if let Some(slot) = self.groups.get_mut(device_index) {
+            *slot = group;
+        }
+        Ok(())
+    }
+
+    /// Returns the intensity group of a device, or `None` for an out-of-range index.
+    pub fn group(&self, device_index: usize) -> Option<u8> {
+        if device_index >= self.device_count {
+            return None;
+        }
+        self.groups.get(device_index).copied()
+    }
+
+    /// Sets the brightness intensity (0 to 15) of every device in `group`.
+    ///
+    /// All devices of the group are updated in one SPI transaction; the other
+    /// devices receive no-ops and keep their intensity. Nothing is sent if no
+    /// device belongs to the group.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.assign_group(3, 1)?; // the alert module
+    /// driver.set_group_intensity(0, 0x01)?; // dim everything else at night
+    /// ```
+    pub fn set_group_intensity(&mut self, group: u8, intensity: u8) -> Result<()> {
+        let intensity = self.checked_intensity(intensity)?;
+
+        let mut ops = [(Register::NoOp, 0x00); N];
+        let mut any = false;
+        for (op, &device_group) in ops.iter_mut().zip(&self.groups).take(self.device_count) {
+            if device_group == group {
+                *op = (Register::Intensity, intensity);
+                any = true;
+            }
+        }
+
+        if !any {
+            return Ok(());
+        }
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
+
+    /// Briefly raises every device to `peak` intensity, then restores the previous levels.
+    ///
+    /// A short flash after a value changes draws the eye to it. The previous
+    /// intensities come from the driver's record of what was last written, so
+    /// devices set to different levels get their own level back.
+    ///
+    /// The restore is attempted even if switching to `peak` fails, in which case
+    /// the hold is skipped and the first error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` if `peak > 0x0F`, or an SPI error.
+    pub fn flash_brightness<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        peak: u8,
+        hold_ms: u32,
+    ) -> Result<()> {
+        let peak = self.checked_intensity(peak)?;
+
+        let count = self.device_count;
+        let mut restore = [(Register::NoOp, 0x00); N];
+        for (op, &level) in restore.iter_mut().zip(&self.intensity).take(count) {
+            *op = (Register::Intensity, level);
+        }
+
+        let peak_ops = [(Register::Intensity, peak); N];
+        let flashed = self.write_all_registers(frame::active(&peak_ops, count));
+        if flashed.is_ok() {
+            self.idle();
+            delay.delay_ms(hold_ms);
+        }
+        let restored = self.write_all_registers(frame::active(&restore, count));
+
+        flashed.and(restored)
+    }
+
+    /// Sets the intensity of every device in one SPI transaction, `values[i]`
+    /// going to device `i`.
+    ///
+    /// Use this for modules that need different levels, e.g. behind different
+    /// diffusers, without the ripple of setting them one by one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if `values` does not have one level per
+    /// device, or `Error::InvalidIntensity` if a level is above `0x0F` and the
+    /// [`ClampPolicy`] rejects it. Both are checked before anything is sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.set_intensities(&[0x03, 0x08, 0x08, 0x03])?;
+    /// ```
+    pub fn set_intensities(&mut self, values: &[u8]) -> Result<()> {
+        if values.len() != self.device_count {
+            return Err(Error::InvalidDeviceCount);
+        }
+        let mut ops = [(Register::NoOp, 0x00); N];
+        for (op, &level) in ops.iter_mut().zip(values) {
+            *op = (Register::Intensity, self.checked_intensity(level)?);
+        }
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
+
+    /// Sets the intensity (0 to 15) of every device in one SPI transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` before anything is sent if the level is
+    /// above `0x0F` and the [`ClampPolicy`] rejects it, or an SPI error.
     pub fn set_intensity_all(&mut self, intensity: u8) -> Result<()> {
-        let ops = [(Register::Intensity, intensity); MAX_DISPLAYS];
-        self.write_all_registers(&ops[..self.device_count])
+        let intensity = self.checked_intensity(intensity)?;
+        let ops = [(Register::Intensity, intensity); N];
+        self.write_all_registers(frame::active(&ops, self.device_count))
+    }
+}
+
+/// Stores `value` in the cache slot of `device_index`, ignoring an index past
+/// the end.
+fn store<T>(cache: &mut [T], device_index: usize, value: T) {
+    if let Some(slot) = cache.get_mut(device_index) {
+        *slot = value;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{MAX_DISPLAYS, NUM_DIGITS, registers::DecodeMode, registers::Register};
-    use embedded_hal_mock::eh1::{spi::Mock as SpiMock, spi::Transaction};
+    use crate::test_support::{ChainSpi, Event, FailingSpi, LoggingDelay, LoggingSpi, ShiftChain};
+    use crate::test_util::{FrameRecorder, RegisterWrite};
+    use crate::{ErrorKind, MAX_DISPLAYS, NUM_DIGITS, registers::DecodeMode, registers::Register};
+    use core::cell::RefCell;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use embedded_hal_mock::eh1::{delay::NoopDelay, spi::Mock as SpiMock, spi::Transaction};
 
     #[test]
     fn test_new() {
@@ -407,6 +1354,49 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_release_and_rebuild() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Intensity.addr(), 0x03]),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Shutdown.addr(), 0x00]),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Shutdown.addr(), 0x01]),
+            Transaction::transaction_end(),
+        ];
+        let spi = SpiMock::new(&expected_transactions);
+
+        let mut driver = Max7219::new(spi);
+        driver.set_intensity(0, 0x03).unwrap();
+        let spi = driver
+            .shutdown_and_release()
+            .map_err(|(_, error)| error)
+            .unwrap();
+
+        // The bus could be used by another driver here
+        let mut driver = Max7219::new(spi);
+        assert!(!driver.is_powered());
+        driver.power_on().unwrap();
+
+        let mut spi = driver.release();
+        spi.done();
+    }
+
+    #[test]
+    fn test_shutdown_and_release_keeps_driver_on_error() {
+        let mut spi = FailingSpi::failing_on(0);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        let (driver, error) = driver.shutdown_and_release().unwrap_err();
+        assert_eq!(error, ErrorKind::SpiError);
+        assert_eq!(driver.device_count(), 2);
+        driver.release();
+        assert_eq!(spi.writes.len(), 1);
+    }
+
     #[test]
     fn test_power_on() {
         let expected_transactions = [
@@ -511,10 +1501,48 @@ mod tests {
         let mut driver = Max7219::new(&mut spi).with_device_count(1).unwrap();
 
         let result = driver.power_on_device(1);
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 1, count: 1 })
+        );
 
         let result = driver.power_off_device(1);
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(result.unwrap_err(), ErrorKind::InvalidDeviceIndex);
+        spi.done();
+    }
+
+    #[test]
+    fn test_power_state_tracking() {
+        let mut expected_transactions = Vec::new();
+        for data in [
+            vec![
+                Register::Shutdown.addr(),
+                0x01,
+                Register::Shutdown.addr(),
+                0x01,
+            ],
+            vec![Register::NoOp.addr(), 0x00, Register::Shutdown.addr(), 0x00],
+            vec![Register::NoOp.addr(), 0x00, Register::Shutdown.addr(), 0x01],
+        ] {
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(data));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        assert!(!driver.is_powered());
+        driver.power_on().unwrap();
+        assert!(driver.is_powered());
+
+        driver.power_off_device(1).unwrap();
+        assert!(!driver.is_powered());
+        assert!(driver.is_device_powered(0));
+        assert!(!driver.is_device_powered(1));
+        assert!(!driver.is_device_powered(2));
+
+        driver.power_on_device(1).unwrap();
+        assert!(driver.is_powered());
         spi.done();
     }
 
@@ -652,7 +1680,10 @@ mod tests {
             .expect("valid device count");
 
         let result = driver.clear_display(1);
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 1, count: 1 })
+        );
         spi.done();
     }
 
@@ -675,6 +1706,60 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_write_device_digits() {
+        let rows = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80];
+        let mut expected_transactions = Vec::new();
+        for (digit_register, &value) in Register::digits().zip(&rows) {
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(vec![
+                Register::NoOp.addr(),
+                0x00,
+                digit_register.addr(),
+                value,
+                Register::NoOp.addr(),
+                0x00,
+            ]));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+
+        driver.write_device_digits(1, &rows).unwrap();
+        assert_eq!(
+            driver.write_device_digits(3, &rows),
+            Err(Error::InvalidDeviceIndex { index: 3, count: 3 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_frame() {
+        let frames: Vec<[u8; 8]> = (0..4u8)
+            .map(|device| core::array::from_fn(|row| device << 4 | row as u8))
+            .collect();
+        let mut expected_transactions = Vec::new();
+        for (row, digit_register) in Register::digits().enumerate() {
+            let mut bytes = Vec::new();
+            // Device 0, the furthest from the MCU, is sent first
+            for rows in &frames {
+                bytes.extend([digit_register.addr(), rows[row]]);
+            }
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(bytes));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+
+        driver.write_frame(&frames).unwrap();
+        assert_eq!(
+            driver.write_frame(&frames[..3]),
+            Err(Error::InvalidDeviceCount)
+        );
+        spi.done();
+    }
+
     #[test]
     fn test_write_raw_digit_invalid_digit() {
         let mut spi = SpiMock::new(&[]); // No transactions expected for invalid digit
@@ -682,7 +1767,7 @@ mod tests {
 
         let result = driver.write_raw_digit(0, 8, 0x00); // Digit 8 is invalid
 
-        assert_eq!(result, Err(Error::InvalidDigit));
+        assert_eq!(result, Err(Error::InvalidDigit { digit: 8 }));
 
         spi.done();
     }
@@ -715,17 +1800,154 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_intensity_groups() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+
+        assert_eq!(driver.group(1), Some(0));
+        driver.assign_group(1, 1).unwrap();
+        driver.assign_group(3, 1).unwrap();
+        assert_eq!(driver.group(3), Some(1));
+        assert_eq!(driver.group(4), None);
+
+        driver.set_group_intensity(0, 0x01).unwrap();
+        driver.set_group_intensity(1, 0x0F).unwrap();
+        // Empty group: nothing is sent
+        driver.set_group_intensity(7, 0x05).unwrap();
+
+        // One frame per group, with no-ops for the other devices
+        assert_eq!(
+            recorder.frames(),
+            [
+                vec![
+                    (0, Register::Intensity, 0x01),
+                    (2, Register::Intensity, 0x01)
+                ],
+                vec![
+                    (1, Register::Intensity, 0x0F),
+                    (3, Register::Intensity, 0x0F)
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intensity_groups_invalid() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        assert_eq!(
+            driver.assign_group(2, 1),
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
+        assert_eq!(
+            driver.set_group_intensity(0, 0x10),
+            Err(Error::InvalidIntensity)
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_flash_brightness() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+
+        driver.set_intensity(0, 0x03).unwrap();
+        driver
+            .flash_brightness(&mut NoopDelay::new(), 0x0F, 200)
+            .unwrap();
+        assert_eq!(
+            driver.flash_brightness(&mut NoopDelay::new(), 0x10, 200),
+            Err(Error::InvalidIntensity)
+        );
+
+        // Each device gets its own level back
+        assert_eq!(
+            recorder.writes(),
+            [
+                (0, Register::Intensity, 0x03),
+                (0, Register::Intensity, 0x0F),
+                (1, Register::Intensity, 0x0F),
+                (0, Register::Intensity, 0x03),
+                (1, Register::Intensity, 0x00),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_idle_hook_runs_before_flash_hold() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn count() {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let mut spi = FailingSpi::default();
+        let mut driver = Max7219::new(&mut spi);
+        driver.set_idle_hook(Some(count));
+
+        driver
+            .flash_brightness(&mut NoopDelay::new(), 0x0F, 200)
+            .unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+        driver.set_idle_hook(None);
+        driver
+            .flash_brightness(&mut NoopDelay::new(), 0x0F, 200)
+            .unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_flash_brightness_restores_after_error() {
+        // Write 0: set_intensity, write 1: peak (fails), write 2: restore
+        let mut spi = FailingSpi::failing_on(1);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        driver.set_intensity(0, 0x02).unwrap();
+        let result = driver.flash_brightness(&mut NoopDelay::new(), 0x0F, 200);
+        assert_eq!(
+            result,
+            Err(Error::SpiError(embedded_hal::spi::ErrorKind::Other))
+        );
+
+        assert_eq!(
+            spi.writes,
+            [
+                vec![Register::Intensity.addr(), 0x02, 0x00, 0x00],
+                vec![
+                    Register::Intensity.addr(),
+                    0x0F,
+                    Register::Intensity.addr(),
+                    0x0F
+                ],
+                vec![
+                    Register::Intensity.addr(),
+                    0x02,
+                    Register::Intensity.addr(),
+                    0x00
+                ],
+            ]
+        );
+    }
+
     #[test]
     fn test_init() {
         // Mock the sequence of calls made by init() for 1 device
+        // 0. noop_sync() -> NoOp 0x00
         // 1. power_on() -> Shutdown 0x01
         // 2. test_all(false) -> DisplayTest 0x00
         // 3. set_scan_limit_all(NUM_DIGITS) -> ScanLimit (NUM_DIGITS-1)
         // 4. set_decode_mode_all(NoDecode) -> DecodeMode 0x00
-        // 5. clear_all() -> 8 separate calls to write_all_registers for each digit reg
+        // 5. set_intensity_all(DEFAULT_INITIAL_INTENSITY) -> Intensity 0x07
+        // 6. clear_all() -> 8 separate calls to write_all_registers for each digit reg
 
         // Use vec![] macro to create the vector with all expected transactions
         let expected_transactions = vec![
+            // 0. noop_sync (write_all_registers)
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::NoOp.addr(), 0x00]),
+            Transaction::transaction_end(),
             // 1. power_on (write_all_registers)
             Transaction::transaction_start(),
             Transaction::write_vec(vec![Register::Shutdown.addr(), 0x01]),
@@ -745,7 +1967,11 @@ mod tests {
                 DecodeMode::NoDecode.value(),
             ]),
             Transaction::transaction_end(),
-            // 5. clear_all() - 8 separate write_all_registers calls, one for each digit reg
+            // 5. set_intensity_all (write_all_registers)
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Intensity.addr(), DEFAULT_INITIAL_INTENSITY]),
+            Transaction::transaction_end(),
+            // 6. clear_all() - 8 separate write_all_registers calls, one for each digit reg
             // Unroll the loop for clarity and to include all transactions in the vec![] macro
             Transaction::transaction_start(),
             Transaction::write_vec(vec![Register::Digit0.addr(), 0x00]),
@@ -780,6 +2006,23 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_noop_sync() {
+        // One packet per device, all no-ops, nothing tracked
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec([Register::NoOp.addr(), 0x00].repeat(3)),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+
+        driver.noop_sync().unwrap();
+        assert!(!driver.is_powered());
+        assert_eq!(driver.intensity(2), Some(0));
+        spi.done();
+    }
+
     #[test]
     fn test_write_device_register_valid_index() {
         let expected_transactions = [
@@ -812,7 +2055,10 @@ mod tests {
             .expect("Should accept valid count");
 
         let result = driver.write_device_register(2, Register::Shutdown, 0x01); // Index 2 is invalid for device_count=2
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
 
         spi.done();
     }
@@ -841,6 +2087,327 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_long_chain_device_count() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::<_, 12>::new_chain(&mut spi)
+            .with_device_count(12)
+            .expect("Should accept a full chain");
+        assert_eq!(driver.device_count(), 12);
+        assert!(matches!(
+            Max7219::<_, 12>::new_chain(&mut spi).with_device_count(13),
+            Err(Error::InvalidDeviceCount)
+        ));
+        spi.done();
+    }
+
+    #[test]
+    fn test_long_chain_init() {
+        let mut expected_transactions = Vec::new();
+        let sync = (Register::NoOp, 0x00);
+        for (register, data) in [sync]
+            .into_iter()
+            .chain(frame::init_sequence(&InitConfig::new()))
+        {
+            expected_transactions.extend([
+                Transaction::transaction_start(),
+                Transaction::write_vec([register.addr(), data].repeat(12)),
+                Transaction::transaction_end(),
+            ]);
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::<_, 12>::new_chain(&mut spi)
+            .with_device_count(12)
+            .unwrap();
+
+        driver.init().expect("Init should succeed");
+        assert!(driver.is_powered());
+        assert!(driver.is_device_powered(11));
+        spi.done();
+    }
+
+    #[test]
+    fn test_long_chain_write_all_registers() {
+        let ops: Vec<(Register, u8)> = (0..12).map(|i| (Register::Intensity, i)).collect();
+        let bytes: Vec<u8> = ops
+            .iter()
+            .flat_map(|&(register, data)| [register.addr(), data])
+            .collect();
+        assert_eq!(bytes.len(), 24);
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(bytes),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::<_, 12>::new_chain(&mut spi)
+            .with_device_count(12)
+            .unwrap();
+
+        driver
+            .write_all_registers(&ops)
+            .expect("should write all registers");
+
+        let result = driver.set_intensity(12, 0x01);
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex {
+                index: 12,
+                count: 12
+            })
+        );
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidDeviceIndex);
+        spi.done();
+    }
+
+    #[test]
+    fn test_register_cache_tracks_writes() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+
+        // Power-up state of the chip
+        assert_eq!(driver.intensity(0), Some(0));
+        assert_eq!(driver.scan_limit(0), Some(1));
+        assert_eq!(driver.decode_mode(0), Some(DecodeMode::NoDecode));
+        assert!(!driver.is_device_powered(0));
+
+        driver.power_on_device(1).unwrap();
+        driver.set_intensity(1, 0x0C).unwrap();
+        driver.set_device_scan_limit(2, 4).unwrap();
+        driver
+            .set_device_decode_mode(0, DecodeMode::Digits0To3)
+            .unwrap();
+
+        assert!(driver.is_device_powered(1));
+        assert!(!driver.is_device_powered(0));
+        assert_eq!(driver.intensity(1), Some(0x0C));
+        assert_eq!(driver.intensity(0), Some(0));
+        assert_eq!(driver.scan_limit(2), Some(4));
+        assert_eq!(driver.scan_limit(1), Some(1));
+        assert_eq!(driver.decode_mode(0), Some(DecodeMode::Digits0To3));
+        assert_eq!(driver.decode_mode(2), Some(DecodeMode::NoDecode));
+
+        // Out-of-range indices have no cached state
+        assert_eq!(driver.intensity(3), None);
+        assert_eq!(driver.scan_limit(3), None);
+        assert_eq!(driver.decode_mode(3), None);
+
+        // A raw decode pattern without a variant is not reported
+        driver
+            .batch(|b| b.write(2, Register::DecodeMode, 0x03))
+            .unwrap();
+        assert_eq!(driver.decode_mode(2), None);
+
+        // init brings every device back to the configured state
+        driver.init().unwrap();
+        for device_index in 0..3 {
+            assert!(driver.is_device_powered(device_index));
+            assert_eq!(
+                driver.intensity(device_index),
+                Some(DEFAULT_INITIAL_INTENSITY)
+            );
+            assert_eq!(driver.scan_limit(device_index), Some(NUM_DIGITS));
+            assert_eq!(driver.decode_mode(device_index), Some(DecodeMode::NoDecode));
+        }
+    }
+
+    #[test]
+    fn test_register_cache_at_chain_end() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder)
+            .with_device_count(MAX_DISPLAYS)
+            .unwrap();
+        let last = MAX_DISPLAYS - 1;
+
+        driver.set_intensity(last, 0x03).unwrap();
+        driver.power_on_device(last).unwrap();
+        driver.assign_group(last, 2).unwrap();
+        assert_eq!(driver.intensity(last), Some(0x03));
+        assert!(driver.is_device_powered(last));
+        assert_eq!(driver.group(last), Some(2));
+
+        assert_eq!(driver.intensity(MAX_DISPLAYS), None);
+        assert!(!driver.is_device_powered(MAX_DISPLAYS));
+        assert_eq!(driver.group(MAX_DISPLAYS), None);
+        assert!(driver.assign_group(MAX_DISPLAYS, 1).is_err());
+
+        // Shrinking and growing again at the far end, index 0, keeps the group of
+        // the device that stayed and resets those of the re-added devices
+        driver.assign_group(0, 1).unwrap();
+        driver.set_device_count(4).unwrap();
+        assert_eq!(driver.group(last), None);
+        assert_eq!(driver.group(3), Some(2));
+        driver.set_device_count(MAX_DISPLAYS).unwrap();
+        assert_eq!(driver.group(last), Some(2));
+        assert_eq!(driver.group(0), Some(0));
+    }
+
+    #[test]
+    fn test_register_cache_ignores_failed_writes() {
+        let mut spi = FailingSpi::failing_on(1);
+        let mut driver = Max7219::new(&mut spi);
+        driver.set_intensity(0, 0x0F).unwrap();
+        assert!(driver.set_device_scan_limit(0, 8).is_err());
+        assert_eq!(driver.intensity(0), Some(0x0F));
+        assert_eq!(driver.scan_limit(0), Some(1));
+    }
+
+    /// Frames sent by `init_with(config)` on a chain of two devices.
+    fn init_frames(config: &InitConfig) -> Vec<Vec<RegisterWrite>> {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        driver.init_with(config).unwrap();
+        recorder.frames().to_vec()
+    }
+
+    fn both(register: Register, data: u8) -> Vec<RegisterWrite> {
+        vec![(0, register, data), (1, register, data)]
+    }
+
+    #[test]
+    fn test_init_with_options() {
+        let default = init_frames(&InitConfig::new());
+        assert_eq!(default.len(), 5 + 8);
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        driver.init().unwrap();
+        // init starts with a frame of no-ops, which the recorder shows as empty
+        let (sync, frames) = recorder.frames().split_first().unwrap();
+        assert!(sync.is_empty());
+        assert_eq!(frames, default);
+
+        let changed = |index: usize, register: Register, data: u8| {
+            let mut frames = default.clone();
+            frames[index] = both(register, data);
+            frames
+        };
+
+        let config = InitConfig::new();
+        assert_eq!(init_frames(&config.skip_clear(true)), default[..5]);
+        assert_eq!(
+            init_frames(&config.powered_on(false)),
+            changed(0, Register::Shutdown, 0x00)
+        );
+        assert_eq!(
+            init_frames(&config.scan_limit(4)),
+            changed(2, Register::ScanLimit, 0x03)
+        );
+        assert_eq!(
+            init_frames(&config.decode_mode(DecodeMode::AllDigits)),
+            changed(3, Register::DecodeMode, 0xFF)
+        );
+        assert_eq!(
+            init_frames(&config.initial_intensity(Some(0x02))),
+            changed(4, Register::Intensity, 0x02)
+        );
+        let mut without_intensity = default.clone();
+        without_intensity.remove(4);
+        assert_eq!(
+            init_frames(&config.initial_intensity(None)),
+            without_intensity
+        );
+    }
+
+    #[test]
+    fn test_init_with_invalid_config() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder);
+        assert_eq!(
+            driver.init_with(&InitConfig::new().scan_limit(0)),
+            Err(Error::InvalidScanLimit)
+        );
+        assert_eq!(
+            driver.init_with(&InitConfig::new().initial_intensity(Some(0x10))),
+            Err(Error::InvalidIntensity)
+        );
+
+        driver
+            .init_with(&InitConfig::new().powered_on(false))
+            .unwrap();
+        assert!(!driver.is_powered());
+        assert_eq!(recorder.frames().len(), 13);
+    }
+
+    #[test]
+    fn test_reapply_config() {
+        let config_writes: Vec<[u8; 4]> = vec![
+            // power_on
+            [
+                Register::Shutdown.addr(),
+                0x01,
+                Register::Shutdown.addr(),
+                0x01,
+            ],
+            // power_off_device(1)
+            [0x00, 0x00, Register::Shutdown.addr(), 0x00],
+            // set_intensity(0, 0x03)
+            [Register::Intensity.addr(), 0x03, 0x00, 0x00],
+            // set_device_scan_limit(1, 4)
+            [0x00, 0x00, Register::ScanLimit.addr(), 0x03],
+            // set_device_decode_mode(0, AllDigits)
+            [Register::DecodeMode.addr(), 0xFF, 0x00, 0x00],
+            // test_device(1, true)
+            [0x00, 0x00, Register::DisplayTest.addr(), 0x01],
+        ];
+        let reapplied: Vec<[u8; 4]> = vec![
+            [
+                Register::Shutdown.addr(),
+                0x01,
+                Register::Shutdown.addr(),
+                0x00,
+            ],
+            [
+                Register::DisplayTest.addr(),
+                0x00,
+                Register::DisplayTest.addr(),
+                0x01,
+            ],
+            [
+                Register::ScanLimit.addr(),
+                0x00,
+                Register::ScanLimit.addr(),
+                0x03,
+            ],
+            [
+                Register::DecodeMode.addr(),
+                0xFF,
+                Register::DecodeMode.addr(),
+                0x00,
+            ],
+            [
+                Register::Intensity.addr(),
+                0x03,
+                Register::Intensity.addr(),
+                0x00,
+            ],
+        ];
+        let expected_transactions: Vec<Transaction<u8>> = config_writes
+            .iter()
+            .chain(&reapplied)
+            .flat_map(|frame| {
+                [
+                    Transaction::transaction_start(),
+                    Transaction::write_vec(frame.to_vec()),
+                    Transaction::transaction_end(),
+                ]
+            })
+            .collect();
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        driver.power_on().unwrap();
+        driver.power_off_device(1).unwrap();
+        driver.set_intensity(0, 0x03).unwrap();
+        driver.set_device_scan_limit(1, 4).unwrap();
+        driver
+            .set_device_decode_mode(0, DecodeMode::AllDigits)
+            .unwrap();
+        driver.test_device(1, true).unwrap();
+
+        driver.reapply_config().unwrap();
+        spi.done();
+    }
+
     #[test]
     fn test_test_device_enable_disable() {
         let expected_transactions = [
@@ -894,6 +2461,524 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_initial_intensity() {
+        let intensity_writes = |recorder: &FrameRecorder| {
+            recorder
+                .writes()
+                .into_iter()
+                .filter(|&(_, register, _)| register == Register::Intensity)
+                .collect::<Vec<_>>()
+        };
+
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        driver.init().unwrap();
+        assert_eq!(
+            intensity_writes(&recorder),
+            [
+                (0, Register::Intensity, 0x07),
+                (1, Register::Intensity, 0x07)
+            ]
+        );
+
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder)
+            .with_initial_intensity(Some(0x02))
+            .unwrap();
+        driver.init().unwrap();
+        assert_eq!(
+            intensity_writes(&recorder),
+            [(0, Register::Intensity, 0x02)]
+        );
+
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder)
+            .with_initial_intensity(None)
+            .unwrap();
+        driver.init().unwrap();
+        assert!(intensity_writes(&recorder).is_empty());
+
+        let mut spi = SpiMock::new(&[]);
+        let result = Max7219::new(&mut spi).with_initial_intensity(Some(0x10));
+        assert!(matches!(result, Err(Error::InvalidIntensity)));
+        spi.done();
+    }
+
+    #[test]
+    fn test_clamp_policy() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        assert_eq!(driver.set_intensity(0, 0x10), Err(Error::InvalidIntensity));
+        assert_eq!(driver.set_intensity_all(0xFF), Err(Error::InvalidIntensity));
+        assert_eq!(
+            driver.set_group_intensity(0, 0x20),
+            Err(Error::InvalidIntensity)
+        );
+        spi.done();
+
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder)
+            .with_device_count(2)
+            .unwrap()
+            .with_clamp_policy(ClampPolicy::Clamp);
+        driver.set_intensity(0, 0x10).unwrap();
+        driver.set_intensity_all(0xFF).unwrap();
+        driver.set_intensity(1, 0x03).unwrap();
+        assert_eq!(
+            recorder.frames(),
+            [
+                vec![(0, Register::Intensity, 0x0F)],
+                vec![
+                    (0, Register::Intensity, 0x0F),
+                    (1, Register::Intensity, 0x0F)
+                ],
+                vec![(1, Register::Intensity, 0x03)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_init_preserve_display() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        driver.init_preserve_display().unwrap();
+        assert!(driver.is_powered());
+
+        let registers: Vec<_> = recorder.frames().iter().map(|frame| frame[0].1).collect();
+        assert_eq!(
+            registers,
+            [
+                Register::Shutdown,
+                Register::DisplayTest,
+                Register::ScanLimit,
+                Register::DecodeMode,
+                Register::Intensity,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_init_device() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+        driver.init_device(1).unwrap();
+        assert_eq!(
+            driver.init_device(3),
+            Err(Error::InvalidDeviceIndex { index: 3, count: 3 })
+        );
+        assert_eq!(driver.intensity(1), Some(DEFAULT_INITIAL_INTENSITY));
+        assert_eq!(driver.intensity(0), Some(0));
+
+        // Only device 1 receives anything but no-ops
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 5 + 8);
+        assert!(
+            frames
+                .iter()
+                .all(|frame| frame.len() == 1 && frame[0].0 == 1)
+        );
+        assert_eq!(frames[0], [(1, Register::Shutdown, 1)]);
+        assert_eq!(frames[12], [(1, Register::Digit7, 0)]);
+    }
+
+    #[test]
+    fn test_power_on_staggered() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::new(LoggingSpi(&log)).with_device_count(3).unwrap();
+        let mut delay = LoggingDelay(&log);
+        driver.power_on_staggered(&mut delay, 20).unwrap();
+        assert!(driver.is_powered());
+
+        let shutdown = Register::Shutdown.addr();
+        assert_eq!(
+            log.take(),
+            [
+                Event::Write(vec![shutdown, 1, 0, 0, 0, 0]),
+                Event::Wait(20_000_000),
+                Event::Write(vec![0, 0, shutdown, 1, 0, 0]),
+                Event::Wait(20_000_000),
+                Event::Write(vec![0, 0, 0, 0, shutdown, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_test_cycle() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::new(LoggingSpi(&log)).with_device_count(3).unwrap();
+        let mut delay = LoggingDelay(&log);
+        driver.test_cycle(&mut delay, 1_000).unwrap();
+
+        let test = Register::DisplayTest.addr();
+        assert_eq!(
+            log.take(),
+            [
+                Event::Write(vec![test, 1, 0, 0, 0, 0]),
+                Event::Wait(1_000),
+                Event::Write(vec![test, 0, 0, 0, 0, 0]),
+                Event::Write(vec![0, 0, test, 1, 0, 0]),
+                Event::Wait(1_000),
+                Event::Write(vec![0, 0, test, 0, 0, 0]),
+                Event::Write(vec![0, 0, 0, 0, test, 1]),
+                Event::Wait(1_000),
+                Event::Write(vec![0, 0, 0, 0, test, 0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_test_cycle_cleans_up_after_error() {
+        // Write 0: device 0 on, write 1: device 0 off, write 2: device 1 on (fails)
+        let mut spi = FailingSpi::failing_on(2);
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+        assert_eq!(
+            driver.test_cycle(&mut NoopDelay::new(), 1_000),
+            Err(Error::SpiError(embedded_hal::spi::ErrorKind::Other))
+        );
+
+        let test = Register::DisplayTest.addr();
+        assert_eq!(
+            spi.writes,
+            [
+                vec![test, 1, 0, 0, 0, 0],
+                vec![test, 0, 0, 0, 0, 0],
+                vec![0, 0, test, 1, 0, 0],
+                vec![test, 0, test, 0, test, 0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_init_with_delay_staggered() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::new(LoggingSpi(&log)).with_device_count(2).unwrap();
+        let mut delay = LoggingDelay(&log);
+        let config = InitConfig::new().stagger_power_on(5);
+        driver.init_with_delay(&config, &mut delay).unwrap();
+        assert!(driver.is_powered());
+
+        // The sequence runs in shutdown, then the devices come on one by one
+        let events = log.take();
+        assert_eq!(events.len(), 5 + 8 + 3);
+        assert_eq!(
+            events[0],
+            Event::Write(vec![
+                Register::Shutdown.addr(),
+                0,
+                Register::Shutdown.addr(),
+                0
+            ])
+        );
+        assert_eq!(
+            events[13..],
+            [
+                Event::Write(vec![Register::Shutdown.addr(), 1, 0, 0]),
+                Event::Wait(5_000_000),
+                Event::Write(vec![0, 0, Register::Shutdown.addr(), 1]),
+            ]
+        );
+
+        // Without a delay the chain is powered on at once
+        driver.init_with(&config).unwrap();
+        let events = log.take();
+        assert_eq!(events.len(), 5 + 8);
+        assert_eq!(
+            events[0],
+            Event::Write(vec![
+                Register::Shutdown.addr(),
+                1,
+                Register::Shutdown.addr(),
+                1
+            ])
+        );
+    }
+
+    #[test]
+    fn test_set_device_count_inits_added_devices() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        driver.init().unwrap();
+        driver.set_device_count(4).unwrap();
+        assert_eq!(driver.device_count(), 4);
+        assert!(driver.is_powered());
+
+        // Full init of the first two devices, then the same steps for the new ones
+        // without the leading no-op frame. The new devices are at the far end,
+        // indices 0 and 1.
+        let init_frames = 5 + 8;
+        let (initial, grown) = recorder.frames()[1..].split_at(init_frames);
+        assert!(
+            initial
+                .iter()
+                .all(|frame| frame.len() == 2 && frame[1].0 == 1)
+        );
+        assert_eq!(grown.len(), init_frames);
+        assert!(
+            grown
+                .iter()
+                .all(|frame| frame.len() == 2 && frame[0].0 == 0 && frame[1].0 == 1)
+        );
+        assert_eq!(
+            grown[0],
+            [(0, Register::Shutdown, 1), (1, Register::Shutdown, 1)]
+        );
+        assert_eq!(
+            grown[4],
+            [
+                (0, Register::Intensity, 0x07),
+                (1, Register::Intensity, 0x07)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_device_count_follows_plugged_modules() {
+        for order in [ChainOrder::FarthestFirst, ChainOrder::NearestFirst] {
+            // Position from the MCU of device `i` in a chain of `count`
+            let physical = |i: usize, count: usize| match order {
+                ChainOrder::FarthestFirst => count - 1 - i,
+                ChainOrder::NearestFirst => i,
+            };
+            let chain = RefCell::new(ShiftChain::new(2));
+            let mut driver = Max7219::new(ChainSpi(&chain))
+                .with_device_count(2)
+                .unwrap()
+                .with_chain_order(order);
+            driver.init().unwrap();
+            driver.set_intensity(0, 0x01).unwrap();
+            driver.set_intensity(1, 0x02).unwrap();
+            driver.write_raw_digit(0, 0, 0xAA).unwrap();
+            driver.write_raw_digit(1, 0, 0xBB).unwrap();
+
+            chain.borrow_mut().plug_in(2);
+            driver.set_device_count(4).unwrap();
+
+            // The running modules keep what they show, the new ones are initialized
+            for (i, (level, digit)) in [(0x01, 0xAA), (0x02, 0xBB)].into_iter().enumerate() {
+                let chain = chain.borrow();
+                assert_eq!(chain.register(physical(i, 2), Register::Intensity), level);
+                assert_eq!(chain.register(physical(i, 2), Register::Digit0), digit);
+            }
+            for position in 2..4 {
+                let chain = chain.borrow();
+                assert_eq!(chain.register(position, Register::Shutdown), 0x01);
+                assert_eq!(chain.register(position, Register::Intensity), 0x07);
+                assert_eq!(chain.register(position, Register::ScanLimit), 0x07);
+            }
+            for i in 0..4 {
+                let level = chain.borrow().register(physical(i, 4), Register::Intensity);
+                assert_eq!(driver.intensity(i), Some(level), "{order:?} device {i}");
+                assert!(driver.is_device_powered(i));
+            }
+
+            driver.set_intensity(0, 0x0F).unwrap();
+            assert_eq!(
+                chain.borrow().register(physical(0, 4), Register::Intensity),
+                0x0F
+            );
+
+            chain.borrow_mut().unplug(1);
+            driver.set_device_count(3).unwrap();
+            for i in 0..3 {
+                let level = chain.borrow().register(physical(i, 3), Register::Intensity);
+                assert_eq!(driver.intensity(i), Some(level), "{order:?} device {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_device_count_shrink_and_invalid() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+
+        driver.set_device_count(2).unwrap();
+        assert_eq!(driver.device_count(), 2);
+        assert_eq!(
+            driver.set_device_count(MAX_DISPLAYS + 1),
+            Err(Error::InvalidDeviceCount)
+        );
+        assert_eq!(driver.device_count(), 2);
+        spi.done();
+    }
+
+    #[test]
+    fn test_set_device_count_frame_widths_follow() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::new(LoggingSpi(&log)).with_device_count(4).unwrap();
+        let widths = |log: &RefCell<Vec<Event>>| -> Vec<usize> {
+            log.take()
+                .iter()
+                .map(|event| match event {
+                    Event::Write(bytes) => bytes.len(),
+                    Event::Wait(_) => 0,
+                })
+                .collect()
+        };
+
+        driver.power_on().unwrap();
+        assert_eq!(widths(&log), [8]);
+
+        driver.set_device_count(1).unwrap();
+        driver.power_on().unwrap();
+        driver
+            .write_device_register(0, Register::Digit0, 0xFF)
+            .unwrap();
+        assert_eq!(widths(&log), [2, 2]);
+
+        // Growing to three devices initializes two, in three-device frames
+        driver.set_device_count(3).unwrap();
+        assert_eq!(widths(&log), [6; 5 + 8]);
+        driver.power_on().unwrap();
+        assert_eq!(widths(&log), [6]);
+    }
+
+    #[test]
+    fn test_set_device_count_unchecked_clamps() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::<_, 2>::new_chain(LoggingSpi(&log));
+        driver.set_device_count_unchecked(5);
+        assert_eq!(driver.device_count(), 2);
+
+        driver.power_on().unwrap();
+        assert!(driver.is_powered());
+        assert_eq!(driver.intensity(1), Some(0));
+        assert_eq!(driver.intensity(2), None);
+        assert_eq!(log.take(), [Event::Write(vec![0x0C, 0x01, 0x0C, 0x01])]);
+    }
+
+    #[test]
+    fn test_write_register_raw() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+        driver.write_register(1, 0x0A, 0x05).unwrap();
+        driver.write_register(2, 0x01, 0x81).unwrap();
+
+        assert_eq!(driver.intensity(1), Some(0x05));
+        assert_eq!(
+            recorder.writes(),
+            [(1, Register::Intensity, 0x05), (2, Register::Digit0, 0x81)]
+        );
+    }
+
+    #[test]
+    fn test_write_register_rejects_unknown_addresses() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        for addr in [0x0D, 0x0E, 0x10, 0xFF] {
+            assert_eq!(
+                driver.write_register(0, addr, 0x01),
+                Err(Error::InvalidRegister)
+            );
+        }
+        assert_eq!(
+            driver.write_register(2, 0x0F, 0x01),
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_register_pads_with_noops() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![0x00, 0x00, 0x0F, 0x01, 0x00, 0x00]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+
+        driver.write_register(1, 0x0F, 0x01).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_chain_order_mirrors_frames() {
+        let intensity = Register::Intensity.addr();
+        let digit0 = Register::Digit0.addr();
+        for (order, expected) in [
+            (
+                ChainOrder::FarthestFirst,
+                [
+                    vec![intensity, 0x05, 0, 0, 0, 0],
+                    vec![digit0, 1, digit0, 2, digit0, 3],
+                ],
+            ),
+            (
+                ChainOrder::NearestFirst,
+                [
+                    vec![0, 0, 0, 0, intensity, 0x05],
+                    vec![digit0, 3, digit0, 2, digit0, 1],
+                ],
+            ),
+        ] {
+            let log = RefCell::new(Vec::new());
+            let mut driver = Max7219::new(LoggingSpi(&log))
+                .with_device_count(3)
+                .unwrap()
+                .with_chain_order(order);
+            assert_eq!(driver.chain_order(), order);
+
+            driver.set_intensity(0, 0x05).unwrap();
+            driver
+                .write_all_registers(&[
+                    (Register::Digit0, 1),
+                    (Register::Digit0, 2),
+                    (Register::Digit0, 3),
+                ])
+                .unwrap();
+
+            // The records follow the logical index
+            assert_eq!(driver.intensity(0), Some(0x05));
+            assert_eq!(driver.intensity(2), Some(0x00));
+            assert_eq!(log.take(), expected.map(Event::Write));
+        }
+    }
+
+    #[test]
+    fn test_write_raw_frame_is_sent_verbatim() {
+        let frame = [0x0A, 0x0F, 0x0F, 0x01, 0x01, 0x81];
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(frame.to_vec()),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+
+        driver.write_raw_frame(&frame).unwrap();
+        spi.done();
+
+        // The recorder decodes the same frame
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+        driver.write_raw_frame(&frame).unwrap();
+        assert_eq!(
+            recorder.frames(),
+            [vec![
+                (0, Register::Intensity, 0x0F),
+                (1, Register::DisplayTest, 0x01),
+                (2, Register::Digit0, 0x81),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_write_raw_frame_invalid_length() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        assert_eq!(
+            driver.write_raw_frame(&[0x0A, 0x0F, 0x0C]),
+            Err(Error::InvalidFrameLength)
+        );
+        assert_eq!(
+            driver.write_raw_frame(&[0x0C, 0x01, 0x0C, 0x01, 0x0C, 0x01]),
+            Err(Error::InvalidFrameLength)
+        );
+        spi.done();
+    }
+
     #[test]
     fn test_set_device_decode_mode() {
         let mode = DecodeMode::Digits0To3;
@@ -911,6 +2996,55 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_set_intensities() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::Intensity.addr(),
+                0x01,
+                Register::Intensity.addr(),
+                0x08,
+                Register::Intensity.addr(),
+                0x0F,
+            ]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+
+        assert_eq!(
+            driver.set_intensities(&[0x01, 0x08]),
+            Err(Error::InvalidDeviceCount)
+        );
+        assert_eq!(
+            driver.set_intensities(&[0x01, 0x10, 0x02]),
+            Err(Error::InvalidIntensity)
+        );
+        driver.set_intensities(&[0x01, 0x08, 0x0F]).unwrap();
+        assert_eq!(driver.intensity(1), Some(0x08));
+        spi.done();
+    }
+
+    #[test]
+    fn test_set_intensity_all_full_chain() {
+        // One transaction for all eight devices, none for a rejected level
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec([Register::Intensity.addr(), 0x0C].repeat(MAX_DISPLAYS)),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi)
+            .with_device_count(MAX_DISPLAYS)
+            .unwrap();
+
+        driver.set_intensity_all(0x0C).unwrap();
+        assert_eq!(driver.set_intensity_all(0x10), Err(Error::InvalidIntensity));
+        assert_eq!(driver.intensity(MAX_DISPLAYS - 1), Some(0x0C));
+        spi.done();
+    }
+
     #[test]
     fn test_set_intensity_all() {
         let intensity = 0x05;
@@ -934,4 +3068,144 @@ mod tests {
             .expect("Set intensity all failed");
         spi.done();
     }
+
+    #[cfg(feature = "log")]
+    mod log_capture {
+        use std::cell::RefCell;
+        use std::string::String;
+        use std::vec::Vec;
+
+        std::thread_local! {
+            static RECORDS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        struct CaptureLogger;
+
+        impl log::Log for CaptureLogger {
+            fn enabled(&self, _metadata: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                RECORDS.with(|r| r.borrow_mut().push(format!("{}", record.args())));
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: CaptureLogger = CaptureLogger;
+
+        /// Installs the capturing logger (once) and clears this thread's records.
+        pub fn start() {
+            let _ = log::set_logger(&LOGGER);
+            log::set_max_level(log::LevelFilter::Trace);
+            RECORDS.with(|r| r.borrow_mut().clear());
+        }
+
+        /// Records logged on the current test's thread.
+        pub fn records() -> Vec<String> {
+            RECORDS.with(|r| r.borrow().clone())
+        }
+    }
+
+    /// Small xorshift generator, so the combinations below are reproducible.
+    fn next_random(state: &mut u32) -> u32 {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        *state
+    }
+
+    #[test]
+    fn test_untargeted_devices_only_get_noops() {
+        let mut state = 0x2545_F491;
+        for device_count in 1..=MAX_DISPLAYS {
+            for _ in 0..200 {
+                let mut spi = FailingSpi::default();
+                let mut driver = Max7219::new(&mut spi)
+                    .with_device_count(device_count)
+                    .unwrap();
+                let target = next_random(&mut state) as usize % device_count;
+                let data = next_random(&mut state) as u8;
+
+                match next_random(&mut state) % 9 {
+                    0 => driver.write_raw_digit(target, data % NUM_DIGITS, data),
+                    1 => driver.set_intensity(target, data & 0x0F),
+                    2 => driver.power_on_device(target),
+                    3 => driver.power_off_device(target),
+                    4 => driver.test_device(target, data & 1 != 0),
+                    5 => driver.set_device_scan_limit(target, data % NUM_DIGITS + 1),
+                    6 => driver.set_device_decode_mode(target, DecodeMode::Digit0),
+                    7 => driver.clear_display(target),
+                    _ => driver.batch(|b| b.write(target, Register::Digit7, data)),
+                }
+                .unwrap();
+
+                assert!(!spi.writes.is_empty());
+                for frame in &spi.writes {
+                    assert_eq!(frame.len(), device_count * 2);
+                    for (device_index, packet) in frame.chunks_exact(2).enumerate() {
+                        if device_index == target {
+                            assert_ne!(packet[0], Register::NoOp.addr());
+                        } else {
+                            assert_eq!(packet, [0x00, 0x00], "device {device_index}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_frames_after_shrinking_chain() {
+        let mut spi = FailingSpi::default();
+        let mut driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        driver.set_intensity_all(0x0F).unwrap();
+        driver.set_device_count(2).unwrap();
+        driver.write_raw_digit(1, 0, 0xAA).unwrap();
+
+        // Nothing of the earlier, longer frame is sent again
+        let last = spi.writes.last().unwrap();
+        assert_eq!(last, &[0x00, 0x00, Register::Digit0.addr(), 0xAA]);
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_log_frames() {
+        log_capture::start();
+
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::NoOp.addr(),
+                0x00,
+                Register::Digit3.addr(),
+                0xAA,
+            ]),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::Intensity.addr(),
+                0x05,
+                Register::Intensity.addr(),
+                0x05,
+            ]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        driver.write_raw_digit(1, 3, 0xAA).unwrap();
+        driver.set_intensity_all(0x05).unwrap();
+
+        assert_eq!(
+            log_capture::records(),
+            [
+                "max7219 device 1: Digit 3 <- 0xaa",
+                "max7219 device 0: Intensity <- 0x05",
+                "max7219 device 1: Intensity <- 0x05",
+            ]
+        );
+        spi.done();
+    }
 }
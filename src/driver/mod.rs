@@ -1,5 +1,32 @@
 //! Core MAX7219 driver implementation
 
+#![cfg_attr(not(test), deny(clippy::indexing_slicing))]
+
+#[cfg(feature = "async")]
+mod asynch;
+mod batch;
+mod brightness;
+mod config;
+#[cfg(feature = "eh0")]
+pub mod eh0;
+mod frame;
+mod init;
 mod max7219;
+mod probe;
+mod self_test;
+mod settle;
+mod stress;
 
-pub use max7219::Max7219;
+#[cfg(feature = "async")]
+pub use asynch::AsyncMax7219;
+pub use batch::{BATCH_CAPACITY, Batch};
+pub use brightness::{BrightnessController, BrightnessCurve, MAX_CURVE_POINTS, MAX_SEGMENTS};
+pub use config::DeviceConfig;
+pub use init::InitConfig;
+pub use max7219::{ChainOrder, ClampPolicy, DEFAULT_INITIAL_INTENSITY, Max7219};
+pub use probe::PROBE_DELAY_MS;
+pub use self_test::{
+    SELF_TEST_DISPLAY_MS, SELF_TEST_STEP_MS, SelfTestFailure, SelfTestReport, SelfTestStep,
+};
+pub use settle::SettleDelay;
+pub use stress::{STRESS_BLINKS, STRESS_STEP_MS};
@@ -0,0 +1,204 @@
+//! Guided detection of the number of devices in a daisy chain
+//!
+//! The MAX7219 has no way to report how many chips are connected, so
+//! [`Max7219::probe_chain`] lights candidate devices one at a time and relies on
+//! the user to confirm what they see.
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{Max7219, Result, driver::frame, error::Error, registers::Register};
+
+/// How long each probed device stays lit before the confirmation callback runs.
+pub const PROBE_DELAY_MS: u32 = 500;
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Detects the chain length with the help of a confirmation callback.
+    ///
+    /// A frame of `N` no-ops, `N` being the driver's longest chain
+    /// ([`MAX_DISPLAYS`](crate::MAX_DISPLAYS) by default), first pushes whatever
+    /// a reset left in the shift registers out of the chain. Then, for each
+    /// assumed length `k` from 1 to `N`, a frame of `k` packets is sent whose
+    /// first packet turns on display test mode. When the chain really has at
+    /// least `k` devices, that packet ends up in the `k`-th device counted from
+    /// the MCU, which lights up completely; otherwise it is shifted out of the end
+    /// of the chain and nothing changes.
+    ///
+    /// After [`PROBE_DELAY_MS`] the callback is called with `k` and should return
+    /// `true` if a new device lit up (hook it to a button or a serial prompt). Test
+    /// mode is then switched off on every device with a frame of `N` packets, so
+    /// only one device is lit at each step. Probing stops at the first `false`,
+    /// and the driver is reconfigured to the detected count.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if the very first device is not
+    /// confirmed, leaving the device count unchanged, or an SPI error if a transfer
+    /// fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let count = driver.probe_chain(&mut delay, |k| {
+    ///     println!("Did display {k} light up? Press the button if so.");
+    ///     button_pressed_within_3s()
+    /// })?;
+    /// driver.init()?;
+    /// ```
+    pub fn probe_chain<D, F>(&mut self, delay: &mut D, mut confirm: F) -> Result<usize>
+    where
+        D: DelayNs,
+        F: FnMut(usize) -> bool,
+    {
+        let mut detected = 0;
+        self.write_packets(&[(Register::NoOp, 0x00); N])?;
+
+        for assumed in 1..=N {
+            self.write_probe_frame(assumed)?;
+            self.idle();
+            delay.delay_ms(PROBE_DELAY_MS);
+            let lit = confirm(assumed);
+            self.write_packets(&[(Register::DisplayTest, 0x00); N])?;
+
+            if !lit {
+                break;
+            }
+            detected = assumed;
+        }
+
+        if detected == 0 {
+            return Err(Error::InvalidDeviceCount);
+        }
+        self.set_device_count_unchecked(detected);
+        Ok(detected)
+    }
+
+    /// Sends `(DisplayTest, 0x01)` as the first of `assumed` packets, padded with no-ops.
+    fn write_probe_frame(&mut self, assumed: usize) -> Result<()> {
+        let mut ops = [(Register::NoOp, 0x00); N];
+        if let Some(first) = ops.first_mut() {
+            *first = (Register::DisplayTest, 0x01);
+        }
+        self.write_packets(frame::active(&ops, assumed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        MAX_DISPLAYS,
+        test_support::{ChainSpi, ShiftChain},
+    };
+    use core::cell::RefCell;
+    use embedded_hal_mock::eh1::{
+        delay::NoopDelay,
+        spi::{Mock as SpiMock, Transaction},
+    };
+
+    fn transaction(frame: Vec<u8>) -> Vec<Transaction<u8>> {
+        vec![
+            Transaction::transaction_start(),
+            Transaction::write_vec(frame),
+            Transaction::transaction_end(),
+        ]
+    }
+
+    fn probe_frame(assumed: usize) -> Vec<Transaction<u8>> {
+        let mut frame = vec![0x00; assumed * 2];
+        frame[0] = Register::DisplayTest.addr();
+        frame[1] = 0x01;
+        transaction(frame)
+    }
+
+    fn test_off_frame() -> Vec<Transaction<u8>> {
+        transaction([Register::DisplayTest.addr(), 0x00].repeat(MAX_DISPLAYS))
+    }
+
+    fn sync_frame() -> Vec<Transaction<u8>> {
+        transaction(vec![0x00; MAX_DISPLAYS * 2])
+    }
+
+    #[test]
+    fn test_probe_chain_detects_three() {
+        let mut expected_transactions = sync_frame();
+        for assumed in 1..=4 {
+            expected_transactions.extend(probe_frame(assumed));
+            expected_transactions.extend(test_off_frame());
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi);
+
+        let mut asked = Vec::new();
+        let count = driver
+            .probe_chain(&mut NoopDelay::new(), |k| {
+                asked.push(k);
+                k <= 3
+            })
+            .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(asked, [1, 2, 3, 4]);
+        assert_eq!(driver.device_count(), 3);
+        spi.done();
+    }
+
+    #[test]
+    fn test_probe_chain_full_chain() {
+        let mut expected_transactions = sync_frame();
+        for assumed in 1..=MAX_DISPLAYS {
+            expected_transactions.extend(probe_frame(assumed));
+            expected_transactions.extend(test_off_frame());
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi);
+
+        let count = driver.probe_chain(&mut NoopDelay::new(), |_| true).unwrap();
+        assert_eq!(count, MAX_DISPLAYS);
+        assert_eq!(driver.device_count(), MAX_DISPLAYS);
+        spi.done();
+    }
+
+    #[test]
+    fn test_probe_chain_nothing_lit() {
+        let mut expected_transactions = sync_frame();
+        expected_transactions.extend(probe_frame(1));
+        expected_transactions.extend(test_off_frame());
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        let result = driver.probe_chain(&mut NoopDelay::new(), |_| false);
+        assert_eq!(result, Err(Error::InvalidDeviceCount));
+        assert_eq!(driver.device_count(), 2);
+        spi.done();
+    }
+
+    #[test]
+    fn test_probe_chain_lights_one_device_at_a_time() {
+        for devices in 1..=MAX_DISPLAYS {
+            // A reset mid-transfer left display test packets in every device
+            let chain = RefCell::new(ShiftChain::with_garbage(
+                devices,
+                [Register::DisplayTest.addr(), 0x01],
+            ));
+            let mut driver = Max7219::new(ChainSpi(&chain));
+
+            // Answer honestly: only device `k` may be lit, counted from 1
+            let count = driver
+                .probe_chain(&mut NoopDelay::new(), |k| {
+                    let lit = chain.borrow().lit();
+                    if k <= devices {
+                        assert_eq!(lit, [k - 1], "step {k} of {devices} devices");
+                    } else {
+                        assert!(lit.is_empty(), "step {k} of {devices} devices");
+                    }
+                    !lit.is_empty()
+                })
+                .unwrap();
+            assert_eq!(count, devices);
+            assert!(chain.borrow().lit().is_empty());
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Power-on self test of every device in the chain
+//!
+//! The MAX7219 cannot be read back, so [`Max7219::self_test`] cannot tell whether
+//! an LED is broken. It drives every device through display test mode and a
+//! walking-bit pattern for someone to watch, and reports which device's writes
+//! failed on the bus.
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{MAX_DISPLAYS, Max7219, NUM_DIGITS, Result, error::Error, registers::Register};
+
+/// How long display test mode stays on for each device.
+pub const SELF_TEST_DISPLAY_MS: u32 = 200;
+
+/// How long each step of the walking-bit pattern is shown.
+pub const SELF_TEST_STEP_MS: u32 = 5;
+
+/// Part of [`Max7219::self_test`] during which a write failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SelfTestStep {
+    /// Switching display test mode on or off.
+    DisplayTest,
+    /// Walking a bit through, or clearing, one digit register.
+    Pattern {
+        /// The digit (0 to 7) being written.
+        digit: u8,
+    },
+}
+
+/// The first failed write of one device.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestFailure {
+    /// What the self test was doing.
+    pub step: SelfTestStep,
+    /// The error the write returned.
+    pub error: Error,
+}
+
+/// Outcome of [`Max7219::self_test`], per device index.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestReport<const N: usize = MAX_DISPLAYS> {
+    device_count: usize,
+    failures: [Option<SelfTestFailure>; N],
+}
+
+impl<const N: usize> SelfTestReport<N> {
+    /// Number of devices that were tested.
+    pub fn device_count(&self) -> usize {
+        self.device_count
+    }
+
+    /// Returns `true` if every write to every device succeeded.
+    pub fn passed(&self) -> bool {
+        self.failures.iter().all(Option::is_none)
+    }
+
+    /// Returns the first failure of a device, or `None` if it passed or the index
+    /// is out of range.
+    pub fn failure(&self, device_index: usize) -> Option<&SelfTestFailure> {
+        self.failures.get(device_index)?.as_ref()
+    }
+}
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Runs a visual self test on each device in turn and reports bus errors.
+    ///
+    /// For every device, display test mode is switched on for
+    /// [`SELF_TEST_DISPLAY_MS`] and off again. Then a single lit segment walks
+    /// through each digit register, bit 0 to bit 7, [`SELF_TEST_STEP_MS`] per
+    /// step, and the digit is cleared. Only the device under test is written; the
+    /// others receive no-ops. The idle hook, if any, runs before every wait.
+    ///
+    /// A failed write ends the test of that device and is recorded in the report
+    /// with the step it happened in; the remaining devices are still tested. Run it
+    /// after [`Self::init`], as the pattern relies on no-decode mode, a full scan
+    /// limit and the display being powered on. Every digit of the devices that
+    /// passed is blank afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let report = driver.self_test(&mut delay)?;
+    /// for device_index in 0..report.device_count() {
+    ///     if let Some(failure) = report.failure(device_index) {
+    ///         defmt::error!("device {}: {:?}", device_index, failure);
+    ///     }
+    /// }
+    /// ```
+    pub fn self_test<D: DelayNs>(&mut self, delay: &mut D) -> Result<SelfTestReport<N>> {
+        let mut report = SelfTestReport {
+            device_count: self.device_count(),
+            failures: [const { None }; N],
+        };
+
+        for (device_index, failure) in report
+            .failures
+            .iter_mut()
+            .enumerate()
+            .take(report.device_count)
+        {
+            *failure = self.self_test_device(device_index, delay).err();
+        }
+        Ok(report)
+    }
+
+    /// Runs the self test steps on one device, stopping at the first failure.
+    fn self_test_device<D: DelayNs>(
+        &mut self,
+        device_index: usize,
+        delay: &mut D,
+    ) -> core::result::Result<(), SelfTestFailure> {
+        let step = SelfTestStep::DisplayTest;
+        let failed = |error| SelfTestFailure { step, error };
+        self.write_device_register(device_index, Register::DisplayTest, 0x01)
+            .map_err(failed)?;
+        self.idle();
+        delay.delay_ms(SELF_TEST_DISPLAY_MS);
+        self.write_device_register(device_index, Register::DisplayTest, 0x00)
+            .map_err(failed)?;
+
+        for digit in 0..NUM_DIGITS {
+            let step = SelfTestStep::Pattern { digit };
+            let failed = |error| SelfTestFailure { step, error };
+            for bit in 0..8 {
+                self.write_raw_digit(device_index, digit, 1 << bit)
+                    .map_err(failed)?;
+                self.idle();
+                delay.delay_ms(SELF_TEST_STEP_MS);
+            }
+            self.write_raw_digit(device_index, digit, 0x00)
+                .map_err(failed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FailingSpi;
+    use crate::test_util::FrameRecorder;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+
+    #[test]
+    fn test_self_test_sequence() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+
+        let report = driver.self_test(&mut NoopDelay::new()).unwrap();
+        assert!(report.passed());
+        assert_eq!(report.device_count(), 2);
+        assert_eq!(report.failure(0), None);
+
+        let writes = recorder.writes();
+        // Two display test writes and 8 x 9 pattern writes per device
+        assert_eq!(writes.len(), 2 * (2 + 8 * 9));
+        assert_eq!(writes[0], (0, Register::DisplayTest, 0x01));
+        assert_eq!(writes[1], (0, Register::DisplayTest, 0x00));
+        let digit0: Vec<u8> = writes[2..11].iter().map(|&(_, _, data)| data).collect();
+        assert_eq!(
+            digit0,
+            [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x00]
+        );
+        assert!(
+            writes[..74]
+                .iter()
+                .all(|&(device_index, _, _)| device_index == 0)
+        );
+        assert!(
+            writes[74..]
+                .iter()
+                .all(|&(device_index, _, _)| device_index == 1)
+        );
+    }
+
+    #[test]
+    fn test_self_test_attributes_spi_error() {
+        // Device 1 starts at write 74; 76..=84 are its digit 0, 85.. its digit 1
+        let mut spi = FailingSpi::failing_on(86);
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+
+        let report = driver.self_test(&mut NoopDelay::new()).unwrap();
+        assert!(!report.passed());
+        assert_eq!(report.failure(0), None);
+        assert_eq!(
+            report.failure(1),
+            Some(&SelfTestFailure {
+                step: SelfTestStep::Pattern { digit: 1 },
+                error: Error::SpiError(embedded_hal::spi::ErrorKind::Other),
+            })
+        );
+        // Device 2 is still tested in full
+        assert_eq!(report.failure(2), None);
+        assert_eq!(spi.writes.len(), 87 + 74);
+        assert_eq!(spi.writes[87][4..], [Register::DisplayTest.addr(), 0x01]);
+    }
+}
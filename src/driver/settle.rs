@@ -0,0 +1,184 @@
+//! Optional settle time after every chain transaction
+//!
+//! On long or poorly decoupled chains, rows written back-to-back at a high SPI
+//! clock can show up as ghost rows on other modules. Waiting a few microseconds
+//! after chip select is released, before the next transaction starts, gives the
+//! chain time to latch. [`Max7219::attach_delay`] wraps the SPI device in a
+//! [`SettleDelay`] that does this for every write the driver makes, including
+//! the ones of the display types built on it.
+
+use embedded_hal::{
+    delay::DelayNs,
+    spi::{ErrorType, Operation, SpiDevice},
+};
+
+use crate::Max7219;
+
+/// An [`SpiDevice`] that waits a configurable time after each transaction.
+///
+/// Created by [`Max7219::attach_delay`]; see
+/// [`Max7219::set_inter_write_delay_us`] for the wait.
+pub struct SettleDelay<SPI, D> {
+    spi: SPI,
+    delay: D,
+    delay_us: u16,
+}
+
+impl<SPI, D> ErrorType for SettleDelay<SPI, D>
+where
+    SPI: SpiDevice,
+{
+    type Error = SPI::Error;
+}
+
+impl<SPI, D> SpiDevice for SettleDelay<SPI, D>
+where
+    SPI: SpiDevice,
+    D: DelayNs,
+{
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), Self::Error> {
+        self.spi.transaction(operations)?;
+        // Chip select has been released, the chain latches during this wait
+        if self.delay_us > 0 {
+            self.delay.delay_us(u32::from(self.delay_us));
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Pairs the driver with a delay provider for an inter-write settle time.
+    ///
+    /// The returned driver keeps the configuration and cached state of this one.
+    /// Its settle time starts at zero, which sends exactly what the plain driver
+    /// would, with no waits; set it with [`Max7219::set_inter_write_delay_us`].
+    /// Attach the delay before handing the driver to a display type, so that
+    /// e.g. `LedMatrix::flush` waits between its row writes as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut driver = Max7219::new(spi).with_device_count(8)?.attach_delay(delay);
+    /// driver.set_inter_write_delay_us(5);
+    /// let mut matrix = Matrix8::from_driver_with_init(driver, true)?;
+    /// ```
+    pub fn attach_delay<D: DelayNs>(self, delay: D) -> Max7219<SettleDelay<SPI, D>, N> {
+        let (driver, ()) = self.map_spi(|spi| {
+            let device = SettleDelay {
+                spi,
+                delay,
+                delay_us: 0,
+            };
+            (device, ())
+        });
+        driver
+    }
+}
+
+impl<SPI, D, const N: usize> Max7219<SettleDelay<SPI, D>, N>
+where
+    SPI: SpiDevice,
+    D: DelayNs,
+{
+    /// Sets how long to wait after each transaction, once chip select is released.
+    ///
+    /// Zero, the default, disables the wait. A few microseconds are usually enough
+    /// to get rid of ghost rows; every row of a matrix flush pays this time once.
+    pub fn set_inter_write_delay_us(&mut self, delay_us: u16) {
+        self.spi_mut().delay_us = delay_us;
+    }
+
+    /// Returns the wait after each transaction, in microseconds.
+    pub fn inter_write_delay_us(&self) -> u16 {
+        self.spi().delay_us
+    }
+
+    /// Removes the delay provider, returning the plain driver and the delay.
+    pub fn detach_delay(self) -> (Max7219<SPI, N>, D) {
+        self.map_spi(|settle| (settle.spi, settle.delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "led-matrix")]
+    use crate::led_matrix::display::Matrix4;
+    use crate::registers::Register;
+    use crate::test_support::{Event, LoggingDelay, LoggingSpi};
+    use core::cell::RefCell;
+
+    #[test]
+    fn test_settle_delay_between_writes() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::new(LoggingSpi(&log)).attach_delay(LoggingDelay(&log));
+        assert_eq!(driver.inter_write_delay_us(), 0);
+        driver.set_inter_write_delay_us(5);
+
+        driver.write_raw_digit(0, 0, 0x81).unwrap();
+        driver.write_raw_digit(0, 1, 0x42).unwrap();
+
+        assert_eq!(
+            log.take(),
+            [
+                Event::Write(vec![Register::Digit0.addr(), 0x81]),
+                Event::Wait(5_000),
+                Event::Write(vec![Register::Digit1.addr(), 0x42]),
+                Event::Wait(5_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_settle_delay_by_default() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::new(LoggingSpi(&log))
+            .with_device_count(2)
+            .unwrap()
+            .attach_delay(LoggingDelay(&log));
+        driver.clear_all().unwrap();
+
+        let events = log.take();
+        assert_eq!(events.len(), 8);
+        assert!(events.iter().all(|event| matches!(event, Event::Write(_))));
+
+        let (mut driver, _delay) = driver.detach_delay();
+        assert_eq!(driver.device_count(), 2);
+        driver.power_on().unwrap();
+        assert_eq!(log.take().len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "led-matrix")]
+    fn test_settle_delay_after_every_flushed_row() {
+        let log = RefCell::new(Vec::new());
+        let mut driver = Max7219::new(LoggingSpi(&log))
+            .with_device_count(4)
+            .unwrap()
+            .attach_delay(LoggingDelay(&log));
+        driver.set_inter_write_delay_us(3);
+        driver.power_on().unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        log.take();
+
+        matrix.write_column(0, 0xFF).unwrap();
+        matrix.flush().unwrap();
+
+        let events = log.take();
+        assert_eq!(events.len(), 16);
+        for (pair, digit) in events.chunks(2).zip(Register::digits()) {
+            let [Event::Write(frame), Event::Wait(ns)] = pair else {
+                panic!("{digit:?} is not a frame followed by a wait: {pair:?}");
+            };
+            assert_eq!(frame.len(), 8);
+            assert!(frame.chunks(2).all(|packet| packet[0] == digit.addr()));
+            assert_eq!(*ns, 3_000);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Visual diagnostics for chains where some modules misbehave
+//!
+//! When one module of a chain loses power or a data line, every module after it
+//! shows garbage, which is easy to mistake for a software bug.
+//! [`Max7219::stress_pattern`] shows a pattern that tells each module's position
+//! apart, so the first faulty module can be spotted by eye.
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{Max7219, Result, driver::frame, registers::Register};
+
+/// How long each step of [`Max7219::stress_pattern`] is shown.
+pub const STRESS_STEP_MS: u32 = 400;
+
+/// Number of frames in the blinking phase of [`Max7219::stress_pattern`].
+pub const STRESS_BLINKS: usize = 6;
+
+/// Rows of the stress pattern for the module `position` places from the MCU,
+/// starting at 1.
+///
+/// The top row is lit, rows 3 and 4 hold `position` in binary with the least
+/// significant bit in the rightmost column, and the bottom row is a checkerboard
+/// that flips with `phase`.
+fn stress_rows(position: usize, phase: bool) -> [u8; 8] {
+    let checker = if phase { 0x55 } else { 0xAA };
+    let binary = position as u8;
+    [0xFF, 0x00, 0x00, binary, binary, 0x00, 0x00, checker]
+}
+
+impl<SPI, const N: usize> Max7219<SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Runs a visual pattern that shows which module of the chain is the first to
+    /// misbehave.
+    ///
+    /// The display is cleared, then the pattern walks down the chain: starting with
+    /// the module next to the MCU, one module at a time is drawn while the others
+    /// stay as they are, each step shown for [`STRESS_STEP_MS`]. Every module then
+    /// blinks for [`STRESS_BLINKS`] frames, all in one transaction per row, and the
+    /// display is cleared again. The idle hook, if any, runs before every wait.
+    ///
+    /// Run it after [`Self::init`], as the pattern relies on no-decode mode and a
+    /// full scan limit.
+    ///
+    /// # Reading the pattern
+    ///
+    /// A healthy module shows:
+    ///
+    /// ```txt
+    /// ########   top row: the module latched its first row
+    /// ........
+    /// ........
+    /// .....#.#   rows 3 and 4: position from the MCU in binary,
+    /// .....#.#   here 5 (rightmost column = 1)
+    /// ........
+    /// ........
+    /// #.#.#.#.   bottom row: flips every frame while blinking
+    /// ```
+    ///
+    /// - While walking, the modules should light up 1, 2, 3, ... moving away from
+    ///   the MCU. The first module that stays dark, shows the wrong number, or
+    ///   changes while another module is being drawn is the first faulty one.
+    ///   Check its power, and the data and load lines between it and the module
+    ///   before it.
+    /// - While blinking, every bottom row should flip in step. A module that
+    ///   freezes or shows noise loses data at high bus activity; the modules after
+    ///   it usually follow.
+    /// - If a module shows the right pattern but upside down or mirrored, the
+    ///   chain is fine and only its orientation needs configuring.
+    /// - If the walk ends with an extra step where nothing lights up, the device
+    ///   count is too high. If modules at the far end never show a number, it is
+    ///   too low.
+    ///
+    /// # Errors
+    ///
+    /// Returns an SPI error if a transfer fails, leaving the pattern half drawn.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// driver.init()?;
+    /// driver.stress_pattern(&mut delay)?;
+    /// ```
+    pub fn stress_pattern<D: DelayNs>(&mut self, delay: &mut D) -> Result<()> {
+        let device_count = self.device_count();
+        self.clear_all()?;
+
+        for position in 1..=device_count {
+            let device_index = device_count - position;
+            let rows = stress_rows(position, false);
+            for (digit_register, data) in Register::digits().zip(rows) {
+                self.write_device_register(device_index, digit_register, data)?;
+            }
+            self.idle();
+            delay.delay_ms(STRESS_STEP_MS);
+        }
+
+        for blink in 0..STRESS_BLINKS {
+            let phase = blink % 2 == 0;
+            for (row, digit_register) in Register::digits().enumerate() {
+                let mut ops = [(Register::NoOp, 0x00); N];
+                for (device_index, op) in ops.iter_mut().enumerate().take(device_count) {
+                    *op = (
+                        digit_register,
+                        stress_rows(device_count - device_index, phase)
+                            .get(row)
+                            .copied()
+                            .unwrap_or(0x00),
+                    );
+                }
+                self.write_all_registers(frame::active(&ops, device_count))?;
+            }
+            self.idle();
+            delay.delay_ms(STRESS_STEP_MS);
+        }
+
+        self.clear_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAX_DISPLAYS;
+    use crate::test_util::{FrameRecorder, RegisterWrite};
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+
+    #[test]
+    fn test_stress_rows_encode_position() {
+        for position in 1..=MAX_DISPLAYS {
+            let rows = stress_rows(position, false);
+            assert_eq!(rows[0], 0xFF);
+            assert_eq!(usize::from(rows[3]), position);
+            assert_eq!(rows[3], rows[4]);
+            assert_eq!(rows[7], 0xAA);
+            assert_eq!(stress_rows(position, true)[7], 0x55);
+        }
+        // Distinct patterns, so neighbours cannot be confused
+        assert_ne!(stress_rows(2, false), stress_rows(3, false));
+    }
+
+    #[test]
+    fn test_stress_pattern_walks_from_mcu() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+        driver.stress_pattern(&mut NoopDelay::new()).unwrap();
+
+        let frames = recorder.frames();
+        // Clear, walk, blink and clear again
+        assert_eq!(frames.len(), 8 + 3 * 8 + STRESS_BLINKS * 8 + 8);
+
+        let walk = &frames[8..32];
+        for (step, device_frames) in walk.chunks(8).enumerate() {
+            let position = step + 1;
+            let expected: Vec<Vec<RegisterWrite>> = Register::digits()
+                .zip(stress_rows(position, false))
+                .map(|(digit_register, data)| vec![(3 - position, digit_register, data)])
+                .collect();
+            assert_eq!(device_frames, expected);
+        }
+
+        let first_blink = &frames[32..40];
+        assert_eq!(
+            first_blink[3],
+            [
+                (0, Register::Digit3, 3),
+                (1, Register::Digit3, 2),
+                (2, Register::Digit3, 1),
+            ]
+        );
+        assert!(first_blink[7].iter().all(|&(_, _, data)| data == 0x55));
+        assert!(frames[47].iter().all(|&(_, _, data)| data == 0xAA));
+        assert!(
+            frames[frames.len() - 8..]
+                .iter()
+                .flatten()
+                .all(|&(_, _, data)| data == 0x00)
+        );
+    }
+}
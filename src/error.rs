@@ -1,7 +1,16 @@
 //! Error types for MAX7219 driver
+//!
+//! [`Error`] is what every fallible operation returns. When you only care about
+//! *which* error happened, compare against the fieldless [`ErrorKind`] instead:
+//!
+//! ```rust,ignore
+//! let err = display.write_char(8, 'A', &STANDARD_FONT).unwrap_err();
+//! assert_eq!(err, ErrorKind::InvalidDigit);
+//! ```
 
 /// Errors that can occur when using the MAX7219 driver
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
     /// The specified device count is invalid (exceeds maximum allowed).
     InvalidDeviceCount,
@@ -10,45 +19,198 @@ pub enum Error {
     /// The specified register address is not valid for the MAX7219.
     InvalidRegister,
     /// Invalid device index (exceeds configured number of devices)
-    InvalidDeviceIndex,
+    InvalidDeviceIndex {
+        /// The device index that was requested.
+        index: usize,
+        /// The number of devices configured on the driver.
+        count: usize,
+    },
     /// Invalid digit position (0-7 for MAX7219)
-    InvalidDigit,
+    InvalidDigit {
+        /// The digit position that was requested.
+        digit: u8,
+    },
     /// Invalid intensity value (must be 0-15)
     InvalidIntensity,
     /// Unsupported Character
     UnsupportedChar,
-    /// Buffer Error
-    BufferError,
-    /// SPI communication error
+    /// A pixel, row or column coordinate lies outside the buffer it was used on.
+    ///
+    /// For row-only operations `x` is `0`, for column-only operations `y` is `0`.
+    OutOfBounds {
+        /// The horizontal coordinate that was requested.
+        x: u16,
+        /// The vertical coordinate that was requested.
+        y: u16,
+    },
+    /// More writes were queued in a batch than it can hold.
+    BatchFull,
+    /// The display was written to while one or more devices were powered off.
+    PoweredOff,
+    /// The framebuffer length of an LED matrix type is not 64 pixels per device.
+    BufferSizeMismatch,
+    /// The orientation wizard got answers that fit no orientation.
+    CalibrationFailed,
+    /// A raw frame has an odd length or is longer than the chain.
+    InvalidFrameLength,
+    /// An animation asset is truncated or does not follow the animation format.
+    InvalidAnimation,
+    /// Time of day out of range (hours above 23, minutes or seconds above 59).
+    InvalidTime,
+    /// A value is outside the range the display can show.
+    ValueOutOfRange,
+    /// SPI communication error, carrying the [`ErrorKind`](embedded_hal::spi::ErrorKind)
+    /// reported by the SPI implementation.
+    SpiError(embedded_hal::spi::ErrorKind),
+}
+
+/// The category of an [`Error`], without any data it carries.
+///
+/// Useful for matching or asserting on an error without caring about its
+/// details, e.g. `assert_eq!(err, ErrorKind::InvalidDigit)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorKind {
+    /// See [`Error::InvalidDeviceCount`].
+    InvalidDeviceCount,
+    /// See [`Error::InvalidScanLimit`].
+    InvalidScanLimit,
+    /// See [`Error::InvalidRegister`].
+    InvalidRegister,
+    /// See [`Error::InvalidDeviceIndex`].
+    InvalidDeviceIndex,
+    /// See [`Error::InvalidDigit`].
+    InvalidDigit,
+    /// See [`Error::InvalidIntensity`].
+    InvalidIntensity,
+    /// See [`Error::UnsupportedChar`].
+    UnsupportedChar,
+    /// See [`Error::OutOfBounds`].
+    OutOfBounds,
+    /// See [`Error::BatchFull`].
+    BatchFull,
+    /// See [`Error::PoweredOff`].
+    PoweredOff,
+    /// See [`Error::BufferSizeMismatch`].
+    BufferSizeMismatch,
+    /// See [`Error::CalibrationFailed`].
+    CalibrationFailed,
+    /// See [`Error::InvalidFrameLength`].
+    InvalidFrameLength,
+    /// See [`Error::InvalidAnimation`].
+    InvalidAnimation,
+    /// See [`Error::InvalidTime`].
+    InvalidTime,
+    /// See [`Error::ValueOutOfRange`].
+    ValueOutOfRange,
+    /// See [`Error::SpiError`].
     SpiError,
 }
 
+impl Error {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidDeviceCount => ErrorKind::InvalidDeviceCount,
+            Self::InvalidScanLimit => ErrorKind::InvalidScanLimit,
+            Self::InvalidRegister => ErrorKind::InvalidRegister,
+            Self::InvalidDeviceIndex { .. } => ErrorKind::InvalidDeviceIndex,
+            Self::InvalidDigit { .. } => ErrorKind::InvalidDigit,
+            Self::InvalidIntensity => ErrorKind::InvalidIntensity,
+            Self::UnsupportedChar => ErrorKind::UnsupportedChar,
+            Self::OutOfBounds { .. } => ErrorKind::OutOfBounds,
+            Self::BatchFull => ErrorKind::BatchFull,
+            Self::PoweredOff => ErrorKind::PoweredOff,
+            Self::BufferSizeMismatch => ErrorKind::BufferSizeMismatch,
+            Self::CalibrationFailed => ErrorKind::CalibrationFailed,
+            Self::InvalidFrameLength => ErrorKind::InvalidFrameLength,
+            Self::InvalidAnimation => ErrorKind::InvalidAnimation,
+            Self::InvalidTime => ErrorKind::InvalidTime,
+            Self::ValueOutOfRange => ErrorKind::ValueOutOfRange,
+            Self::SpiError(_) => ErrorKind::SpiError,
+        }
+    }
+}
+
+// Older releases documented the device errors as `InvalidDisplayCount` and
+// `InvalidDisplayIndex`. Keep those names compiling for one release.
+#[allow(non_upper_case_globals)]
+impl Error {
+    /// Deprecated name of [`Error::InvalidDeviceCount`].
+    #[deprecated(since = "0.1.7", note = "use `Error::InvalidDeviceCount`")]
+    pub const InvalidDisplayCount: Error = Error::InvalidDeviceCount;
+}
+
+#[allow(non_upper_case_globals)]
+impl ErrorKind {
+    /// Deprecated name of [`ErrorKind::InvalidDeviceCount`].
+    #[deprecated(since = "0.1.7", note = "use `ErrorKind::InvalidDeviceCount`")]
+    pub const InvalidDisplayCount: ErrorKind = ErrorKind::InvalidDeviceCount;
+
+    /// Deprecated name of [`ErrorKind::InvalidDeviceIndex`].
+    #[deprecated(since = "0.1.7", note = "use `ErrorKind::InvalidDeviceIndex`")]
+    pub const InvalidDisplayIndex: ErrorKind = ErrorKind::InvalidDeviceIndex;
+}
+
+impl PartialEq<ErrorKind> for Error {
+    fn eq(&self, other: &ErrorKind) -> bool {
+        self.kind() == *other
+    }
+}
+
+impl PartialEq<Error> for ErrorKind {
+    fn eq(&self, other: &Error) -> bool {
+        *self == other.kind()
+    }
+}
+
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::SpiError => write!(f, "SPI communication error"),
-            Self::InvalidDeviceIndex => write!(f, "Invalid device index"),
-            Self::InvalidDigit => write!(f, "Invalid digit"),
+            Self::SpiError(kind) => write!(f, "SPI communication error: {kind}"),
+            Self::ValueOutOfRange => write!(f, "Value out of displayable range"),
+            Self::InvalidTime => write!(f, "Invalid time of day"),
+            Self::InvalidAnimation => write!(f, "Invalid animation asset"),
+            Self::InvalidFrameLength => write!(f, "Raw frame length is odd or exceeds the chain"),
+            Self::CalibrationFailed => write!(f, "Orientation calibration failed"),
+            Self::BufferSizeMismatch => {
+                write!(f, "Framebuffer length does not match the device count")
+            }
+            Self::PoweredOff => write!(f, "Display is powered off"),
+            Self::BatchFull => write!(f, "Batch capacity exceeded"),
+            Self::InvalidDeviceIndex { index, count } => {
+                write!(f, "Invalid device index {index} (device count is {count})")
+            }
+            Self::InvalidDigit { digit } => write!(f, "Invalid digit {digit}"),
             Self::InvalidIntensity => write!(f, "Invalid intensity value"),
             Self::InvalidScanLimit => write!(f, "Invalid scan limit value"),
             Self::InvalidDeviceCount => write!(f, "Invalid device count"),
             Self::InvalidRegister => write!(f, "Invalid register address"),
             Self::UnsupportedChar => write!(f, "Unsupported Character"),
-            Self::BufferError => write!(f, "LED Matrix buffer error"),
+            Self::OutOfBounds { x, y } => write!(f, "Coordinate ({x}, {y}) is out of bounds"),
         }
     }
 }
 
+/// Lets the driver errors flow into `core::error::Error` based chains
+/// (`anyhow`, `thiserror`, boxed errors in std test harnesses).
+///
+/// `source()` is always `None`: the SPI implementation's own error type is not kept,
+/// only its [`ErrorKind`](embedded_hal::spi::ErrorKind), which is already part of the
+/// `Display` output of [`Error::SpiError`].
+impl core::error::Error for Error {}
+
 /// Convert any embedded-hal SPI error into a general `SpiError`.
 ///
 /// This allows using the `?` operator with SPI operations, automatically
-/// mapping their error into the driver's unified [`Error`] type.
+/// mapping their error into the driver's unified [`Error`] type. The error's
+/// [`kind`](embedded_hal::spi::Error::kind) is preserved.
 impl<E> From<E> for Error
 where
     E: embedded_hal::spi::Error,
 {
-    fn from(_value: E) -> Self {
-        Self::SpiError
+    fn from(value: E) -> Self {
+        Self::SpiError(value.kind())
     }
 }
 
@@ -87,10 +249,13 @@ mod tests {
             "Invalid register address"
         );
         assert_eq!(
-            format!("{}", Error::InvalidDeviceIndex),
-            "Invalid device index"
+            format!("{}", Error::InvalidDeviceIndex { index: 4, count: 2 }),
+            "Invalid device index 4 (device count is 2)"
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidDigit { digit: 9 }),
+            "Invalid digit 9"
         );
-        assert_eq!(format!("{}", Error::InvalidDigit), "Invalid digit");
         assert_eq!(
             format!("{}", Error::InvalidIntensity),
             "Invalid intensity value"
@@ -99,23 +264,69 @@ mod tests {
             format!("{}", Error::UnsupportedChar),
             "Unsupported Character"
         );
-        assert_eq!(format!("{}", Error::BufferError), "LED Matrix buffer error");
-        assert_eq!(format!("{}", Error::SpiError), "SPI communication error");
+        assert_eq!(
+            format!("{}", Error::OutOfBounds { x: 8, y: 3 }),
+            "Coordinate (8, 3) is out of bounds"
+        );
+        assert_eq!(format!("{}", Error::BatchFull), "Batch capacity exceeded");
+        assert_eq!(format!("{}", Error::PoweredOff), "Display is powered off");
+        assert_eq!(
+            format!("{}", Error::BufferSizeMismatch),
+            "Framebuffer length does not match the device count"
+        );
+        assert_eq!(
+            format!("{}", Error::CalibrationFailed),
+            "Orientation calibration failed"
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidFrameLength),
+            "Raw frame length is odd or exceeds the chain"
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidAnimation),
+            "Invalid animation asset"
+        );
+        assert_eq!(format!("{}", Error::InvalidTime), "Invalid time of day");
+        assert_eq!(
+            format!("{}", Error::ValueOutOfRange),
+            "Value out of displayable range"
+        );
+        assert_eq!(
+            format!("{}", Error::SpiError(embedded_hal::spi::ErrorKind::Overrun)),
+            "SPI communication error: The peripheral receive buffer was overrun"
+        );
     }
 
     #[test]
     fn test_error_debug() {
         // Test that Debug trait is implemented and works
-        let error = Error::InvalidDigit;
+        let error = Error::InvalidDigit { digit: 8 };
         let debug_output = format!("{error:?}",);
         assert!(debug_output.contains("InvalidDigit"));
+        assert!(debug_output.contains('8'));
     }
 
     #[test]
     fn test_from_spi_error() {
         let spi_error = MockSpiError;
         let error = Error::from(spi_error);
-        assert_eq!(error, Error::SpiError);
+        assert_eq!(error, Error::SpiError(embedded_hal::spi::ErrorKind::Other));
+    }
+
+    #[test]
+    fn test_core_error_impl() {
+        use core::error::Error as _;
+
+        let error = Error::from(MockSpiError);
+        assert!(error.source().is_none());
+        assert!(Error::InvalidDigit { digit: 8 }.source().is_none());
+
+        // Boxes into a std error chain
+        let boxed: Box<dyn core::error::Error> = Box::new(error);
+        assert_eq!(
+            boxed.to_string(),
+            "SPI communication error: A different error occurred. The original error may contain more information"
+        );
     }
 
     #[test]
@@ -124,4 +335,51 @@ mod tests {
         assert!(Error::InvalidDeviceCount.eq(&Error::InvalidDeviceCount));
         assert!(!Error::InvalidDeviceCount.eq(&Error::InvalidScanLimit));
     }
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(
+            Error::InvalidDigit { digit: 8 }.kind(),
+            ErrorKind::InvalidDigit
+        );
+        assert_eq!(
+            Error::InvalidDeviceIndex { index: 3, count: 1 }.kind(),
+            ErrorKind::InvalidDeviceIndex
+        );
+        assert_eq!(
+            Error::OutOfBounds { x: 1, y: 9 }.kind(),
+            ErrorKind::OutOfBounds
+        );
+        assert_eq!(Error::from(MockSpiError).kind(), ErrorKind::SpiError);
+
+        // Compare errors directly against a kind, in both directions
+        assert_eq!(Error::InvalidIntensity, ErrorKind::InvalidIntensity);
+        assert_eq!(ErrorKind::SpiError, Error::from(MockSpiError));
+        assert_ne!(
+            Error::InvalidDigit { digit: 8 },
+            ErrorKind::InvalidDeviceIndex
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_display_names() {
+        assert_eq!(Error::InvalidDisplayCount, Error::InvalidDeviceCount);
+        assert_eq!(
+            ErrorKind::InvalidDisplayCount,
+            ErrorKind::InvalidDeviceCount
+        );
+        assert_eq!(
+            Error::InvalidDeviceIndex { index: 2, count: 1 },
+            ErrorKind::InvalidDisplayIndex
+        );
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format_impls() {
+        fn assert_format<T: defmt::Format>() {}
+        assert_format::<Error>();
+        assert_format::<ErrorKind>();
+    }
 }
@@ -0,0 +1,398 @@
+//! Multi-frame animations stored in a compact binary asset
+//!
+//! Animations are authored offline and embedded with `include_bytes!`. The
+//! format is little endian throughout:
+//!
+//! | Offset | Size | Content                                       |
+//! |--------|------|-----------------------------------------------|
+//! | 0      | 4    | Magic `MXA1`                                  |
+//! | 4      | 2    | Frame count, at least 1                       |
+//! | 6      | 1    | Device span: devices per frame, `1..=8`       |
+//! | 7      | ...  | Frames, back to back                          |
+//!
+//! Each frame is its display time in milliseconds (2 bytes) followed by
+//! `span * 8` row bytes: the eight rows of device 0 from top to bottom, then
+//! those of device 1, and so on. Bit 7 of a row is its leftmost pixel, as in
+//! [`MatrixBuffer`].
+//!
+//! A few lines of Python produce such a file:
+//!
+//! ```text
+//! import struct
+//!
+//! def write_animation(path, span, frames):
+//!     """frames: list of (delay_ms, rows), rows being span * 8 ints"""
+//!     with open(path, "wb") as f:
+//!         f.write(b"MXA1" + struct.pack("<HB", len(frames), span))
+//!         for delay_ms, rows in frames:
+//!             f.write(struct.pack("<H", delay_ms) + bytes(rows))
+//! ```
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    Error, MAX_DISPLAYS, Result,
+    led_matrix::{LedMatrix, buffer::MatrixBuffer, timer::FrameTimer},
+};
+
+const MAGIC: [u8; 4] = *b"MXA1";
+const HEADER_LEN: usize = 7;
+
+/// A validated animation asset, see the [module docs](self) for the format.
+///
+/// Use the [`animation!`](crate::animation) macro to check an embedded asset at
+/// compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct Animation {
+    data: &'static [u8],
+    frame_count: usize,
+    span: usize,
+}
+
+impl Animation {
+    /// Parses and validates an animation asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidAnimation` if the magic is wrong, there are no
+    /// frames, the span is not `1..=8`, or the length does not match the header.
+    pub const fn from_bytes(data: &'static [u8]) -> Result<Self> {
+        let &[m0, m1, m2, m3, count_low, count_high, span, ..] = data else {
+            return Err(Error::InvalidAnimation);
+        };
+        if !matches!([m0, m1, m2, m3], MAGIC) {
+            return Err(Error::InvalidAnimation);
+        }
+
+        let frame_count = u16::from_le_bytes([count_low, count_high]) as usize;
+        let span = span as usize;
+        if frame_count == 0 || span == 0 || span > MAX_DISPLAYS {
+            return Err(Error::InvalidAnimation);
+        }
+        if data.len() != HEADER_LEN + frame_count * (2 + span * 8) {
+            return Err(Error::InvalidAnimation);
+        }
+
+        Ok(Self {
+            data,
+            frame_count,
+            span,
+        })
+    }
+
+    /// Number of frames.
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Number of devices each frame covers.
+    pub fn device_span(&self) -> usize {
+        self.span
+    }
+
+    /// Returns frame `index`, or `None` past the last frame.
+    pub fn frame(&self, index: usize) -> Option<AnimationFrame> {
+        if index >= self.frame_count {
+            return None;
+        }
+        let frame_len = 2 + self.span * 8;
+        let start = HEADER_LEN + index * frame_len;
+        let [delay_low, delay_high, rows @ ..] = self.data.get(start..start + frame_len)? else {
+            return None;
+        };
+        Some(AnimationFrame {
+            delay_ms: u16::from_le_bytes([*delay_low, *delay_high]),
+            rows,
+        })
+    }
+
+    /// Iterates over the frames in order.
+    pub fn frames(&self) -> impl Iterator<Item = AnimationFrame> + '_ {
+        (0..self.frame_count).filter_map(|index| self.frame(index))
+    }
+}
+
+/// One frame of an [`Animation`].
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationFrame {
+    delay_ms: u16,
+    rows: &'static [u8],
+}
+
+impl AnimationFrame {
+    /// How long the frame stays on screen, in milliseconds.
+    pub fn delay_ms(&self) -> u16 {
+        self.delay_ms
+    }
+
+    /// Rows of `device_index`, or `None` outside the animation's span.
+    pub fn device_rows(&self, device_index: usize) -> Option<[u8; 8]> {
+        let rows = self.rows.get(device_index * 8..device_index * 8 + 8)?;
+        let mut out = [0; 8];
+        out.copy_from_slice(rows);
+        Some(out)
+    }
+}
+
+/// Validates an embedded animation asset at compile time.
+///
+/// Expands to a constant [`Animation`], so a malformed asset fails the build
+/// instead of returning an error at runtime.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use max7219_display::animation;
+/// use max7219_display::led_matrix::animation::Animation;
+///
+/// const SPINNER: Animation = animation!(include_bytes!("spinner.mxa"));
+/// ```
+#[macro_export]
+macro_rules! animation {
+    ($bytes:expr) => {{
+        const ANIMATION: $crate::led_matrix::animation::Animation =
+            match $crate::led_matrix::animation::Animation::from_bytes($bytes) {
+                Ok(animation) => animation,
+                Err(_) => panic!("invalid animation asset"),
+            };
+        ANIMATION
+    }};
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Plays `animation` `repeat` times, starting at device 0.
+    ///
+    /// Frames are written straight to the chip like [`Self::write_buffer`], so the
+    /// framebuffer is left untouched, and each one is held for its own delay.
+    /// Devices beyond the animation's span are not written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if the animation spans more devices
+    /// than the matrix has, or an SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// const BOUNCE: Animation = animation!(include_bytes!("bounce.mxa"));
+    /// matrix.play(&BOUNCE, &mut delay, 3)?;
+    /// ```
+    pub fn play<D: DelayNs>(
+        &mut self,
+        animation: &Animation,
+        delay: &mut D,
+        repeat: u32,
+    ) -> Result<()> {
+        if animation.device_span() > DEVICE_COUNT {
+            return Err(Error::InvalidDeviceCount);
+        }
+
+        for _ in 0..repeat {
+            for frame in animation.frames() {
+                self.show_animation_frame(
+                    animation.device_span(),
+                    |device_index| frame.device_rows(device_index),
+                    frame.delay_ms(),
+                    delay,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Like [`Self::play`], but paces the frames with `timer` so the time spent
+    /// writing each one counts toward its delay.
+    ///
+    /// The timer is started when playback starts. A frame that takes longer than
+    /// its delay drops the frames it ran into, counted in its own delay, so the
+    /// animation stays in step with the clock.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if the animation spans more devices
+    /// than the matrix has, or an SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut timer = FrameTimer::new(|| clock.now_us());
+    /// matrix.play_timed(&BOUNCE, &mut delay, &mut timer, 3)?;
+    /// ```
+    pub fn play_timed<D: DelayNs, C: FnMut() -> u64>(
+        &mut self,
+        animation: &Animation,
+        delay: &mut D,
+        timer: &mut FrameTimer<C>,
+        repeat: u32,
+    ) -> Result<()> {
+        if animation.device_span() > DEVICE_COUNT {
+            return Err(Error::InvalidDeviceCount);
+        }
+
+        let mut frames = (0..repeat).flat_map(|_| animation.frames());
+        timer.start();
+        while let Some(frame) = frames.next() {
+            self.write_animation_frame(animation.device_span(), |device_index| {
+                frame.device_rows(device_index)
+            })?;
+            self.driver().idle();
+            let skipped = timer.wait_for_next_frame(delay, u32::from(frame.delay_ms()) * 1000);
+            if skipped > 0 {
+                frames.nth(skipped as usize - 1);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the rows of the first `span` devices and waits for `delay_ms`.
+    pub(super) fn show_animation_frame<D: DelayNs>(
+        &mut self,
+        span: usize,
+        device_rows: impl Fn(usize) -> Option<[u8; 8]>,
+        delay_ms: u16,
+        delay: &mut D,
+    ) -> Result<()> {
+        self.write_animation_frame(span, device_rows)?;
+        self.driver().idle();
+        delay.delay_ms(delay_ms.into());
+        Ok(())
+    }
+
+    /// Writes the rows of the first `span` devices.
+    fn write_animation_frame(
+        &mut self,
+        span: usize,
+        device_rows: impl Fn(usize) -> Option<[u8; 8]>,
+    ) -> Result<()> {
+        for device_index in 0..span {
+            let rows = device_rows(device_index).unwrap_or_default();
+            self.write_buffer(device_index, &MatrixBuffer::from_data(rows))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::{Matrix4, SingleMatrix};
+    use crate::registers::Register;
+    use crate::test_support::RecordingDelay;
+    use crate::test_util::FrameRecorder;
+    use core::cell::Cell;
+
+    #[rustfmt::skip]
+    const BLOB: &[u8] = &[
+        b'M', b'X', b'A', b'1', 3, 0, 1,
+        100, 0, 0x80, 0, 0, 0, 0, 0, 0, 0,
+        44, 1, 0, 0x40, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0x20, 0, 0, 0, 0, 0,
+    ];
+
+    const ANIMATION: Animation = crate::animation!(BLOB);
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ANIMATION.frame_count(), 3);
+        assert_eq!(ANIMATION.device_span(), 1);
+
+        let delays: Vec<_> = ANIMATION.frames().map(|frame| frame.delay_ms()).collect();
+        assert_eq!(delays, [100, 300, 0]);
+
+        let second = ANIMATION.frame(1).unwrap();
+        assert_eq!(second.device_rows(0), Some([0, 0x40, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(second.device_rows(1), None);
+        assert!(ANIMATION.frame(3).is_none());
+    }
+
+    #[test]
+    fn test_invalid_assets() {
+        let cases: [&'static [u8]; 5] = [
+            b"MXA1",
+            b"MXA2\x01\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+            b"MXA1\x00\x00\x01",
+            b"MXA1\x01\x00\x09",
+            &BLOB[..BLOB.len() - 1],
+        ];
+        for data in cases {
+            assert!(matches!(
+                Animation::from_bytes(data),
+                Err(Error::InvalidAnimation)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_play() {
+        let mut recorder = FrameRecorder::new();
+        let mut matrix = SingleMatrix::from_driver(Max7219::new(&mut recorder)).unwrap();
+        let mut delay = RecordingDelay::default();
+
+        matrix.play(&ANIMATION, &mut delay, 2).unwrap();
+
+        let mut expected = Vec::new();
+        for frame in ANIMATION.frames().chain(ANIMATION.frames()) {
+            let rows = frame.device_rows(0).unwrap();
+            for (digit_register, data) in Register::digits().zip(rows) {
+                expected.push(vec![(0, digit_register, data)]);
+            }
+        }
+        assert_eq!(recorder.frames(), expected);
+        assert_eq!(delay.waits_ms, [100, 300, 0, 100, 300, 0]);
+    }
+
+    #[test]
+    fn test_play_timed_drops_overrun_frames() {
+        let mut recorder = FrameRecorder::new();
+        let mut matrix = SingleMatrix::from_driver(Max7219::new(&mut recorder)).unwrap();
+        let mut delay = RecordingDelay::default();
+
+        // Every frame takes 150 ms to write, longer than the first one's 100 ms
+        let now_us = Cell::new(0);
+        let mut timer = FrameTimer::new(|| {
+            now_us.set(now_us.get() + 150_000);
+            now_us.get()
+        });
+        matrix
+            .play_timed(&ANIMATION, &mut delay, &mut timer, 1)
+            .unwrap();
+
+        // Frame 0 overruns into frame 1's slot, which is dropped
+        let shown: Vec<u8> = recorder
+            .writes()
+            .iter()
+            .map(|&(_, _, data)| data)
+            .filter(|&data| data != 0)
+            .collect();
+        assert_eq!(shown, [0x80, 0x20]);
+        assert_eq!(recorder.frames().len(), 16);
+        assert_eq!(delay.waits_ms, [50, 0]);
+    }
+
+    #[test]
+    fn test_play_span_too_wide() {
+        #[rustfmt::skip]
+        const WIDE: &[u8] = &[
+            b'M', b'X', b'A', b'1', 1, 0, 5,
+            0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        let wide = Animation::from_bytes(WIDE).unwrap();
+        let result = matrix.play(&wide, &mut RecordingDelay::default(), 1);
+        assert_eq!(result, Err(Error::InvalidDeviceCount));
+        assert!(recorder.frames().is_empty());
+    }
+}
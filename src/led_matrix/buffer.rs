@@ -4,6 +4,7 @@ use crate::{Error, Result};
 
 /// 8x8 matrix buffer for LED matrix displays
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct MatrixBuffer {
     data: [u8; 8],
 }
@@ -31,15 +32,20 @@ impl MatrixBuffer {
 
     /// Set a pixel in the buffer
     pub fn set_pixel(&mut self, x: u8, y: u8, state: bool) -> Result<()> {
-        if x >= 8 || y >= 8 {
-            return Err(Error::BufferError);
-        }
+        let row = self
+            .data
+            .get_mut(y as usize)
+            .filter(|_| x < 8)
+            .ok_or(Error::OutOfBounds {
+                x: x.into(),
+                y: y.into(),
+            })?;
 
         let bit_mask = 1 << x;
         if state {
-            self.data[y as usize] |= bit_mask;
+            *row |= bit_mask;
         } else {
-            self.data[y as usize] &= !bit_mask;
+            *row &= !bit_mask;
         }
 
         Ok(())
@@ -47,12 +53,17 @@ impl MatrixBuffer {
 
     /// Get pixel state from buffer
     pub fn get_pixel(&self, x: u8, y: u8) -> Result<bool> {
-        if x >= 8 || y >= 8 {
-            return Err(Error::BufferError);
-        }
+        let row = self
+            .data
+            .get(y as usize)
+            .filter(|_| x < 8)
+            .ok_or(Error::OutOfBounds {
+                x: x.into(),
+                y: y.into(),
+            })?;
 
         let bit_mask = 1 << x;
-        Ok((self.data[y as usize] & bit_mask) != 0)
+        Ok((row & bit_mask) != 0)
     }
 
     /// Clear the entire buffer
@@ -67,21 +78,24 @@ impl MatrixBuffer {
 
     /// Set a row in the buffer
     pub fn set_row(&mut self, row: u8, data: u8) -> Result<()> {
-        if row >= 8 {
-            return Err(Error::BufferError);
-        }
+        let slot = self.data.get_mut(row as usize).ok_or(Error::OutOfBounds {
+            x: 0,
+            y: row.into(),
+        })?;
 
-        self.data[row as usize] = data;
+        *slot = data;
         Ok(())
     }
 
     /// Get a row from the buffer
     pub fn get_row(&self, row: u8) -> Result<u8> {
-        if row >= 8 {
-            return Err(Error::BufferError);
-        }
-
-        Ok(self.data[row as usize])
+        self.data
+            .get(row as usize)
+            .copied()
+            .ok_or(Error::OutOfBounds {
+                x: 0,
+                y: row.into(),
+            })
     }
 }
 
@@ -149,10 +163,22 @@ mod tests {
         let mut buffer = MatrixBuffer::new();
 
         // Test invalid coordinates
-        assert_eq!(buffer.set_pixel(8, 0, true), Err(Error::BufferError));
-        assert_eq!(buffer.set_pixel(0, 8, true), Err(Error::BufferError));
-        assert_eq!(buffer.set_pixel(255, 0, true), Err(Error::BufferError));
-        assert_eq!(buffer.set_pixel(0, 255, true), Err(Error::BufferError));
+        assert_eq!(
+            buffer.set_pixel(8, 0, true),
+            Err(Error::OutOfBounds { x: 8, y: 0 })
+        );
+        assert_eq!(
+            buffer.set_pixel(0, 8, true),
+            Err(Error::OutOfBounds { x: 0, y: 8 })
+        );
+        assert_eq!(
+            buffer.set_pixel(255, 0, true),
+            Err(Error::OutOfBounds { x: 255, y: 0 })
+        );
+        assert_eq!(
+            buffer.set_pixel(0, 255, true),
+            Err(Error::OutOfBounds { x: 0, y: 255 })
+        );
     }
 
     #[test]
@@ -160,10 +186,22 @@ mod tests {
         let buffer = MatrixBuffer::new();
 
         // Test invalid coordinates
-        assert_eq!(buffer.get_pixel(8, 0), Err(Error::BufferError));
-        assert_eq!(buffer.get_pixel(0, 8), Err(Error::BufferError));
-        assert_eq!(buffer.get_pixel(255, 0), Err(Error::BufferError));
-        assert_eq!(buffer.get_pixel(0, 255), Err(Error::BufferError));
+        assert_eq!(
+            buffer.get_pixel(8, 0),
+            Err(Error::OutOfBounds { x: 8, y: 0 })
+        );
+        assert_eq!(
+            buffer.get_pixel(0, 8),
+            Err(Error::OutOfBounds { x: 0, y: 8 })
+        );
+        assert_eq!(
+            buffer.get_pixel(255, 0),
+            Err(Error::OutOfBounds { x: 255, y: 0 })
+        );
+        assert_eq!(
+            buffer.get_pixel(0, 255),
+            Err(Error::OutOfBounds { x: 0, y: 255 })
+        );
     }
 
     #[test]
@@ -207,8 +245,14 @@ mod tests {
         let mut buffer = MatrixBuffer::new();
 
         // Test invalid row indices
-        assert_eq!(buffer.set_row(8, 0xFF), Err(Error::BufferError));
-        assert_eq!(buffer.set_row(255, 0xFF), Err(Error::BufferError));
+        assert_eq!(
+            buffer.set_row(8, 0xFF),
+            Err(Error::OutOfBounds { x: 0, y: 8 })
+        );
+        assert_eq!(
+            buffer.set_row(255, 0xFF),
+            Err(Error::OutOfBounds { x: 0, y: 255 })
+        );
     }
 
     #[test]
@@ -216,8 +260,11 @@ mod tests {
         let buffer = MatrixBuffer::new();
 
         // Test invalid row indices
-        assert_eq!(buffer.get_row(8), Err(Error::BufferError));
-        assert_eq!(buffer.get_row(255), Err(Error::BufferError));
+        assert_eq!(buffer.get_row(8), Err(Error::OutOfBounds { x: 0, y: 8 }));
+        assert_eq!(
+            buffer.get_row(255),
+            Err(Error::OutOfBounds { x: 0, y: 255 })
+        );
     }
 
     #[test]
@@ -233,4 +280,12 @@ mod tests {
         assert_eq!(buffer.data()[0], 0b10101010);
         assert_eq!(buffer.data()[1], 0b01010101);
     }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format_impls() {
+        fn assert_format<T: defmt::Format>() {}
+        assert_format::<MatrixBuffer>();
+        assert_format::<crate::led_matrix::symbols::Symbol>();
+    }
 }
@@ -0,0 +1,237 @@
+//! One-expression setup of an [`LedMatrix`]
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{
+    Result,
+    driver::InitConfig,
+    led_matrix::{
+        LedMatrix, PowerPolicy,
+        fonts::CharMap,
+        transform::{Orientation, Rotation},
+    },
+};
+
+/// Settings applied by [`LedMatrix::from_spi_with_config`].
+///
+/// The default leaves the intensity at the chip's power-up level and uses the
+/// defaults of every other setting, so it behaves exactly like
+/// [`LedMatrix::from_spi`].
+///
+/// With the `serde` feature the config can be persisted, except for
+/// [`char_map`](Self::char_map): it borrows `'static` tables, so it is skipped
+/// and a loaded config gets the default map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct MatrixConfig {
+    /// Intensity written to every device after initialization (`0x00..=0x0F`).
+    pub intensity: Option<u8>,
+    /// Rotation, mirroring and device order.
+    pub orientation: Orientation,
+    /// What [`LedMatrix::flush`] does while the display is powered off.
+    pub power_policy: PowerPolicy,
+    /// Character substitutions and custom glyphs for the text helpers.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub char_map: CharMap,
+    /// Keep what the devices show instead of clearing them, see
+    /// [`Max7219::init_preserve_display`](crate::Max7219::init_preserve_display).
+    ///
+    /// The framebuffer still starts blank, so the first [`LedMatrix::flush`]
+    /// replaces the preserved content. Draw into the framebuffer before flushing,
+    /// or only use the methods that write straight to the chip until the
+    /// application is ready to take over the whole display.
+    pub preserve_display: bool,
+    /// Steps of the initialization sequence, e.g. to leave the chain powered off
+    /// or set the intensity before anything lights up. `preserve_display` skips
+    /// the clear as well.
+    ///
+    /// Keep the default decode mode and scan limit, the matrix needs all 8 rows
+    /// without decoding.
+    pub init: InitConfig,
+}
+
+/// Fluent builder returned by [`LedMatrix::builder`].
+///
+/// The device count comes from the matrix type, e.g. [`Matrix4`], as the
+/// framebuffer size has to be known at compile time.
+///
+/// [`Matrix4`]: crate::led_matrix::display::Matrix4
+#[must_use = "call build() to create the matrix"]
+pub struct LedMatrixBuilder<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> {
+    spi: SPI,
+    config: MatrixConfig,
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrixBuilder<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Sets the intensity of every device (`0x00..=0x0F`), checked by [`Self::build`].
+    pub fn intensity(mut self, intensity: u8) -> Self {
+        self.config.intensity = Some(intensity);
+        self
+    }
+
+    /// Sets the rotation, keeping the rest of the orientation.
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.config.orientation.rotation = rotation;
+        self
+    }
+
+    /// Sets rotation, mirroring and device order at once.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.config.orientation = orientation;
+        self
+    }
+
+    /// Sets what [`LedMatrix::flush`] does while the display is powered off.
+    pub fn power_policy(mut self, policy: PowerPolicy) -> Self {
+        self.config.power_policy = policy;
+        self
+    }
+
+    /// Sets the character substitutions and custom glyphs of the text helpers.
+    pub fn char_map(mut self, char_map: CharMap) -> Self {
+        self.config.char_map = char_map;
+        self
+    }
+
+    /// Keeps what the devices show during initialization, see
+    /// [`MatrixConfig::preserve_display`].
+    pub fn preserve_display(mut self, preserve: bool) -> Self {
+        self.config.preserve_display = preserve;
+        self
+    }
+
+    /// Sets the steps of the initialization sequence, see [`MatrixConfig::init`].
+    pub fn init_config(mut self, init: InitConfig) -> Self {
+        self.config.init = init;
+        self
+    }
+
+    /// Initializes the chain and returns the configured matrix.
+    ///
+    /// # Errors
+    ///
+    /// See [`LedMatrix::from_spi_with_config`].
+    pub fn build(self) -> Result<LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>> {
+        LedMatrix::from_spi_with_config(self.spi, self.config)
+    }
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Starts building a matrix on `spi`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut matrix = Matrix4::builder(spi)
+    ///     .intensity(3)
+    ///     .rotation(Rotation::Cw180)
+    ///     .build()?;
+    /// ```
+    pub fn builder(spi: SPI) -> LedMatrixBuilder<SPI, BUFFER_LENGTH, DEVICE_COUNT> {
+        LedMatrixBuilder {
+            spi,
+            config: MatrixConfig::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, led_matrix::display::Matrix4, test_util::FrameRecorder};
+
+    #[test]
+    fn test_builder_matches_manual_setup() {
+        let mut built = FrameRecorder::new();
+        let matrix = Matrix4::builder(&mut built)
+            .intensity(3)
+            .rotation(Rotation::Cw180)
+            .power_policy(PowerPolicy::Skip)
+            .build()
+            .unwrap();
+        assert_eq!(matrix.rotation(), Rotation::Cw180);
+        assert_eq!(matrix.power_policy(), PowerPolicy::Skip);
+
+        let mut manual = FrameRecorder::new();
+        let mut matrix = Matrix4::from_spi(&mut manual).unwrap();
+        matrix.driver().set_intensity_all(3).unwrap();
+        matrix.set_rotation(Rotation::Cw180);
+
+        assert_eq!(built.frames(), manual.frames());
+        assert_eq!(
+            built.frames().last().unwrap(),
+            &[0, 1, 2, 3].map(|device| (device, crate::Register::Intensity, 3))
+        );
+    }
+
+    #[test]
+    fn test_builder_defaults_match_from_spi() {
+        let mut built = FrameRecorder::new();
+        Matrix4::builder(&mut built).build().unwrap();
+        let mut manual = FrameRecorder::new();
+        Matrix4::from_spi(&mut manual).unwrap();
+        assert_eq!(built.frames(), manual.frames());
+    }
+
+    #[test]
+    fn test_builder_preserve_display() {
+        let mut recorder = FrameRecorder::new();
+        Matrix4::builder(&mut recorder)
+            .preserve_display(true)
+            .build()
+            .unwrap();
+        assert!(
+            recorder
+                .writes()
+                .iter()
+                .all(|&(_, register, _)| !register.is_digit())
+        );
+    }
+
+    #[test]
+    fn test_builder_invalid_intensity() {
+        let mut recorder = FrameRecorder::new();
+        let result = Matrix4::builder(&mut recorder).intensity(0x10).build();
+        assert!(matches!(result, Err(Error::InvalidIntensity)));
+        assert!(recorder.frames().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_matrix_config_postcard_round_trip() {
+        let config = MatrixConfig {
+            intensity: Some(0x05),
+            orientation: Orientation {
+                rotation: Rotation::Cw90,
+                ..Orientation::default()
+            },
+            power_policy: PowerPolicy::Error,
+            char_map: CharMap::new(&[('a', 'A')]),
+            preserve_display: true,
+            init: InitConfig::new().skip_clear(true),
+        };
+
+        let bytes = postcard::to_allocvec(&config).expect("serialize");
+        let decoded: MatrixConfig = postcard::from_bytes(&bytes).expect("deserialize");
+        assert_eq!(
+            decoded,
+            MatrixConfig {
+                char_map: CharMap::default(),
+                ..config
+            }
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Tiny analog clock face for a single 8x8 module
+//!
+//! The dial is drawn as a ring, and both hands are Bresenham lines from the middle of
+//! the module. An 8x8 grid has no single center pixel, so each hand starts from
+//! whichever of the four middle pixels lies on its side; this keeps opposite hands
+//! mirror images of each other. Both hands snap to the twelve hour positions.
+
+use crate::{Result, error::Error, led_matrix::buffer::MatrixBuffer};
+
+/// Dial outline, one byte per row with bit 7 as the leftmost column.
+const DIAL: [u8; 8] = [
+    0b00111100, 0b01000010, 0b10000001, 0b10000001, 0b10000001, 0b10000001, 0b01000010, 0b00111100,
+];
+
+/// `(start, end)` of the minute hand for each of the 12 positions, 12 o'clock first.
+const MINUTE_HAND: [((i8, i8), (i8, i8)); 12] = [
+    ((3, 3), (3, 1)),
+    ((4, 3), (5, 1)),
+    ((4, 3), (6, 2)),
+    ((4, 3), (6, 3)),
+    ((4, 4), (6, 5)),
+    ((4, 4), (5, 6)),
+    ((4, 4), (4, 6)),
+    ((3, 4), (2, 6)),
+    ((3, 4), (1, 5)),
+    ((3, 4), (1, 4)),
+    ((3, 3), (1, 2)),
+    ((3, 3), (2, 1)),
+];
+
+/// `(start, end)` of the shorter hour hand for each of the 12 positions.
+const HOUR_HAND: [((i8, i8), (i8, i8)); 12] = [
+    ((3, 3), (3, 2)),
+    ((4, 3), (5, 2)),
+    ((4, 3), (5, 2)),
+    ((4, 3), (5, 3)),
+    ((4, 4), (5, 5)),
+    ((4, 4), (5, 5)),
+    ((4, 4), (4, 5)),
+    ((3, 4), (2, 5)),
+    ((3, 4), (2, 5)),
+    ((3, 4), (2, 4)),
+    ((3, 3), (2, 2)),
+    ((3, 3), (2, 2)),
+];
+
+/// Renders a clock face showing `hours:minutes`.
+///
+/// Hours wrap at 12 (so 24-hour values work) and minutes are rounded to the nearest
+/// five. The hour hand points at the current hour and does not creep between hours.
+/// The result uses the digit-register layout (bit 7 = leftmost column), ready for
+/// [`LedMatrix::write_buffer`](crate::led_matrix::LedMatrix::write_buffer).
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTime` if `hours` is above 23 or `minutes` above 59.
+pub fn clock_face(hours: u8, minutes: u8) -> Result<MatrixBuffer> {
+    if hours > 23 || minutes > 59 {
+        return Err(Error::InvalidTime);
+    }
+    let mut rows = DIAL;
+
+    let minute_pos = (minutes as usize + 2) / 5 % 12;
+    if let Some(&(start, end)) = MINUTE_HAND.get(minute_pos) {
+        draw_line(&mut rows, start, end);
+    }
+
+    if let Some(&(start, end)) = HOUR_HAND.get((hours % 12) as usize) {
+        draw_line(&mut rows, start, end);
+    }
+
+    Ok(MatrixBuffer::from_data(rows))
+}
+
+/// Bresenham line between two in-bounds points, both ends included.
+fn draw_line(rows: &mut [u8; 8], (x0, y0): (i8, i8), (x1, y1): (i8, i8)) {
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let (mut x, mut y) = (x0, y0);
+    let mut err = dx + dy;
+
+    loop {
+        if let Some(row) = rows.get_mut(y as usize) {
+            *row |= 0x80 >> x;
+        }
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pixels lit inside the dial ring, as (x, y) pairs.
+    fn hand_pixels(face: &MatrixBuffer) -> Vec<(u8, u8)> {
+        let mut pixels = Vec::new();
+        for (y, (&row, &dial)) in face.data().iter().zip(&DIAL).enumerate() {
+            for x in 0..8 {
+                let mask = 0x80 >> x;
+                if row & mask != 0 && dial & mask == 0 {
+                    pixels.push((x, y as u8));
+                }
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn test_three_oclock() {
+        // Minute hand straight up, hour hand to the right
+        assert_eq!(
+            hand_pixels(&clock_face(3, 0).unwrap()),
+            [(3, 1), (3, 2), (3, 3), (4, 3), (5, 3)]
+        );
+    }
+
+    #[test]
+    fn test_half_past_six() {
+        // Both hands point down and overlap
+        assert_eq!(
+            hand_pixels(&clock_face(6, 30).unwrap()),
+            [(4, 4), (4, 5), (4, 6)]
+        );
+        assert_eq!(
+            clock_face(18, 30).unwrap().data(),
+            clock_face(6, 30).unwrap().data()
+        );
+    }
+
+    #[test]
+    fn test_twelve_oclock() {
+        assert_eq!(
+            hand_pixels(&clock_face(12, 0).unwrap()),
+            [(3, 1), (3, 2), (3, 3)]
+        );
+        assert_eq!(
+            clock_face(0, 0).unwrap().data(),
+            clock_face(12, 0).unwrap().data()
+        );
+    }
+
+    #[test]
+    fn test_dial_is_always_drawn() {
+        for hours in 0..12 {
+            let face = clock_face(hours, hours * 5).unwrap();
+            for (&row, &dial) in face.data().iter().zip(&DIAL) {
+                assert_eq!(row & dial, dial);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_time() {
+        assert!(matches!(clock_face(24, 0), Err(Error::InvalidTime)));
+        assert!(matches!(clock_face(0, 60), Err(Error::InvalidTime)));
+        assert!(matches!(clock_face(0, 75), Err(Error::InvalidTime)));
+        assert!(clock_face(23, 59).is_ok());
+    }
+
+    #[test]
+    fn test_diagonal_line() {
+        let mut rows = [0u8; 8];
+        draw_line(&mut rows, (3, 3), (1, 2));
+        assert_eq!(rows, [0, 0, 0b0110_0000, 0b0001_0000, 0, 0, 0, 0]);
+    }
+}
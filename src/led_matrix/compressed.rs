@@ -0,0 +1,457 @@
+//! Animations compressed as run-length encoded frame deltas
+//!
+//! Most frames of an animation differ from the previous one in a few rows, so
+//! storing each frame as its XOR with the previous one and run-length encoding
+//! the result usually takes a fraction of the [`Animation`] format. The format
+//! is little endian throughout:
+//!
+//! | Offset | Size | Content                                       |
+//! |--------|------|-----------------------------------------------|
+//! | 0      | 4    | Magic `MXC1`                                  |
+//! | 4      | 2    | Frame count, at least 1                       |
+//! | 6      | 1    | Device span: devices per frame, `1..=8`       |
+//! | 7      | ...  | Frames, back to back                          |
+//!
+//! Each frame is its display time in milliseconds (2 bytes) followed by tokens
+//! covering exactly `span * 8` row bytes, in the row order of [`Animation`]:
+//!
+//! - `0x00..=0x7F`: the next `token + 1` bytes are unchanged.
+//! - `0x80..=0xFF`: the next `(token & 0x7F) + 1` bytes are XORed with the
+//!   bytes following the token.
+//!
+//! The first frame is XORed with a blank frame. [`encode_frames`] produces such
+//! an asset, e.g. from a build script with the `std` feature. Decoding only keeps
+//! the current frame, at most 64 bytes, never the whole animation.
+//!
+//! [`Animation`]: crate::led_matrix::animation::Animation
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{Error, MAX_DISPLAYS, Result, led_matrix::LedMatrix};
+
+const MAGIC: [u8; 4] = *b"MXC1";
+const HEADER_LEN: usize = 7;
+/// Set in tokens followed by changed bytes.
+const LITERAL: u8 = 0x80;
+/// Longest run a single token covers.
+#[cfg(any(test, feature = "std"))]
+const MAX_RUN: usize = 0x80;
+
+/// A validated compressed animation, see the [module docs](self) for the format.
+///
+/// Use the [`compressed_animation!`](crate::compressed_animation) macro to check
+/// an embedded asset at compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct CompressedAnimation {
+    data: &'static [u8],
+    frame_count: usize,
+    span: usize,
+}
+
+impl CompressedAnimation {
+    /// Parses and validates a compressed animation asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidAnimation` if the magic is wrong, there are no
+    /// frames, the span is not `1..=8`, a frame's tokens do not cover exactly its
+    /// rows, or the length does not match the header.
+    pub const fn from_bytes(data: &'static [u8]) -> Result<Self> {
+        let &[m0, m1, m2, m3, count_low, count_high, span, ref frames @ ..] = data else {
+            return Err(Error::InvalidAnimation);
+        };
+        if !matches!([m0, m1, m2, m3], MAGIC) {
+            return Err(Error::InvalidAnimation);
+        }
+
+        let frame_count = u16::from_le_bytes([count_low, count_high]) as usize;
+        let span = span as usize;
+        if frame_count == 0 || span == 0 || span > MAX_DISPLAYS {
+            return Err(Error::InvalidAnimation);
+        }
+
+        let mut rest = frames;
+        let mut frame = 0;
+        while frame < frame_count {
+            let [_delay_low, _delay_high, ref tokens @ ..] = *rest else {
+                return Err(Error::InvalidAnimation);
+            };
+            rest = tokens;
+            let mut covered = 0;
+            while covered < span * 8 {
+                let [token, ref tail @ ..] = *rest else {
+                    return Err(Error::InvalidAnimation);
+                };
+                let run = (token & !LITERAL) as usize + 1;
+                rest = tail;
+                if token & LITERAL != 0 {
+                    let Some((_changes, tail)) = rest.split_at_checked(run) else {
+                        return Err(Error::InvalidAnimation);
+                    };
+                    rest = tail;
+                }
+                covered += run;
+            }
+            if covered != span * 8 {
+                return Err(Error::InvalidAnimation);
+            }
+            frame += 1;
+        }
+        if !rest.is_empty() {
+            return Err(Error::InvalidAnimation);
+        }
+
+        Ok(Self {
+            data,
+            frame_count,
+            span,
+        })
+    }
+
+    /// Number of frames.
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Number of devices each frame covers.
+    pub fn device_span(&self) -> usize {
+        self.span
+    }
+
+    /// Decodes the frames in order.
+    pub fn frames(&self) -> CompressedFrames {
+        CompressedFrames {
+            animation: *self,
+            rest: self.data.get(HEADER_LEN..).unwrap_or(&[]),
+            remaining: self.frame_count,
+            rows: [0; MAX_DISPLAYS * 8],
+        }
+    }
+}
+
+/// Iterator over the frames of a [`CompressedAnimation`], see
+/// [`CompressedAnimation::frames`].
+#[derive(Debug, Clone)]
+pub struct CompressedFrames {
+    animation: CompressedAnimation,
+    /// The asset from the start of the next frame.
+    rest: &'static [u8],
+    remaining: usize,
+    /// Rows of the last frame returned.
+    rows: [u8; MAX_DISPLAYS * 8],
+}
+
+impl Iterator for CompressedFrames {
+    type Item = CompressedFrame;
+
+    fn next(&mut self) -> Option<CompressedFrame> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // The asset was validated, so it never ends early
+        let &[delay_low, delay_high, ref tokens @ ..] = self.rest else {
+            return None;
+        };
+        let delay_ms = u16::from_le_bytes([delay_low, delay_high]);
+        self.rest = tokens;
+        let mut row = 0;
+        while row < self.animation.span * 8 {
+            let &[token, ref tail @ ..] = self.rest else {
+                return None;
+            };
+            let run = usize::from(token & !LITERAL) + 1;
+            self.rest = tail;
+            if token & LITERAL != 0 {
+                let (changes, tail) = self.rest.split_at_checked(run)?;
+                for (byte, change) in self.rows.iter_mut().skip(row).zip(changes) {
+                    *byte ^= change;
+                }
+                self.rest = tail;
+            }
+            row += run;
+        }
+
+        Some(CompressedFrame {
+            delay_ms,
+            span: self.animation.span,
+            rows: self.rows,
+        })
+    }
+}
+
+/// One decoded frame of a [`CompressedAnimation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedFrame {
+    delay_ms: u16,
+    span: usize,
+    rows: [u8; MAX_DISPLAYS * 8],
+}
+
+impl CompressedFrame {
+    /// How long the frame stays on screen, in milliseconds.
+    pub fn delay_ms(&self) -> u16 {
+        self.delay_ms
+    }
+
+    /// Rows of `device_index`, or `None` outside the animation's span.
+    pub fn device_rows(&self, device_index: usize) -> Option<[u8; 8]> {
+        if device_index >= self.span {
+            return None;
+        }
+        let rows = self.rows.get(device_index * 8..device_index * 8 + 8)?;
+        rows.try_into().ok()
+    }
+}
+
+/// Compresses `frames` into the format of [`CompressedAnimation`].
+///
+/// Each frame is its delay in milliseconds and `span * 8` row bytes, laid out
+/// as in an [`Animation`](crate::led_matrix::animation::Animation) asset. Only
+/// available with the `std` feature, e.g. in a build script.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidAnimation` if the span is not `1..=8`, there are no
+/// frames or more than 65535, or a frame does not have `span * 8` rows.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // build.rs
+/// let asset = encode_frames(4, &frames)?;
+/// std::fs::write(out_dir.join("intro.mxc"), asset)?;
+/// ```
+#[cfg(any(test, feature = "std"))]
+pub fn encode_frames(span: usize, frames: &[(u16, &[u8])]) -> Result<std::vec::Vec<u8>> {
+    let frame_count = u16::try_from(frames.len()).map_err(|_| Error::InvalidAnimation)?;
+    if frame_count == 0 || span == 0 || span > MAX_DISPLAYS {
+        return Err(Error::InvalidAnimation);
+    }
+
+    let mut out = std::vec::Vec::from(MAGIC);
+    out.extend(frame_count.to_le_bytes());
+    out.push(span as u8);
+
+    let mut previous = [0u8; MAX_DISPLAYS * 8];
+    for &(delay_ms, rows) in frames {
+        if rows.len() != span * 8 {
+            return Err(Error::InvalidAnimation);
+        }
+        out.extend(delay_ms.to_le_bytes());
+
+        let mut delta = [0u8; MAX_DISPLAYS * 8];
+        for ((change, &row), &before) in delta.iter_mut().zip(rows).zip(&previous) {
+            *change = row ^ before;
+        }
+        let mut delta = delta.get(..rows.len()).unwrap_or(&[]);
+
+        while let Some(&first) = delta.first() {
+            let changed = first != 0;
+            let run = delta
+                .iter()
+                .take(MAX_RUN)
+                .take_while(|&&change| (change != 0) == changed)
+                .count();
+            let (changes, rest) = delta.split_at(run);
+            if changed {
+                out.push(LITERAL | (run - 1) as u8);
+                out.extend(changes);
+            } else {
+                out.push((run - 1) as u8);
+            }
+            delta = rest;
+        }
+        for (before, &row) in previous.iter_mut().zip(rows) {
+            *before = row;
+        }
+    }
+    Ok(out)
+}
+
+/// Validates an embedded compressed animation at compile time.
+///
+/// Expands to a constant [`CompressedAnimation`], so a malformed asset fails
+/// the build instead of returning an error at runtime.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use max7219_display::compressed_animation;
+/// use max7219_display::led_matrix::compressed::CompressedAnimation;
+///
+/// const INTRO: CompressedAnimation = compressed_animation!(include_bytes!("intro.mxc"));
+/// ```
+#[macro_export]
+macro_rules! compressed_animation {
+    ($bytes:expr) => {{
+        const ANIMATION: $crate::led_matrix::compressed::CompressedAnimation =
+            match $crate::led_matrix::compressed::CompressedAnimation::from_bytes($bytes) {
+                Ok(animation) => animation,
+                Err(_) => panic!("invalid compressed animation asset"),
+            };
+        ANIMATION
+    }};
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Plays a compressed animation `repeat` times, like [`Self::play`].
+    ///
+    /// Frames are decoded one at a time while playing and written straight to
+    /// the chip, so the framebuffer is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if the animation spans more devices
+    /// than the matrix has, or an SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// const INTRO: CompressedAnimation = compressed_animation!(include_bytes!("intro.mxc"));
+    /// matrix.play_compressed(&INTRO, &mut delay, 1)?;
+    /// ```
+    pub fn play_compressed<D: DelayNs>(
+        &mut self,
+        animation: &CompressedAnimation,
+        delay: &mut D,
+        repeat: u32,
+    ) -> Result<()> {
+        if animation.device_span() > DEVICE_COUNT {
+            return Err(Error::InvalidDeviceCount);
+        }
+
+        for _ in 0..repeat {
+            for frame in animation.frames() {
+                self.show_animation_frame(
+                    animation.device_span(),
+                    |device_index| frame.device_rows(device_index),
+                    frame.delay_ms(),
+                    delay,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::animation::Animation;
+    use crate::led_matrix::display::Matrix4;
+    use crate::test_util::FrameRecorder;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+
+    /// Two devices, mostly blank, with a pixel moving across them.
+    fn sparse_frames() -> Vec<(u16, Vec<u8>)> {
+        (0..12u16)
+            .map(|step| {
+                let mut rows = vec![0u8; 16];
+                rows[usize::from(step) % 16] = 0x80 >> (step % 8);
+                rows[7] = 0xFF;
+                (50 + step, rows)
+            })
+            .collect()
+    }
+
+    fn encode(span: usize, frames: &[(u16, Vec<u8>)]) -> Result<Vec<u8>> {
+        let frames: Vec<(u16, &[u8])> = frames
+            .iter()
+            .map(|(delay_ms, rows)| (*delay_ms, &rows[..]))
+            .collect();
+        encode_frames(span, &frames)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let frames = sparse_frames();
+        let asset: &'static [u8] = encode(2, &frames).unwrap().leak();
+        let animation = CompressedAnimation::from_bytes(asset).unwrap();
+        assert_eq!(animation.frame_count(), 12);
+        assert_eq!(animation.device_span(), 2);
+
+        for (decoded, (delay_ms, rows)) in animation.frames().zip(&frames) {
+            assert_eq!(decoded.delay_ms(), *delay_ms);
+            assert_eq!(decoded.device_rows(0).unwrap(), rows[..8]);
+            assert_eq!(decoded.device_rows(1).unwrap(), rows[8..]);
+            assert_eq!(decoded.device_rows(2), None);
+        }
+        assert_eq!(animation.frames().count(), 12);
+
+        // 7 + 12 * (2 + 16) bytes uncompressed
+        assert!(asset.len() < 7 + 12 * 18 / 2, "{}", asset.len());
+    }
+
+    #[test]
+    fn test_round_trip_long_runs() {
+        // Eight devices: runs longer than one token, fully changed frames
+        let frames = [(1, vec![0u8; 64]), (2, vec![0xFF; 64]), (3, vec![0xFF; 64])];
+        let asset: &'static [u8] = encode(8, &frames).unwrap().leak();
+        let decoded: Vec<_> = CompressedAnimation::from_bytes(asset)
+            .unwrap()
+            .frames()
+            .map(|frame| frame.device_rows(7).unwrap())
+            .collect();
+        assert_eq!(decoded, [[0; 8], [0xFF; 8], [0xFF; 8]]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(encode(0, &sparse_frames()), Err(Error::InvalidAnimation));
+        assert_eq!(encode(1, &sparse_frames()), Err(Error::InvalidAnimation));
+        assert_eq!(encode(2, &[]), Err(Error::InvalidAnimation));
+
+        let asset = encode(2, &sparse_frames()).unwrap();
+        let cases: [&'static [u8]; 4] = [
+            b"MXC1\x01\x00",
+            b"MXA1\x01\x00\x01\x00\x00\x07",
+            // The token covers 9 rows of a one-device frame
+            b"MXC1\x01\x00\x01\x00\x00\x08",
+            asset[..asset.len() - 1].to_vec().leak(),
+        ];
+        for data in cases {
+            assert!(matches!(
+                CompressedAnimation::from_bytes(data),
+                Err(Error::InvalidAnimation)
+            ));
+        }
+        assert!(CompressedAnimation::from_bytes(b"MXC1\x01\x00\x01\x00\x00\x07").is_ok());
+    }
+
+    #[test]
+    fn test_player_matches_uncompressed() {
+        let frames = sparse_frames();
+        let mut blob = b"MXA1".to_vec();
+        blob.extend((frames.len() as u16).to_le_bytes());
+        blob.push(2);
+        for (delay_ms, rows) in &frames {
+            blob.extend(delay_ms.to_le_bytes());
+            blob.extend(rows);
+        }
+        let plain = Animation::from_bytes(blob.leak()).unwrap();
+        let compressed =
+            CompressedAnimation::from_bytes(encode(2, &frames).unwrap().leak()).unwrap();
+
+        let mut played = FrameRecorder::new();
+        let driver = Max7219::new(&mut played).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix.play(&plain, &mut NoopDelay::new(), 2).unwrap();
+
+        let mut streamed = FrameRecorder::new();
+        let driver = Max7219::new(&mut streamed).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix
+            .play_compressed(&compressed, &mut NoopDelay::new(), 2)
+            .unwrap();
+
+        assert!(!played.frames().is_empty());
+        assert_eq!(streamed.frames(), played.frames());
+    }
+}
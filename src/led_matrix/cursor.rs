@@ -0,0 +1,34 @@
+//! Cursor shapes for editing interfaces
+//!
+//! The cursor is not drawn into the framebuffer. [`LedMatrix::flush`] XORs it
+//! over one 8-pixel cell while packing the rows, so the content under it is
+//! kept and hiding the cursor, or blinking it, shows that content again.
+//!
+//! [`LedMatrix::flush`]: crate::LedMatrix::flush
+
+/// Shape of the cursor set with [`LedMatrix::set_cursor`](crate::LedMatrix::set_cursor).
+///
+/// Lit pixels of the content are switched off where the cursor covers them, so
+/// it stays visible over anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorStyle {
+    /// The bottom row of the cell.
+    Underline,
+    /// The whole cell, inverting it.
+    Block,
+    /// The outermost pixels of the cell.
+    Frame,
+}
+
+impl CursorStyle {
+    /// Rows XORed over the cell, bit 7 being the leftmost column.
+    pub(crate) const fn mask(self) -> [u8; 8] {
+        match self {
+            CursorStyle::Underline => [0, 0, 0, 0, 0, 0, 0, 0xFF],
+            CursorStyle::Block => [0xFF; 8],
+            CursorStyle::Frame => [0xFF, 0x81, 0x81, 0x81, 0x81, 0x81, 0x81, 0xFF],
+        }
+    }
+}
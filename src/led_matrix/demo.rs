@@ -0,0 +1,289 @@
+//! Scripted demo sequences
+//!
+//! A demo is a slice of [`DemoStep`]s run in order by [`LedMatrix::run_sequence`],
+//! each step using one of the existing helpers: a splash, a scrolling message, a
+//! clock drawn by a callback, an animation, a pause or a brightness change.
+//! [`DemoStep::Loop`] repeats the steps before it.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let draw_time = |m: &mut Matrix4<_>| m.draw_text(&time_text());
+//! let steps = [
+//!     DemoStep::Splash { image: &LOGO, hold_ms: 1500, fade: true },
+//!     DemoStep::ScrollText { text: "WELCOME", config: ScrollConfig::default(), repeat: 2 },
+//!     DemoStep::ShowTime { draw: &draw_time, hold_ms: 10_000 },
+//!     DemoStep::Animation { animation: &BOUNCE, repeat: 3 },
+//!     DemoStep::Loop(0),
+//! ];
+//! matrix.run_sequence(&mut delay, &steps, || !button.is_pressed())?;
+//! ```
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    Result,
+    led_matrix::{Animation, LedMatrix, scroll::ScrollConfig},
+};
+
+/// How often [`DemoStep::ShowTime`] redraws the time.
+pub const TIME_REFRESH_MS: u32 = 1000;
+
+/// Callback drawing the time, see [`DemoStep::ShowTime`].
+pub type DrawTime<'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> =
+    &'a dyn Fn(&mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>) -> Result<()>;
+
+/// One step of a demo run by [`LedMatrix::run_sequence`].
+pub enum DemoStep<'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> {
+    /// Shows a splash image, see [`LedMatrix::splash`].
+    Splash {
+        /// Rows of each device.
+        image: &'a [[u8; 8]; DEVICE_COUNT],
+        /// Time the image stays up.
+        hold_ms: u32,
+        /// Whether the image fades in and out.
+        fade: bool,
+    },
+    /// Scrolls `text` across the matrix `repeat` times with the default font,
+    /// see [`LedMatrix::scroll_text`].
+    ///
+    /// Each pass ends once the text has left the display; `config.loop_text` is
+    /// ignored.
+    ScrollText {
+        /// Text to scroll.
+        text: &'a str,
+        /// Speed and step of the scroll.
+        config: ScrollConfig,
+        /// Number of passes.
+        repeat: u32,
+    },
+    /// Calls `draw` every [`TIME_REFRESH_MS`] for `hold_ms`.
+    ///
+    /// The callback reads the time from wherever the application keeps it and
+    /// draws it with any [`LedMatrix`] API.
+    ShowTime {
+        /// Draws the current time.
+        draw: DrawTime<'a, SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        /// Time the clock stays up.
+        hold_ms: u32,
+    },
+    /// Plays an animation, see [`LedMatrix::play`].
+    Animation {
+        /// Animation to play.
+        animation: &'a Animation,
+        /// Number of times it is played.
+        repeat: u32,
+    },
+    /// Waits without changing the display.
+    Pause(u32),
+    /// Sets the intensity of every device, `0x00` to `0x0F`.
+    SetBrightness(u8),
+    /// Goes back to the step after the previous `Loop`, or to the first step,
+    /// until the steps in between have run this many times in total.
+    ///
+    /// `Loop(0)` repeats them until `keep_going` stops the sequence, and `Loop(1)`
+    /// does nothing.
+    Loop(u32),
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Runs `steps` in order for as long as `keep_going` returns `true`.
+    ///
+    /// `keep_going` is checked before every step and every redraw of
+    /// [`DemoStep::ShowTime`]; a step that has started, such as a scroll, runs to
+    /// its end. Returns once the last step is done or `keep_going` returns
+    /// `false`. The idle hook, if any, runs before every wait.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a step, leaving the rest of the sequence unrun.
+    pub fn run_sequence<D: DelayNs, F: FnMut() -> bool>(
+        &mut self,
+        delay: &mut D,
+        steps: &[DemoStep<'_, SPI, BUFFER_LENGTH, DEVICE_COUNT>],
+        mut keep_going: F,
+    ) -> Result<()> {
+        // First step of the section a `Loop` repeats, and the passes through it
+        let mut section_start = 0;
+        let mut passes = 0;
+        let mut index = 0;
+
+        while let Some(step) = steps.get(index) {
+            if !keep_going() {
+                return Ok(());
+            }
+            match step {
+                DemoStep::Splash {
+                    image,
+                    hold_ms,
+                    fade,
+                } => self.splash(delay, image, *hold_ms, *fade)?,
+                DemoStep::ScrollText {
+                    text,
+                    config,
+                    repeat,
+                } => {
+                    let config = ScrollConfig {
+                        loop_text: false,
+                        ..*config
+                    };
+                    for _ in 0..*repeat {
+                        self.scroll_text(delay, text, config)?;
+                    }
+                }
+                DemoStep::ShowTime { draw, hold_ms } => {
+                    if !self.show_time(delay, *draw, *hold_ms, &mut keep_going)? {
+                        return Ok(());
+                    }
+                }
+                DemoStep::Animation { animation, repeat } => {
+                    self.play(animation, delay, *repeat)?
+                }
+                DemoStep::Pause(ms) => {
+                    self.driver().idle();
+                    delay.delay_ms(*ms);
+                }
+                DemoStep::SetBrightness(level) => self.driver().set_intensity_all(*level)?,
+                DemoStep::Loop(count) => {
+                    passes += 1;
+                    if *count == 0 || passes < *count {
+                        index = section_start;
+                        continue;
+                    }
+                    passes = 0;
+                    section_start = index + 1;
+                }
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    /// Redraws the time for `hold_ms`, returning `false` if `keep_going` stopped it.
+    fn show_time<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        draw: DrawTime<'_, SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        hold_ms: u32,
+        keep_going: &mut impl FnMut() -> bool,
+    ) -> Result<bool> {
+        let mut remaining = hold_ms;
+        loop {
+            draw(self)?;
+            let wait = remaining.min(TIME_REFRESH_MS);
+            self.driver().idle();
+            delay.delay_ms(wait);
+            remaining -= wait;
+            if remaining == 0 {
+                return Ok(true);
+            }
+            if !keep_going() {
+                return Ok(false);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::Matrix4;
+    use crate::registers::Register;
+    use crate::test_support::RecordingDelay;
+    use crate::test_util::FrameRecorder;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_sequence_stops_when_asked() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        let mut delay = RecordingDelay::default();
+
+        let steps = [
+            DemoStep::Pause(100),
+            DemoStep::SetBrightness(3),
+            DemoStep::Loop(0),
+        ];
+        // Allows Pause, SetBrightness, Loop, Pause and SetBrightness
+        let mut checks = 0;
+        matrix
+            .run_sequence(&mut delay, &steps, || {
+                checks += 1;
+                checks <= 5
+            })
+            .unwrap();
+
+        assert_eq!(checks, 6);
+        assert_eq!(delay.waits_ms, [100, 100]);
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 2);
+        assert!(frames.iter().all(|frame| {
+            frame
+                .iter()
+                .all(|&(_, register, level)| register == Register::Intensity && level == 3)
+        }));
+    }
+
+    #[test]
+    fn test_sequence_loop_count() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        let mut delay = RecordingDelay::default();
+
+        let steps = [
+            DemoStep::Pause(10),
+            DemoStep::Loop(2),
+            DemoStep::Pause(20),
+            DemoStep::Pause(30),
+            DemoStep::Loop(3),
+            DemoStep::Pause(40),
+        ];
+        matrix.run_sequence(&mut delay, &steps, || true).unwrap();
+
+        assert_eq!(delay.waits_ms, [10, 10, 20, 30, 20, 30, 20, 30, 40]);
+    }
+
+    #[test]
+    fn test_sequence_show_time() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        let mut delay = RecordingDelay::default();
+
+        let draws = Cell::new(0);
+        let draw = |matrix: &mut Matrix4<_>| {
+            draws.set(draws.get() + 1);
+            matrix.draw_text("12")
+        };
+        let steps = [
+            DemoStep::ShowTime {
+                draw: &draw,
+                hold_ms: 2500,
+            },
+            DemoStep::Pause(5),
+        ];
+        matrix.run_sequence(&mut delay, &steps, || true).unwrap();
+        assert_eq!(draws.get(), 3);
+        assert_eq!(delay.waits_ms, [1000, 1000, 500, 5]);
+
+        // Stopped between two redraws
+        draws.set(0);
+        delay.waits_ms.clear();
+        let mut checks = 0;
+        matrix
+            .run_sequence(&mut delay, &steps, || {
+                checks += 1;
+                checks <= 2
+            })
+            .unwrap();
+        assert_eq!(draws.get(), 2);
+        assert_eq!(delay.waits_ms, [1000, 1000]);
+    }
+}
@@ -1,13 +1,26 @@
 //! LED matrix display implementation
 
+use core::ops::Range;
+
 use embedded_hal::{delay::DelayNs, spi::SpiDevice};
 
 use crate::{
-    Error, MAX_DISPLAYS, Max7219, Register, Result,
+    DecodeMode, Error, Max7219, NUM_DIGITS, Register, Result,
+    driver::{BrightnessCurve, SelfTestReport},
     led_matrix::{
         buffer::MatrixBuffer,
-        fonts::{self, LedFont},
+        builder::MatrixConfig,
+        clock,
+        cursor::CursorStyle,
+        effects::XorShift32,
+        fonts::{self, COMPACT_DIGITS, COMPACT_MINUS, CharMap, LedFont, text_width},
+        glyph::Glyph,
+        gray::GrayLevel,
+        layout::Layout,
         scroll::{ScrollConfig, ScrollingText},
+        style::TextStyle,
+        timer::FrameTimer,
+        transform::{Orientation, PixelLayout, Rotation},
     },
 };
 
@@ -20,7 +33,30 @@ pub type Matrix4<SPI> = LedMatrix<SPI, 256, 4>;
 /// Represents an 8-in-1 LED matrix module (total 8x64 pixels) using eight chained MAX7219 devices.
 pub type Matrix8<SPI> = LedMatrix<SPI, 512, 8>;
 
+/// What [`LedMatrix::flush`] does when the display is not powered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerPolicy {
+    /// Power the display on, then write the framebuffer.
+    #[default]
+    AutoPowerOn,
+    /// Return `Error::PoweredOff` without writing anything.
+    Error,
+    /// Return `Ok(())` without writing anything.
+    Skip,
+}
+
 /// A high-level abstraction for controlling an LED matrix display using the MAX7219 driver.
+///
+/// # SPI errors
+///
+/// Every register write is a single SPI transaction to the whole chain, so one
+/// row of every device is updated together or not at all. Operations that send
+/// several transactions, such as [`Self::flush`], [`Self::write_buffer`] or
+/// [`Self::draw_text`], are not atomic: if one of them fails part way, the
+/// devices show a mix of old and new rows. [`Self::needs_repair`] then returns
+/// `true` until [`Self::repair`] brings the chain back to a known state.
 pub struct LedMatrix<SPI, const BUFFER_LENGTH: usize = 64, const DEVICE_COUNT: usize = 1> {
     driver: Max7219<SPI>,
     /// The framebuffer with one `u8` per pixel (0 = off, non-zero = on).
@@ -32,7 +68,47 @@ pub struct LedMatrix<SPI, const BUFFER_LENGTH: usize = 64, const DEVICE_COUNT: u
     ///
     /// This buffer is modified by `embedded-graphics` through the
     /// [`DrawTarget`](https://docs.rs/embedded-graphics-core/latest/embedded_graphics_core/draw_target/trait.DrawTarget.html) trait.
-    framebuffer: [u8; BUFFER_LENGTH],
+    pub(super) framebuffer: [u8; BUFFER_LENGTH],
+    power_policy: PowerPolicy,
+    orientation: Orientation,
+    blanked: [bool; DEVICE_COUNT],
+    /// Wiring of each device, indexed like the driver.
+    pixel_layouts: [PixelLayout; DEVICE_COUNT],
+    /// Rows shown by [`Self::pin_value`], indexed like the driver.
+    pinned: [Option<[u8; 8]>; DEVICE_COUNT],
+    char_map: CharMap,
+    /// Font of the helpers that take none, see [`Self::set_default_font`].
+    default_font: &'static LedFont,
+    /// Style of the text helpers, see [`Self::set_text_style`].
+    text_style: TextStyle,
+    /// Cursor and the framebuffer block it covers, see [`Self::set_cursor`].
+    cursor: Option<(CursorStyle, usize)>,
+    /// Blink phase of the cursor, see [`Self::blink_cursor_tick`].
+    cursor_visible: bool,
+    /// Scan limit last programmed by [`Self::flush`] while the optimization is on.
+    auto_scan_limit: Option<u8>,
+    /// Set when a multi-transaction write failed part way, see [`Self::repair`].
+    needs_repair: bool,
+    /// Random source of the built-in effects, see [`Self::seed_effects`].
+    pub(super) effects_rng: XorShift32,
+    #[cfg(feature = "stats")]
+    stats: FlushStats,
+}
+
+/// SPI traffic caused by [`LedMatrix::flush`] and [`LedMatrix::flush_dithered`].
+///
+/// Only available with the `stats` feature. Counters saturate instead of
+/// wrapping.
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FlushStats {
+    /// SPI transactions sent, including automatic power-on and scan limit writes.
+    pub transactions: u32,
+    /// Bytes sent in those transactions.
+    pub bytes: u32,
+    /// Rows not sent at all because every device was blanked.
+    pub rows_skipped: u32,
 }
 
 impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
@@ -52,20 +128,73 @@ where
     ///
     /// # Returns
     ///
-    /// Returns a `LedMatrix` instance on success, or an error if the display count is invalid
+    /// Returns a `LedMatrix` instance on success, or an error if the device count is invalid
+    /// or `BUFFER_LENGTH` is not `DEVICE_COUNT * 64` (`Error::BufferSizeMismatch`).
     ///
     /// # Example
     ///
-    /// ```rust,ignore
-    /// let spi = /* your SPI device */;
-    /// let mut matrix = SingleMatrix::from_spi(spi, 4).unwrap();
+    /// ```rust
+    /// # #[cfg(feature = "test-util")]
+    /// # fn main() -> max7219_display::Result<()> {
+    /// # let spi = max7219_display::test_util::FakeSpi::default();
+    /// use max7219_display::led_matrix::display::Matrix4;
+    ///
+    /// // Four modules in a row, initialized and powered on
+    /// let mut matrix = Matrix4::from_spi(spi)?;
+    /// assert_eq!(matrix.device_count(), 4);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "test-util"))]
+    /// # fn main() {}
     /// ```
     pub fn from_spi(spi: SPI) -> Result<Self> {
+        Self::from_spi_with_config(spi, MatrixConfig::default())
+    }
+
+    /// Creates and initializes an `LedMatrix` like [`Self::from_spi`], then applies `config`.
+    ///
+    /// This is the constructor behind [`Self::builder`]. The intensity, if set, is
+    /// written to every device right after initialization; the other settings are
+    /// stored without any SPI traffic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` before anything is sent if the configured
+    /// intensity is above `0x0F`, otherwise the same errors as [`Self::from_spi`].
+    pub fn from_spi_with_config(spi: SPI, config: MatrixConfig) -> Result<Self> {
+        if config.intensity.is_some_and(|level| level > 0x0F) {
+            return Err(Error::InvalidIntensity);
+        }
+
         let mut driver = Max7219::new(spi).with_device_count(DEVICE_COUNT)?;
-        driver.init()?;
+        Self::check_buffer_length()?;
+        let init = if config.preserve_display {
+            config.init.skip_clear(true)
+        } else {
+            config.init
+        };
+        driver.init_with(&init)?;
+        if let Some(level) = config.intensity {
+            driver.set_intensity_all(level)?;
+        }
         Ok(Self {
             driver,
             framebuffer: [0; BUFFER_LENGTH],
+            power_policy: config.power_policy,
+            orientation: config.orientation,
+            blanked: [false; DEVICE_COUNT],
+            pixel_layouts: [PixelLayout::RowMajor; DEVICE_COUNT],
+            pinned: [None; DEVICE_COUNT],
+            char_map: config.char_map,
+            default_font: &fonts::STANDARD_LED_FONT,
+            text_style: TextStyle::Normal,
+            cursor: None,
+            cursor_visible: true,
+            auto_scan_limit: None,
+            needs_repair: false,
+            effects_rng: XorShift32::default(),
+            #[cfg(feature = "stats")]
+            stats: FlushStats::default(),
         })
     }
 
@@ -84,14 +213,16 @@ where
     /// # Error
     ///
     /// Returns `Err(Error::InvalidDeviceCount)` if the driver's device count
-    /// does not match the generic `DEVICE_COUNT` parameter of this matrix type.
+    /// does not match the generic `DEVICE_COUNT` parameter of this matrix type, or
+    /// `Err(Error::BufferSizeMismatch)` if `BUFFER_LENGTH` is not `DEVICE_COUNT * 64`.
     ///
     /// # Warning
     ///
     /// This method is more error-prone than [`Self::from_spi`] because it is easy to configure a driver
     /// with one device count (e.g., `.with_device_count(4)`) and then call `from_driver` on a `LedMatrix`
     /// type instantiated with a different generic parameter (e.g., `LedMatrix<_, 1>`).
-    /// This mismatch will result in an error.
+    /// This mismatch will result in an error. The same goes for a driver resized
+    /// with [`Max7219::set_device_count`]: resize it before handing it over.
     ///
     /// # Example
     ///
@@ -100,15 +231,66 @@ where
     /// let mut matrix = SingleMatrix::new(driver).unwrap();
     /// ```
     pub fn from_driver(driver: Max7219<SPI>) -> Result<Self> {
+        Self::from_driver_with_init(driver, false)
+    }
+
+    /// Creates a new `LedMatrix` from an existing driver, optionally running
+    /// [`Max7219::init`] on it first.
+    ///
+    /// Pass `init = true` when the driver has not been initialized yet, or when you are
+    /// not sure. Otherwise this behaves exactly like [`Self::from_driver`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(Error::InvalidDeviceCount)` if the driver's device count does not
+    /// match `DEVICE_COUNT`, or an SPI error if initialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let driver = Max7219::new(spi).with_device_count(4)?;
+    /// let mut matrix = Matrix4::from_driver_with_init(driver, true)?;
+    /// ```
+    pub fn from_driver_with_init(mut driver: Max7219<SPI>, init: bool) -> Result<Self> {
         if driver.device_count() != DEVICE_COUNT {
             return Err(Error::InvalidDeviceCount);
         }
+        Self::check_buffer_length()?;
+        if init {
+            driver.init()?;
+        }
         Ok(Self {
             driver,
             framebuffer: [0; BUFFER_LENGTH],
+            power_policy: PowerPolicy::default(),
+            orientation: Orientation::default(),
+            blanked: [false; DEVICE_COUNT],
+            pixel_layouts: [PixelLayout::RowMajor; DEVICE_COUNT],
+            pinned: [None; DEVICE_COUNT],
+            char_map: CharMap::default(),
+            default_font: &fonts::STANDARD_LED_FONT,
+            text_style: TextStyle::Normal,
+            cursor: None,
+            cursor_visible: true,
+            auto_scan_limit: None,
+            needs_repair: false,
+            effects_rng: XorShift32::default(),
+            #[cfg(feature = "stats")]
+            stats: FlushStats::default(),
         })
     }
 
+    /// Ensures `BUFFER_LENGTH` holds exactly 64 pixels per device.
+    ///
+    /// A shorter buffer would drop pixels past its end and a longer one would never
+    /// be flushed, so both are rejected up front.
+    fn check_buffer_length() -> Result<()> {
+        if BUFFER_LENGTH != DEVICE_COUNT * 64 {
+            return Err(Error::BufferSizeMismatch);
+        }
+        Ok(())
+    }
+
     /// Provides mutable access to the underlying MAX7219 driver.
     ///
     /// This allows users to call low-level functions directly
@@ -116,9 +298,473 @@ where
         &mut self.driver
     }
 
+    /// Consumes the display and returns the driver, e.g. to
+    /// [`release`](Max7219::release) the SPI device.
+    pub fn into_driver(self) -> Max7219<SPI> {
+        self.driver
+    }
+
+    /// Number of 8x8 devices in the chain.
+    pub fn device_count(&self) -> usize {
+        DEVICE_COUNT
+    }
+
+    /// Width of the whole chain in pixels, 8 per device.
+    ///
+    /// Available without the `graphics` feature, unlike `OriginDimensions::size`.
+    pub fn width_pixels(&self) -> usize {
+        DEVICE_COUNT * 8
+    }
+
+    /// Height of the matrix in pixels, always 8.
+    pub fn height_pixels(&self) -> usize {
+        8
+    }
+
+    /// Re-applies the register settings an LED matrix depends on.
+    ///
+    /// Writes a scan limit of 8 and [`DecodeMode::NoDecode`] to every device. A lower
+    /// scan limit hides whole rows and Code B decoding garbles row patterns, and both
+    /// are easy to set by accident through [`Self::driver`]. The chip cannot be read
+    /// back, so the registers are always rewritten; call this at runtime to repair a
+    /// misconfigured chain.
+    pub fn verify_matrix_config(&mut self) -> Result<()> {
+        self.driver.set_scan_limit_all(NUM_DIGITS)?;
+        if self.auto_scan_limit.is_some() {
+            self.auto_scan_limit = Some(NUM_DIGITS);
+        }
+        self.driver.set_decode_mode_all(DecodeMode::NoDecode)
+    }
+
+    /// Returns `true` if an operation failed with an SPI error after writing part
+    /// of its rows, leaving the devices in an unknown state.
+    ///
+    /// See [`Self::repair`].
+    pub fn needs_repair(&self) -> bool {
+        self.needs_repair
+    }
+
+    /// Brings the chain back to a consistent state after a failed write.
+    ///
+    /// Reasserts every register setting the driver has cached, see
+    /// [`Max7219::reapply_config`], then writes all rows of the framebuffer
+    /// regardless of the [`PowerPolicy`]. Content drawn straight to the chip, e.g.
+    /// with [`Self::draw_text`], is not in the framebuffer and has to be drawn
+    /// again. [`Self::needs_repair`] is cleared once everything was sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if matrix.flush().is_err() && matrix.needs_repair() {
+    ///     matrix.repair()?;
+    /// }
+    /// ```
+    pub fn repair(&mut self) -> Result<()> {
+        self.driver.reapply_config()?;
+        self.send_framebuffer(|_, _, value| value != 0)?;
+        self.needs_repair = false;
+        Ok(())
+    }
+
+    /// Runs a write made of several transactions, flagging the chain for
+    /// [`Self::repair`] if it fails with an SPI error.
+    fn tracked(&mut self, write: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let result = write(self);
+        if let Err(Error::SpiError(_)) = result {
+            self.needs_repair = true;
+        }
+        result
+    }
+
+    /// Lets [`Self::flush`] scan only the rows the content uses.
+    ///
+    /// While enabled, every flush finds the bottom-most lit row on any device and, if
+    /// it changed, programs the scan limit of all devices to end there before the
+    /// rows are written. A thin ticker in the top rows then draws less current.
+    /// Rows are counted after rotation, as the chip sees them, and an empty frame
+    /// scans a single row. Disabling restores the full scan limit of 8 right away.
+    ///
+    /// **Brightness changes with the scan limit.** The chip multiplexes the
+    /// scanned rows, so fewer rows means each one is lit for longer and looks
+    /// brighter; content that grows or shrinks in height will visibly change in
+    /// brightness. The datasheet also warns that scanning three rows or fewer
+    /// needs a larger RSET resistor to keep the row drivers within their power
+    /// rating, so only enable this on hardware designed for it.
+    ///
+    /// Calling [`Self::verify_matrix_config`] resets the limit to 8 until the next
+    /// flush.
+    pub fn optimize_scan_limit(&mut self, enabled: bool) -> Result<()> {
+        if enabled {
+            self.auto_scan_limit.get_or_insert(NUM_DIGITS);
+            return Ok(());
+        }
+        if let Some(limit) = self.auto_scan_limit.take()
+            && limit != NUM_DIGITS
+        {
+            self.driver.set_scan_limit_all(NUM_DIGITS)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the rotation applied to every device.
+    ///
+    /// The rotation is used by [`Self::flush`] as well as the methods that write
+    /// straight to the chip ([`Self::write_buffer`], [`Self::draw_char`],
+    /// [`Self::draw_text`] and friends), so both ways of drawing agree.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut matrix = Matrix4::from_spi(spi)?.with_rotation(Rotation::Cw180);
+    /// ```
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.orientation.rotation = rotation;
+        self
+    }
+
+    /// Changes the rotation at runtime.
+    ///
+    /// Nothing is redrawn; call [`Self::flush`] or draw again to apply it.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.orientation.rotation = rotation;
+    }
+
+    /// Returns the current [`Rotation`].
+    pub fn rotation(&self) -> Rotation {
+        self.orientation.rotation
+    }
+
+    /// Sets rotation, mirroring and device order at once, e.g. as found by
+    /// [`Self::orientation_wizard`]. Like the rotation, it applies to every drawing path.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Changes the [`Orientation`] at runtime. Nothing is redrawn.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Returns the current [`Orientation`].
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Sets how device `device_index` is wired, see [`PixelLayout`].
+    ///
+    /// Every device starts as [`PixelLayout::RowMajor`]. The layout is applied
+    /// after the [`Orientation`] on every drawing path: pixels are mirrored, then
+    /// rotated, then transposed for a column-major module. Nothing is redrawn.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // A generic module at the end of a chain of FC-16 modules
+    /// matrix.set_pixel_layout(3, PixelLayout::ColumnMajor)?;
+    /// ```
+    pub fn set_pixel_layout(&mut self, device_index: usize, layout: PixelLayout) -> Result<()> {
+        if device_index >= DEVICE_COUNT {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                count: DEVICE_COUNT,
+            });
+        }
+        let chain_index = self.chain_index(device_index);
+        if let Some(slot) = self.pixel_layouts.get_mut(chain_index) {
+            *slot = layout;
+        }
+        Ok(())
+    }
+
+    /// Returns the [`PixelLayout`] of a device, `RowMajor` for an out-of-range index.
+    pub fn pixel_layout(&self, device_index: usize) -> PixelLayout {
+        self.pixel_layouts
+            .get(self.chain_index(device_index))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Turns `rows` into the register bytes of the driver's device `chain_index`:
+    /// orientation first, then pixel layout.
+    fn device_output(&self, chain_index: usize, rows: [u8; 8]) -> [u8; 8] {
+        let layout = self.pixel_layouts.get(chain_index).copied();
+        layout
+            .unwrap_or_default()
+            .apply(self.orientation.apply(rows))
+    }
+
+    /// Maps a device index to the driver's index for it, honouring the device order.
+    ///
+    /// Out-of-range indices are passed through for the driver to reject.
+    fn chain_index(&self, device_index: usize) -> usize {
+        if device_index < DEVICE_COUNT {
+            self.orientation.device_index(device_index, DEVICE_COUNT)
+        } else {
+            device_index
+        }
+    }
+
+    /// Rows pinned to the device at `chain_index`, if any.
+    fn pinned_rows(&self, chain_index: usize) -> Option<[u8; 8]> {
+        self.pinned.get(chain_index).copied().flatten()
+    }
+
+    /// Returns `true` if the device at `chain_index` is blanked.
+    fn chain_blanked(&self, chain_index: usize) -> bool {
+        self.blanked.get(chain_index).copied().unwrap_or(false)
+    }
+
+    /// Sets what [`Self::flush`] does when the display is powered off.
+    ///
+    /// Defaults to [`PowerPolicy::AutoPowerOn`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut matrix = SingleMatrix::from_spi(spi)?.with_power_policy(PowerPolicy::Error);
+    /// ```
+    pub fn with_power_policy(mut self, policy: PowerPolicy) -> Self {
+        self.power_policy = policy;
+        self
+    }
+
+    /// Returns the current [`PowerPolicy`].
+    pub fn power_policy(&self) -> PowerPolicy {
+        self.power_policy
+    }
+
+    /// Powers on every device in the matrix.
+    pub fn power_on(&mut self) -> Result<()> {
+        self.driver.power_on()
+    }
+
+    /// Powers off every device in the matrix.
+    ///
+    /// The framebuffer is kept, so a later [`Self::power_on`] followed by
+    /// [`Self::flush`] restores the picture.
+    pub fn power_off(&mut self) -> Result<()> {
+        self.driver.power_off()
+    }
+
+    /// Returns `true` if every device is powered on.
+    ///
+    /// Power changes made through [`Self::driver`] are tracked as well.
+    pub fn is_powered(&self) -> bool {
+        self.driver.is_powered()
+    }
+
+    /// Puts a device into an intensity group, see [`Max7219::assign_group`].
+    pub fn assign_group(&mut self, device_index: usize, group: u8) -> Result<()> {
+        self.driver.assign_group(device_index, group)
+    }
+
+    /// Sets the intensity of every device in a group, see [`Max7219::set_group_intensity`].
+    pub fn set_group_intensity(&mut self, group: u8, intensity: u8) -> Result<()> {
+        self.driver.set_group_intensity(group, intensity)
+    }
+
+    /// Installs a hook called before every wait in blocking helpers such as
+    /// [`Self::scroll_text`], see [`Max7219::set_idle_hook`].
+    pub fn set_idle_hook(&mut self, hook: Option<fn()>) {
+        self.driver.set_idle_hook(hook);
+    }
+
+    /// Replaces characters before font lookup in every text helper, e.g. `'µ'` by `'u'`.
+    ///
+    /// Applies to [`Self::draw_char`], [`Self::draw_text`], scrolling and layouts.
+    /// See [`CharMap`] for the lookup order.
+    pub fn set_char_map(&mut self, substitutions: &'static [(char, char)]) {
+        self.char_map = self.char_map.with_substitutions(substitutions);
+    }
+
+    /// Draws `ch` with a custom bitmap instead of the font glyph in every text helper.
+    ///
+    /// Custom bitmaps are looked up after [`Self::set_char_map`] substitutions.
+    pub fn set_custom_glyphs(&mut self, glyphs: &'static [(char, [u8; 8])]) {
+        self.char_map = self.char_map.with_glyphs(glyphs);
+    }
+
+    /// Returns the character map used by the text helpers.
+    pub fn char_map(&self) -> CharMap {
+        self.char_map
+    }
+
+    /// Sets the font used by [`Self::draw_char`], [`Self::draw_text`],
+    /// [`Self::scroll_text`] and layout scrolling regions.
+    ///
+    /// Defaults to [`fonts::STANDARD_LED_FONT`]. Helpers that take a font keep
+    /// using the one they are given.
+    pub fn set_default_font(&mut self, font: &'static LedFont) {
+        self.default_font = font;
+    }
+
+    /// Returns the font used by the helpers that take none.
+    pub fn default_font(&self) -> &'static LedFont {
+        self.default_font
+    }
+
+    /// Sets the style of [`Self::draw_text`], [`Self::scroll_text`], their
+    /// `_with_font` variants and layout scrolling regions.
+    ///
+    /// Defaults to [`TextStyle::Normal`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.set_text_style(TextStyle::Shadow { dx: 1, dy: 1 });
+    /// matrix.draw_text("HI")?;
+    /// ```
+    pub fn set_text_style(&mut self, style: TextStyle) {
+        self.text_style = style;
+    }
+
+    /// Returns the style of the text helpers.
+    pub fn text_style(&self) -> TextStyle {
+        self.text_style
+    }
+
+    /// Shows a cursor over the 8-pixel cell `x_cell`, i.e. framebuffer columns
+    /// `x_cell * 8` to `x_cell * 8 + 7`, or removes it with `None`.
+    ///
+    /// The cursor is XORed over the cell by [`Self::flush`], leaving the
+    /// framebuffer alone, so removing it restores the content under it. It is
+    /// shown right away on the next flush, also after blinking off.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::OutOfBounds` if the chain has no cell `x_cell`, leaving
+    /// the cursor unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Editing the third digit of a value
+    /// matrix.draw_text("0420")?;
+    /// matrix.set_cursor(Some(CursorStyle::Underline), 2)?;
+    /// loop {
+    ///     matrix.blink_cursor_tick()?;
+    ///     delay.delay_ms(400);
+    /// }
+    /// ```
+    pub fn set_cursor(&mut self, style: Option<CursorStyle>, x_cell: usize) -> Result<()> {
+        let Some(style) = style else {
+            self.cursor = None;
+            return Ok(());
+        };
+        if x_cell >= DEVICE_COUNT {
+            return Err(Error::OutOfBounds {
+                x: (x_cell * 8) as u16,
+                y: 0,
+            });
+        }
+        self.cursor = Some((style, x_cell));
+        self.cursor_visible = true;
+        Ok(())
+    }
+
+    /// Returns the cursor style and cell, if a cursor is set.
+    pub fn cursor(&self) -> Option<(CursorStyle, usize)> {
+        self.cursor
+    }
+
+    /// Toggles the cursor between shown and hidden, then flushes.
+    ///
+    /// Call it at the blink rate, e.g. every 400 ms. Without a cursor this is a
+    /// plain [`Self::flush`].
+    pub fn blink_cursor_tick(&mut self) -> Result<()> {
+        if self.cursor.is_some() {
+            self.cursor_visible = !self.cursor_visible;
+        }
+        self.flush()
+    }
+
+    /// Starts building a [`Layout`] of fixed glyphs and a scrolling region.
+    ///
+    /// See [`Layout`] for an example.
+    pub fn layout<'a>(&mut self) -> Layout<'_, 'a, SPI, BUFFER_LENGTH, DEVICE_COUNT> {
+        Layout::new(self)
+    }
+
+    /// Runs the driver's self test, see [`Max7219::self_test`].
+    ///
+    /// The report is indexed like the driver, following its
+    /// [`ChainOrder`](crate::driver::ChainOrder) but not the [`Orientation`]. The framebuffer is left untouched, so
+    /// [`Self::flush`] brings the picture back.
+    pub fn self_test<D: DelayNs>(&mut self, delay: &mut D) -> Result<SelfTestReport> {
+        self.driver.self_test(delay)
+    }
+
+    /// Flashes every device at `peak` intensity for `hold_ms`, then restores the
+    /// previous levels. See [`Max7219::flash_brightness`].
+    pub fn flash_brightness<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        peak: u8,
+        hold_ms: u32,
+    ) -> Result<()> {
+        self.driver.flash_brightness(delay, peak, hold_ms)
+    }
+
+    /// Follows an ambient light reading, see [`Max7219::auto_brightness_tick`].
+    ///
+    /// Returns whether a new intensity was written.
+    pub fn auto_brightness_tick(
+        &mut self,
+        ambient: u16,
+        curve: &BrightnessCurve,
+        hysteresis: u8,
+    ) -> Result<bool> {
+        self.driver.auto_brightness_tick(ambient, curve, hysteresis)
+    }
+
+    /// Spreads intensity evenly from `from` on device 0 to `to` on the last device.
+    ///
+    /// Intensity is set per chip, so a chain can approximate a brightness gradient,
+    /// e.g. for a sunrise lamp. Intermediate levels are rounded to the nearest step,
+    /// and both ends get exactly `from` and `to`. All devices are updated in one
+    /// transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidIntensity` if either level is above `0x0F` and the
+    /// driver's [`ClampPolicy`](crate::driver::ClampPolicy) rejects it, or an SPI error.
+    pub fn set_intensity_gradient(&mut self, from: u8, to: u8) -> Result<()> {
+        let from = self.driver.checked_intensity(from)?;
+        let to = self.driver.checked_intensity(to)?;
+
+        let mut profile = [from; DEVICE_COUNT];
+        let steps = DEVICE_COUNT.saturating_sub(1) as u32;
+        if steps > 0 {
+            for (device_index, level) in profile.iter_mut().enumerate() {
+                let i = device_index as u32;
+                let weighted = from as u32 * (steps - i) + to as u32 * i;
+                *level = ((weighted + steps / 2) / steps) as u8;
+            }
+        }
+        self.set_intensity_profile(&profile)
+    }
+
+    /// Sets the intensity of every device at once, `profile[i]` going to device `i`.
+    ///
+    /// Sends a single frame, see [`Max7219::set_intensities`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` if `profile` does not have one level per
+    /// device, `Error::InvalidIntensity` if a level is above `0x0F` and the driver's
+    /// [`ClampPolicy`](crate::driver::ClampPolicy) rejects it, or an SPI error.
+    pub fn set_intensity_profile(&mut self, profile: &[u8]) -> Result<()> {
+        self.driver.set_intensities(profile)
+    }
+
     /// Clear a specific device
     pub fn clear(&mut self, device_index: usize) -> Result<()> {
-        self.driver.clear_display(device_index)
+        self.driver.clear_display(self.chain_index(device_index))
     }
 
     /// Clear all device
@@ -127,16 +773,99 @@ where
     }
 
     /// Write a complete buffer to a specific display
+    ///
+    /// The configured [`Orientation`] and [`PixelLayout`] are applied before writing.
     pub fn write_buffer(&mut self, device_index: usize, buffer: &MatrixBuffer) -> Result<()> {
-        for (row, &data) in buffer.data().iter().enumerate() {
-            self.driver.write_raw_digit(device_index, row as u8, data)?;
+        let device_index = self.chain_index(device_index);
+        let rows = self.device_output(device_index, *buffer.data());
+        self.tracked(|matrix| matrix.driver.write_device_digits(device_index, &rows))
+    }
+
+    /// Draws a glyph on the specified display device.
+    ///
+    /// Accepts anything implementing [`Glyph`]: a predefined [`Symbol`](crate::led_matrix::symbols::Symbol),
+    /// a [`MatrixBuffer`], or your own glyph type.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.draw_symbol(0, Symbol::Heart)?;
+    /// ```
+    pub fn draw_symbol(&mut self, device_index: usize, glyph: impl Glyph) -> Result<()> {
+        self.write_buffer(device_index, &glyph.to_buffer())
+    }
+
+    /// Writes the same buffer to every device, one transaction per row.
+    ///
+    /// Much cheaper than calling [`Self::write_buffer`] for each device: 8 frames
+    /// instead of 8 per device. Each device's [`Orientation`] and [`PixelLayout`] is
+    /// applied, and every block of the framebuffer is set to `buffer` as well, so a
+    /// later [`Self::flush`] keeps showing it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.write_buffer_all(&Symbol::Heart.to_buffer())?;
+    /// ```
+    pub fn write_buffer_all(&mut self, buffer: &MatrixBuffer) -> Result<()> {
+        for block in self.framebuffer.chunks_exact_mut(64) {
+            for (pixels, &row) in block.chunks_exact_mut(8).zip(buffer.data()) {
+                for (col, pixel) in pixels.iter_mut().enumerate() {
+                    *pixel = (row >> (7 - col)) & 1;
+                }
+            }
         }
-        Ok(())
+
+        let mut device_rows = [[0u8; 8]; DEVICE_COUNT];
+        for (chain_index, rows) in device_rows.iter_mut().enumerate() {
+            *rows = self.device_output(chain_index, *buffer.data());
+        }
+        self.tracked(|matrix| {
+            for (row, digit_register) in Register::digits().enumerate() {
+                let mut ops = [(Register::NoOp, 0); DEVICE_COUNT];
+                for (op, rows) in ops.iter_mut().zip(&device_rows) {
+                    *op = (digit_register, rows.get(row).copied().unwrap_or(0));
+                }
+                matrix.driver.write_all_registers(&ops)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Draws the same glyph on every device, see [`Self::write_buffer_all`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.draw_symbol_all(Symbol::XMark)?;
+    /// ```
+    pub fn draw_symbol_all(&mut self, glyph: impl Glyph) -> Result<()> {
+        self.write_buffer_all(&glyph.to_buffer())
+    }
+
+    /// Draws a small analog clock showing `hours:minutes` on one device.
+    ///
+    /// Call it again whenever the time changes. Hours wrap at 12 and minutes snap to
+    /// the nearest five; see [`clock::clock_face`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidTime` if `hours` is above 23 or `minutes` above 59,
+    /// before anything is written, or an SPI error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.draw_clock_face(0, 10, 45)?;
+    /// ```
+    pub fn draw_clock_face(&mut self, device_index: usize, hours: u8, minutes: u8) -> Result<()> {
+        self.write_buffer(device_index, &clock::clock_face(hours, minutes)?)
     }
 
     /// Draws a single 8x8 character on the specified display device.
     ///
-    /// The character is converted into an 8-byte bitmap using a predefined font.
+    /// The character is converted into an 8-byte bitmap using the default font, see
+    /// [`Self::set_default_font`].
     /// If the character is unsupported, it will be replaced with "?" char.
     ///
     /// Each byte in the bitmap corresponds to one row of the 8x8 LED matrix (from D0 to D7),
@@ -149,7 +878,7 @@ where
     /// # Errors
     /// Returns an error if the digit conversion fails or if SPI communication fails.
     pub fn draw_char(&mut self, device_index: usize, ch: char) -> Result<()> {
-        self.draw_char_with_font(device_index, ch, &fonts::STANDARD_LED_FONT)
+        self.draw_char_with_font(device_index, ch, self.default_font)
     }
 
     /// Draws a single 8x8 character on the specified display device using a provided font.
@@ -169,34 +898,60 @@ where
         ch: char,
         font: &LedFont,
     ) -> Result<()> {
-        let bitmap = font.get_char(ch);
-        // self.driver.draw_bitmap(bitmap, pos);
-        for (row, value) in bitmap.iter().enumerate() {
-            self.driver
-                .write_raw_digit(device_index, row as u8, *value)?;
-        }
-        Ok(())
+        let bitmap = self.char_map.bitmap(ch, font);
+        self.write_buffer(device_index, &MatrixBuffer::from_data(bitmap))
     }
 
     /// Draw a string of text on the LED matrix using the default font.
     /// Each character is displayed on one device in the daisy chain.
     /// If the string is longer than the number of devices, the extra characters are ignored.
+    /// Devices without a character are cleared, so `draw_text("")` blanks the display.
+    /// Zero-width characters such as combining accents do not take a device, see
+    /// [`LedFont::visible_chars`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "test-util")]
+    /// # fn main() -> max7219_display::Result<()> {
+    /// # let mut matrix = max7219_display::led_matrix::display::Matrix4::for_tests()?;
+    /// // One character per device, the fourth device is cleared
+    /// matrix.draw_text("ABC")?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "test-util"))]
+    /// # fn main() {}
+    /// ```
     pub fn draw_text(&mut self, text: &str) -> Result<()> {
-        self.draw_text_with_font(text, &fonts::STANDARD_LED_FONT)
+        self.draw_text_with_font(text, self.default_font)
     }
 
     /// Draw a string of text on the LED matrix using a specified font.
     /// Each character is displayed on one device in the daisy chain.
     /// If the string is longer than the number of devices, the extra characters are ignored.
+    /// Devices pinned by [`Self::pin_value`] keep showing their value.
+    /// The text is drawn in the style set by [`Self::set_text_style`].
     pub fn draw_text_with_font(&mut self, text: &str, font: &LedFont) -> Result<()> {
-        let device_count = self.driver.device_count();
-
-        let mut row_data = [[0u8; MAX_DISPLAYS]; 8];
-
-        for (device_index, ch) in text.chars().take(device_count).enumerate() {
-            let bitmap = font.get_char(ch);
-            for (row, &value) in bitmap.iter().enumerate() {
-                row_data[row][device_index] = value;
+        // Rows of each device, indexed like the driver
+        let mut device_rows = [[0u8; 8]; DEVICE_COUNT];
+
+        let mut glyphs = [[0u8; 8]; DEVICE_COUNT];
+        let mut glyph_count = 0;
+        for (glyph, ch) in glyphs.iter_mut().zip(font.visible_chars(text)) {
+            *glyph = self.char_map.bitmap(ch, font);
+            glyph_count += 1;
+        }
+        let glyphs = glyphs.get(..glyph_count).unwrap_or_default();
+        for index in 0..glyphs.len() {
+            let device_index = self.chain_index(index);
+            let rows = self.text_style.render(glyphs, index);
+            if let Some(out) = device_rows.get_mut(device_index) {
+                *out = self.device_output(device_index, rows);
+            }
+        }
+        for (device_index, (out, pinned)) in device_rows.iter_mut().zip(&self.pinned).enumerate() {
+            if let Some(rows) = pinned {
+                *out = self.device_output(device_index, *rows);
             }
         }
 
@@ -204,121 +959,635 @@ where
         // Example: if digit_register = Digit3 and device_count = 2,
         // then ops will look like:
         //     ops = [
-        //         (Digit3, row_data[3][0]), // device 1 (farthest), row 3
-        //         (Digit3, row_data[3][1]), // device 0 (nearest), row 3
+        //         (Digit3, device_rows[0][3]), // device 0, row 3
+        //         (Digit3, device_rows[1][3]), // device 1, row 3
         //     ];
-        for (row_index, digit_register) in Register::digits().enumerate() {
-            let ops_row = row_data[row_index];
-            let mut ops = [(Register::NoOp, 0); MAX_DISPLAYS];
+        self.tracked(|matrix| {
+            for (row, digit_register) in Register::digits().enumerate() {
+                let mut ops = [(Register::NoOp, 0); DEVICE_COUNT];
+                for (op, rows) in ops.iter_mut().zip(&device_rows) {
+                    *op = (digit_register, rows.get(row).copied().unwrap_or(0));
+                }
+                matrix.driver.write_all_registers(&ops)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Scroll the given text across the LED matrix.
+    ///
+    /// This will render `text` using the default font (see [`Self::set_default_font`])
+    /// and step through each frame at the delay specified by `config.step_delay_ns`. If
+    /// `config.loop_text` is true, the text will repeat with
+    /// `config.loop_padding` pixels of blank space between repetitions.
+    ///
+    ///
+    /// # Parameters
+    ///
+    /// - `delay`: delay provider implementing `embedded_hal::delay::DelayNs`.
+    /// - `text`: the string slice to scroll.
+    /// - `config`: scrolling configuration (speed, step size, looping).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MatrixError` if updating the display buffer fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "test-util")]
+    /// # fn main() -> max7219_display::Result<()> {
+    /// # let mut matrix = max7219_display::led_matrix::display::Matrix4::for_tests()?;
+    /// # let mut delay = embedded_hal_mock::eh1::delay::NoopDelay::new();
+    /// use max7219_display::led_matrix::scroll::ScrollConfig;
+    ///
+    /// // Scroll through once, two columns every 50 ms
+    /// let config = ScrollConfig {
+    ///     step_delay_ns: 50_000_000,
+    ///     pixels_per_step: 2,
+    ///     loop_text: false,
+    ///     ..ScrollConfig::default()
+    /// };
+    /// matrix.scroll_text(&mut delay, "Hello, world", config)?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "test-util"))]
+    /// # fn main() {}
+    /// ```
+    pub fn scroll_text<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        text: &str,
+        config: ScrollConfig,
+    ) -> Result<()> {
+        self.scroll_text_with_font(delay, text, self.default_font, config)
+    }
+
+    /// Shows a message of any length, scrolling only when it does not fit.
+    ///
+    /// Text that fits the chain is drawn once, centered, and the method returns
+    /// without waiting. Longer text scrolls through exactly once with `config`'s speed
+    /// and step size; `loop_text` is ignored. A single pass ends with the text
+    /// scrolled off, leaving the display blank.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.show_message(&mut delay, "HI", &STANDARD_LED_FONT, ScrollConfig::default())?;
+    /// matrix.show_message(&mut delay, "Hello, world", &STANDARD_LED_FONT, ScrollConfig::default())?;
+    /// ```
+    pub fn show_message<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        text: &str,
+        font: &LedFont,
+        config: ScrollConfig,
+    ) -> Result<()> {
+        let config = ScrollConfig {
+            loop_text: false,
+            ..config
+        };
+        if !self.fits(text, font) {
+            return self.scroll_text_with_font(delay, text, font, config);
+        }
+
+        let padding = self.width_pixels() - text_width(text, font, 0);
+        let mut scroller = ScrollingText::new(text, font, config)
+            .with_char_map(self.char_map)
+            .with_style(self.text_style)
+            .with_visible_width(self.width_pixels());
+        scroller.set_offset(-((padding / 2) as i32));
+        self.draw_scroller_frame(&scroller)
+    }
+
+    /// Returns whether `text` drawn with `font` fits on the chain without scrolling.
+    ///
+    /// This is the check [`Self::show_message`] uses to decide between centering
+    /// and scrolling.
+    pub fn fits(&self, text: &str, font: &LedFont) -> bool {
+        text_width(text, font, 0) <= self.width_pixels()
+    }
+
+    /// Like [`Self::scroll_text`], but paces the steps with `timer` so the time
+    /// spent drawing each one counts toward `config.step_delay_ns`.
+    ///
+    /// The timer is started when the scroll starts. A step that takes longer than
+    /// the delay skips the steps it ran into, so the text keeps its speed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut timer = FrameTimer::new(|| clock.now_us());
+    /// matrix.scroll_text_timed(&mut delay, &mut timer, "IN TIME", ScrollConfig::default())?;
+    /// ```
+    pub fn scroll_text_timed<D: DelayNs, C: FnMut() -> u64>(
+        &mut self,
+        delay: &mut D,
+        timer: &mut FrameTimer<C>,
+        text: &str,
+        config: ScrollConfig,
+    ) -> Result<()> {
+        timer.start();
+        self.scroll_loop(text, self.default_font, config, |matrix| {
+            matrix.driver.idle();
+            timer.wait_for_next_frame(delay, config.step_delay_ns / 1000)
+        })
+    }
+
+    fn scroll_text_with_font<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        text: &str,
+        font: &LedFont,
+        config: ScrollConfig,
+    ) -> Result<()> {
+        self.scroll_loop(text, font, config, |matrix| {
+            matrix.driver.idle();
+            delay.delay_ns(config.step_delay_ns);
+            0
+        })
+    }
+
+    /// Scrolls `text` through once, or forever when looping, calling `wait`
+    /// between steps; `wait` returns how many extra steps to skip.
+    fn scroll_loop(
+        &mut self,
+        text: &str,
+        font: &LedFont,
+        config: ScrollConfig,
+        mut wait: impl FnMut(&mut Self) -> u32,
+    ) -> Result<()> {
+        let mut scroller = ScrollingText::new(text, font, config)
+            .with_char_map(self.char_map)
+            .with_style(self.text_style)
+            .with_visible_width(self.width_pixels());
+        scroller.reset();
+
+        loop {
+            self.draw_scroller_frame(&scroller)?;
+
+            if !scroller.step() {
+                break; // Stop if not looping and text has finished scrolling
+            }
+
+            let skipped = wait(self);
+            for _ in 0..skipped {
+                if !scroller.step() {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the scroller's current window across all devices.
+    fn draw_scroller_frame(&mut self, scroller: &ScrollingText<'_>) -> Result<()> {
+        // Each device shows 8 pixels, so device N shows pixels at offset + (N * 8)
+        for device_index in 0..self.device_count() {
+            let frame = scroller.get_frame_at(device_index as i32 * 8)?;
+            if self.pinned_rows(self.chain_index(device_index)).is_none() {
+                self.write_buffer(device_index, &frame)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush the internal display buffer to the actual LED matrix hardware.
+    ///
+    /// This function goes row by row (0 to 7), and for each row, it builds an array of
+    /// SPI operations (`ops`) to send to all devices in the daisy-chained display.
+    /// It packs each row of pixels into a single byte for each device, then sends the data
+    /// using the driver's `write_all_registers` method.
+    ///
+    /// ### Example logic (DEVICE_COUNT = 2, row = 0):
+    /// Assume self.framebuffer contains pixel bits for 2 devices (128 total):
+    ///
+    /// Device 0, row 0 pixels: [1, 0, 1, 0, 1, 0, 1, 0]  => 0b10101010 => 0xAA
+    /// Device 1, row 0 pixels: [1, 1, 1, 1, 0, 0, 0, 0]  => 0b11110000 => 0xF0
+    ///
+    /// Framebuffer block `i` goes to device `i`, like character `i` of [`Self::draw_text`]:
+    ///     ops\[0\] = (Digit0, 0xAA)  // Device 0
+    ///     ops\[1\] = (Digit0, 0xF0)  // Device 1
+    ///
+    /// These are sent out in one SPI write for Digit0, and similarly repeated for Digit1 through Digit7.
+    /// The driver's [`ChainOrder`](crate::driver::ChainOrder) decides which end
+    /// of the chain device 0 is.
+    ///
+    /// Before packing, each device's block is mirrored and rotated by the
+    /// [`Orientation`], then transposed if its [`PixelLayout`] is column-major.
+    ///
+    /// If any device is powered off, the configured [`PowerPolicy`] decides whether the
+    /// display is powered on first, `Error::PoweredOff` is returned, or nothing is written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "test-util")]
+    /// # fn main() -> max7219_display::Result<()> {
+    /// # let mut matrix = max7219_display::led_matrix::display::Matrix4::for_tests()?;
+    /// # matrix.driver().power_on()?;
+    /// // Drawing only changes the framebuffer, the chain is updated at once
+    /// matrix.write_column(0, 0b1000_0001)?;
+    /// matrix.write_column(31, 0b1000_0001)?;
+    /// matrix.flush()?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "test-util"))]
+    /// # fn main() {}
+    /// ```
+    pub fn flush(&mut self) -> Result<()> {
+        self.write_framebuffer(|_, _, value| value != 0)
+    }
+
+    /// Stores a gray level for one pixel, for use with [`Self::flush_dithered`].
+    ///
+    /// `x` runs across the whole chain (`0..DEVICE_COUNT * 8`) and `y` from top to
+    /// bottom (`0..8`). A plain [`Self::flush`] shows every non-`Off` level fully lit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::OutOfBounds` if the coordinate is outside the matrix.
+    pub fn set_gray_pixel(&mut self, x: usize, y: usize, level: GrayLevel) -> Result<()> {
+        if x >= self.width_pixels() || y >= self.height_pixels() {
+            return Err(Error::OutOfBounds {
+                x: x as u16,
+                y: y as u16,
+            });
+        }
+        let index = (x / 8) * 64 + y * 8 + x % 8;
+        if let Some(pixel) = self.framebuffer.get_mut(index) {
+            *pixel = level.to_pixel();
+        }
+        Ok(())
+    }
+
+    /// Sets the 8 vertical pixels of chain column `x` in the framebuffer.
+    ///
+    /// `x` runs across the whole chain (`0..DEVICE_COUNT * 8`). Bit 0 of `bits` is
+    /// the top row and bit 7 the bottom row, so a bar of height `h` is
+    /// `0xFF << (8 - h)`. Call [`Self::flush`] to show the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::OutOfBounds` if `x` is outside the matrix.
+    pub fn write_column(&mut self, x: usize, bits: u8) -> Result<()> {
+        let base = Self::column_base(x)?;
+        let column = self.framebuffer.iter_mut().skip(base).step_by(8).take(8);
+        for (row, pixel) in column.enumerate() {
+            *pixel = (bits >> row) & 1;
+        }
+        Ok(())
+    }
+
+    /// Reads chain column `x` from the framebuffer, with bit 0 as the top row.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::OutOfBounds` if `x` is outside the matrix.
+    pub fn get_column(&self, x: usize) -> Result<u8> {
+        let base = Self::column_base(x)?;
+        let column = self.framebuffer.iter().skip(base).step_by(8).take(8);
+        let mut bits = 0;
+        for (row, &pixel) in column.enumerate() {
+            if pixel != 0 {
+                bits |= 1 << row;
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Copies the chain columns `src` into the columns `dst`, mirrored left to
+    /// right, in the framebuffer.
+    ///
+    /// The first column of `dst` gets the last column of `src`, and so on. This
+    /// lets one render show a message normally on one part of the chain and
+    /// mirrored on another, e.g. for a sign read from both sides of a window.
+    /// Call [`Self::flush`] to show the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::OutOfBounds` if a range reaches past the matrix, or
+    /// `Error::ValueOutOfRange` if the ranges differ in length or overlap. Nothing
+    /// is copied then.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Text on the left half of a Matrix8, mirrored onto the right half
+    /// matrix.draw_text("OPEN")?;
+    /// matrix.mirrored_copy(0..32, 32..64)?;
+    /// matrix.flush()?;
+    /// ```
+    pub fn mirrored_copy(&mut self, src: Range<usize>, dst: Range<usize>) -> Result<()> {
+        for range in [&src, &dst] {
+            if range.end > DEVICE_COUNT * 8 {
+                // The last column of the range is past the matrix
+                return Err(Error::OutOfBounds {
+                    x: (range.end - 1) as u16,
+                    y: 0,
+                });
+            }
+        }
+        if src.len() != dst.len() || (src.start < dst.end && dst.start < src.end) {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        for (x, src_x) in dst.zip(src.rev()) {
+            let bits = self.get_column(src_x)?;
+            self.write_column(x, bits)?;
+        }
+        Ok(())
+    }
+
+    /// Framebuffer index of the top pixel of chain column `x`.
+    fn column_base(x: usize) -> Result<usize> {
+        if x >= DEVICE_COUNT * 8 {
+            return Err(Error::OutOfBounds { x: x as u16, y: 0 });
+        }
+        Ok((x / 8) * 64 + x % 8)
+    }
+
+    /// Flushes the framebuffer with temporal dithering for gray levels.
+    ///
+    /// Each pixel is lit or not according to its [`GrayLevel`] and an ordered-dither
+    /// pattern keyed by `frame_index`. Call this from a timer at 200 Hz or faster and
+    /// increment `frame_index` every time; see the [`gray`](crate::led_matrix::gray)
+    /// module for the flicker trade-offs. Pixels drawn through the binary APIs are
+    /// always lit.
+    pub fn flush_dithered(&mut self, frame_index: u8) -> Result<()> {
+        self.write_framebuffer(|col, row, value| {
+            GrayLevel::from_pixel(value).is_lit(col, row, frame_index)
+        })
+    }
+
+    /// Applies the [`PowerPolicy`], then writes the framebuffer with
+    /// [`Self::send_framebuffer`].
+    fn write_framebuffer(&mut self, lit: impl Fn(usize, usize, u8) -> bool) -> Result<()> {
+        if !self.driver.is_powered() {
+            match self.power_policy {
+                PowerPolicy::AutoPowerOn => {
+                    self.count_flush_write();
+                    self.driver.power_on()?
+                }
+                PowerPolicy::Error => return Err(Error::PoweredOff),
+                PowerPolicy::Skip => return Ok(()),
+            }
+        }
+        self.tracked(|matrix| matrix.send_framebuffer(lit))
+    }
+
+    /// Packs the framebuffer into rows, deciding each pixel with `lit(col, row, value)`,
+    /// and writes them to the chain.
+    fn send_framebuffer(&mut self, lit: impl Fn(usize, usize, u8) -> bool) -> Result<()> {
+        // Pack every device first so the rotation can move pixels between rows,
+        // indexed by framebuffer block
+        let mut device_rows = [[0u8; 8]; DEVICE_COUNT];
+        for (block, rows) in device_rows.iter_mut().enumerate() {
+            let chain_index = self.chain_index(block);
+            let mut packed = match self.pinned_rows(chain_index) {
+                Some(pinned) => pinned,
+                None => self.packed_rows(block, &lit),
+            };
+            if let Some((style, x_cell)) = self.cursor
+                && self.cursor_visible
+                && x_cell == block
+            {
+                for (data, mask) in packed.iter_mut().zip(style.mask()) {
+                    *data ^= mask;
+                }
+            }
+            *rows = self.device_output(chain_index, packed);
+        }
+
+        if let Some(current) = self.auto_scan_limit {
+            let used = device_rows
+                .iter()
+                .filter_map(|rows| rows.iter().rposition(|&data| data != 0))
+                .max()
+                .map_or(1, |row| row as u8 + 1);
+            if used != current {
+                self.count_flush_write();
+                self.driver.set_scan_limit_all(used)?;
+                self.auto_scan_limit = Some(used);
+            }
+        }
+
+        for (row, digit_register) in Register::digits().enumerate() {
+            let mut ops = [(Register::NoOp, 0); DEVICE_COUNT];
+
+            for (block, rows) in device_rows.iter().enumerate() {
+                let ops_index = self.chain_index(block);
+                if self.chain_blanked(ops_index) {
+                    continue;
+                }
+                if let Some(op) = ops.get_mut(ops_index) {
+                    *op = (digit_register, rows.get(row).copied().unwrap_or(0));
+                }
+            }
+
+            if ops.iter().all(|&(register, _)| register == Register::NoOp) {
+                #[cfg(feature = "stats")]
+                {
+                    self.stats.rows_skipped = self.stats.rows_skipped.saturating_add(1);
+                }
+                continue;
+            }
+            self.count_flush_write();
+            self.driver.write_all_registers(&ops[..DEVICE_COUNT])?;
+        }
+        Ok(())
+    }
+
+    /// Counts one full-chain frame sent by a flush.
+    #[inline]
+    fn count_flush_write(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            let bytes = (DEVICE_COUNT * 2) as u32;
+            self.stats.transactions = self.stats.transactions.saturating_add(1);
+            self.stats.bytes = self.stats.bytes.saturating_add(bytes);
+        }
+    }
+
+    /// Returns the SPI traffic of all flushes since creation or the last
+    /// [`Self::reset_stats`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.reset_stats();
+    /// matrix.flush()?;
+    /// let stats = matrix.flush_stats();
+    /// defmt::info!("flush: {} transactions, {} bytes", stats.transactions, stats.bytes);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn flush_stats(&self) -> FlushStats {
+        self.stats
+    }
+
+    /// Sets all flush counters back to zero.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats = FlushStats::default();
+    }
 
-            for (device_index, op) in ops.iter_mut().take(device_count).enumerate() {
-                *op = (digit_register, ops_row[device_index]);
+    /// Packs the framebuffer block of one device into rows, before orientation.
+    fn packed_rows(&self, block: usize, lit: &impl Fn(usize, usize, u8) -> bool) -> [u8; 8] {
+        let mut rows = [0u8; 8];
+        for (row, packed_byte) in rows.iter_mut().enumerate() {
+            let buffer_start = block * 64 + row * 8;
+            for col in 0..8 {
+                let pixel_index = buffer_start + col;
+                if self
+                    .framebuffer
+                    .get(pixel_index)
+                    .is_some_and(|&value| lit(col, row, value))
+                {
+                    // bit 7 is leftmost pixel (Col 0) on the display
+                    *packed_byte |= 1 << (7 - col);
+                }
             }
-
-            self.driver.write_all_registers(&ops[..device_count])?;
         }
-
-        Ok(())
+        rows
     }
 
-    /// Scroll the given text across the LED matrix.
-    ///
-    /// This will render `text` using the current font and step through
-    /// each frame at the delay specified by `config.step_delay_ns`. If
-    /// `config.loop_text` is true, the text will repeat with
-    /// `config.loop_padding` pixels of blank space between repetitions.
-    ///
-    ///
-    /// # Parameters
+    /// Hides or shows one device without touching the framebuffer.
     ///
-    /// - `delay`: delay provider implementing `embedded_hal::delay::DelayNs`.
-    /// - `text`: the string slice to scroll.
-    /// - `config`: scrolling configuration (speed, step size, looping).
+    /// Blanking writes zeros to the device's rows, and [`Self::flush`] leaves it
+    /// alone until it is shown again, even while the framebuffer keeps changing.
+    /// Unblanking redraws the device from the framebuffer right away. Unlike
+    /// [`Self::clear`], nothing is forgotten.
     ///
     /// # Errors
     ///
-    /// Returns a `MatrixError` if updating the display buffer fails.
-    pub fn scroll_text<D: DelayNs>(
-        &mut self,
-        delay: &mut D,
-        text: &str,
-        config: ScrollConfig,
-    ) -> Result<()> {
-        let mut scroller = ScrollingText::new(text, &fonts::STANDARD_LED_FONT, config);
-        scroller.reset();
-
-        let device_count = self.driver().device_count();
-
-        loop {
-            // Store the original offset
-            let base_offset = scroller.current_offset;
-
-            // Update each display device
-            for device_index in 0..device_count {
-                // Set offset for this specific device
-                // Each device shows 8 pixels, so device N shows pixels at offset + (N * 8)
-                scroller.current_offset = base_offset + (device_index as i32 * 8);
-
-                let frame = scroller.get_frame()?; // Each device shows 8 pixels width
-                self.write_buffer(device_index, &frame)?;
-            }
-
-            // Restore the original offset and step to next position
-            scroller.current_offset = base_offset;
-
-            if !scroller.step() {
-                break; // Stop if not looping and text has finished scrolling
-            }
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range, or an SPI error.
+    pub fn blank_device(&mut self, device_index: usize, blank: bool) -> Result<()> {
+        if device_index >= DEVICE_COUNT {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                count: DEVICE_COUNT,
+            });
+        }
 
-            delay.delay_ns(config.step_delay_ns);
+        // `blanked` is indexed like the driver, as is the ops array in flush
+        let chain_index = self.chain_index(device_index);
+        if let Some(slot) = self.blanked.get_mut(chain_index) {
+            *slot = blank;
         }
+        let rows = if blank {
+            [0; 8]
+        } else {
+            let packed = self.packed_rows(device_index, &|_, _, value| value != 0);
+            self.device_output(chain_index, packed)
+        };
+        self.driver.write_device_digits(chain_index, &rows)
+    }
 
-        Ok(())
+    /// Returns `true` if the device is hidden by [`Self::blank_device`].
+    pub fn is_blanked(&self, device_index: usize) -> bool {
+        self.chain_blanked(self.chain_index(device_index))
     }
 
-    /// Flush the internal display buffer to the actual LED matrix hardware.
+    /// Pins a small number to one device, keeping it there while the rest of the
+    /// chain changes.
     ///
-    /// This function goes row by row (0 to 7), and for each row, it builds an array of
-    /// SPI operations (`ops`) to send to all devices in the daisy-chained display.
-    /// It packs each row of pixels into a single byte for each device, then sends the data
-    /// using the driver's `write_all_registers` method.
+    /// Values `0` to `9` are drawn with the default font. `10` to `99` and `-9` to
+    /// `-1` are drawn as two right-aligned characters from
+    /// [`fonts::COMPACT_DIGITS`]. Only the pinned device is written, right away,
+    /// and its framebuffer area is updated to match. Until [`Self::unpin_value`],
+    /// [`Self::draw_text`], scrolling and [`Self::flush`] (and so layouts) show
+    /// the value on that device instead of their own content. Direct writes such as
+    /// [`Self::write_buffer`] still go through.
     ///
-    /// ### Example logic (DEVICE_COUNT = 2, row = 0):
-    /// Assume self.framebuffer contains pixel bits for 2 devices (128 total):
+    /// # Errors
     ///
-    /// Device 0, row 0 pixels: [1, 0, 1, 0, 1, 0, 1, 0]  => 0b10101010 => 0xAA
-    /// Device 1, row 0 pixels: [1, 1, 1, 1, 0, 0, 0, 0]  => 0b11110000 => 0xF0
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range, and
+    /// `Error::ValueOutOfRange` if `value` is outside `-9..=99`.
     ///
-    /// Since SPI sends left to right, we must reverse the device order in the ops array:
-    ///     ops\[0\] = (Digit0, 0xF0)  // Device 1
-    ///     ops\[1\] = (Digit0, 0xAA)  // Device 0
+    /// # Example
     ///
-    /// These are sent out in one SPI write for Digit0, and similarly repeated for Digit1 through Digit7.
-    pub fn flush(&mut self) -> Result<()> {
-        for (row, digit_register) in Register::digits().enumerate() {
-            let mut ops = [(Register::NoOp, 0); DEVICE_COUNT];
+    /// ```rust,ignore
+    /// matrix.pin_value(3, temperature)?;
+    /// matrix.scroll_text(&mut delay, "OUTSIDE", ScrollConfig::default())?;
+    /// ```
+    pub fn pin_value(&mut self, device_index: usize, value: i32) -> Result<()> {
+        if device_index >= DEVICE_COUNT {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                count: DEVICE_COUNT,
+            });
+        }
 
-            for device_index in 0..DEVICE_COUNT {
-                let buffer_start = device_index * 64 + row * 8;
-                let mut packed_byte = 0;
-                for col in 0..8 {
-                    let pixel_index = buffer_start + col;
-                    if pixel_index < self.framebuffer.len() && self.framebuffer[pixel_index] != 0 {
-                        // bit 7 is leftmost pixel (Col 0) on the display
-                        packed_byte |= 1 << (7 - col);
-                    }
-                }
+        let rows = self.value_rows(value)?;
+        let chain_index = self.chain_index(device_index);
+        if let Some(slot) = self.pinned.get_mut(chain_index) {
+            *slot = Some(rows);
+        }
+        self.blit_rows(device_index, &rows);
+        if self.chain_blanked(chain_index) {
+            return Ok(());
+        }
 
-                // Fill ops array in reverse order for SPI chain
-                let ops_index = DEVICE_COUNT - 1 - device_index;
-                ops[ops_index] = (digit_register, packed_byte);
-            }
+        let rows = self.device_output(chain_index, rows);
+        self.driver.write_device_digits(chain_index, &rows)
+    }
 
-            self.driver.write_all_registers(&ops[..DEVICE_COUNT])?;
+    /// Releases a device pinned by [`Self::pin_value`].
+    ///
+    /// The value stays visible until the next draw or flush.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if the index is out of range.
+    pub fn unpin_value(&mut self, device_index: usize) -> Result<()> {
+        if device_index >= DEVICE_COUNT {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                count: DEVICE_COUNT,
+            });
+        }
+        let chain_index = self.chain_index(device_index);
+        if let Some(slot) = self.pinned.get_mut(chain_index) {
+            *slot = None;
         }
         Ok(())
     }
 
+    /// Renders a value for [`Self::pin_value`].
+    fn value_rows(&self, value: i32) -> Result<[u8; 8]> {
+        let (left, right) = match value {
+            0..=9 => {
+                let ch = char::from(b'0' + value as u8);
+                return Ok(self.char_map.bitmap(ch, self.default_font));
+            }
+            10..=99 => (compact_digit(value / 10)?, compact_digit(value % 10)?),
+            -9..=-1 => (COMPACT_MINUS, compact_digit(-value)?),
+            _ => return Err(Error::ValueOutOfRange),
+        };
+
+        // Columns 1-3 and 5-7, rows 1-5
+        let mut rows = [0u8; 8];
+        for (row, (&l, &r)) in rows[1..6].iter_mut().zip(left.iter().zip(&right)) {
+            *row = (l << 4) | r;
+        }
+        Ok(rows)
+    }
+
+    /// Copies an 8x8 row pattern (bit 7 = leftmost column) into the framebuffer
+    /// area of one device, without writing to the chip.
+    pub(crate) fn blit_rows(&mut self, device_index: usize, rows: &[u8; 8]) {
+        let block = self.framebuffer.chunks_exact_mut(64).nth(device_index);
+        let block_rows = block
+            .into_iter()
+            .flat_map(|block| block.chunks_exact_mut(8));
+        for (pixels, &bits) in block_rows.zip(rows) {
+            for (col, pixel) in pixels.iter_mut().enumerate() {
+                *pixel = (bits >> (7 - col)) & 1;
+            }
+        }
+    }
+
     /// Clear the internal framebuffer (sets all pixels to 0).
     pub fn clear_buffer(&mut self) {
         self.framebuffer.fill(0);
@@ -334,9 +1603,10 @@ where
 #[cfg(feature = "graphics")]
 mod eg_imports {
     pub use embedded_graphics_core::Pixel;
+    pub use embedded_graphics_core::image::GetPixel;
 
     pub use embedded_graphics_core::pixelcolor::BinaryColor;
-    pub use embedded_graphics_core::prelude::{DrawTarget, OriginDimensions, Size};
+    pub use embedded_graphics_core::prelude::{DrawTarget, OriginDimensions, Point, Size};
 }
 
 #[cfg(feature = "graphics")]
@@ -367,8 +1637,8 @@ where
 
                 if device < DEVICE_COUNT && row < 8 && col < 8 {
                     let index = device * 64 + row * 8 + col;
-                    if index < self.framebuffer.len() {
-                        self.framebuffer[index] = color.is_on() as u8;
+                    if let Some(pixel) = self.framebuffer.get_mut(index) {
+                        *pixel = color.is_on() as u8;
                     }
                 }
             }
@@ -387,14 +1657,57 @@ impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> OriginDimension
     }
 }
 
+// Reads back pixels from the framebuffer, not from the hardware
+#[cfg(feature = "graphics")]
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> GetPixel
+    for LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+{
+    type Color = BinaryColor;
+
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        if !self.bounding_box().contains(p) {
+            return None;
+        }
+        let device = (p.x as usize) / 8;
+        let col = (p.x as usize) % 8;
+        let row = p.y as usize;
+        let index = device * 64 + row * 8 + col;
+
+        self.framebuffer
+            .get(index)
+            .map(|&value| BinaryColor::from(value != 0))
+    }
+}
+
+/// Pattern of the compact digit `digit`, `0` to `9`.
+fn compact_digit(digit: i32) -> Result<[u8; 5]> {
+    usize::try_from(digit)
+        .ok()
+        .and_then(|digit| COMPACT_DIGITS.get(digit))
+        .copied()
+        .ok_or(Error::ValueOutOfRange)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Error;
-    use crate::led_matrix::display::{Matrix4, SingleMatrix};
+    use crate::driver::ChainOrder;
+    use crate::led_matrix::display::{Matrix4, Matrix8, SingleMatrix};
     use crate::led_matrix::fonts::STANDARD_LED_FONT;
-    use crate::led_matrix::{LedMatrix, buffer::MatrixBuffer, fonts::LedFont};
+    use crate::led_matrix::style::TextStyle;
+    use crate::led_matrix::{
+        CursorStyle, GrayLevel, LedMatrix, Orientation, PixelLayout, PowerPolicy, Rotation,
+        buffer::MatrixBuffer,
+        fonts::LedFont,
+        scroll::{ScrollConfig, ScrollingText},
+    };
+    #[cfg(feature = "symbols-basic")]
+    use crate::led_matrix::{Glyph, symbols::Symbol};
     use crate::registers::Register;
+    use crate::test_support::FailingSpi;
+    use crate::test_util::{FakeMatrix, FrameRecorder};
     use crate::{Max7219, NUM_DIGITS};
+    use core::sync::atomic::{AtomicUsize, Ordering};
     use embedded_hal_mock::eh1::{spi::Mock as SpiMock, spi::Transaction};
 
     fn write_reg(addr: u8, value: u8) -> Vec<Transaction<u8>> {
@@ -433,6 +1746,12 @@ mod tests {
             crate::registers::DecodeMode::NoDecode as u8,
         ));
 
+        // set_intensity_all(DEFAULT_INITIAL_INTENSITY)
+        expected_transactions.extend(write_reg(
+            Register::Intensity.addr(),
+            crate::driver::DEFAULT_INITIAL_INTENSITY,
+        ));
+
         // clear_all() - 8 digits/rows
         for digit in Register::digits() {
             expected_transactions.extend(write_reg(digit.addr(), 0x00));
@@ -445,6 +1764,100 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_from_driver_with_init() {
+        let mut expected_transactions = write_reg(Register::NoOp.addr(), 0x00);
+        expected_transactions.extend(write_reg(Register::Shutdown.addr(), 0x01));
+        expected_transactions.extend(write_reg(Register::DisplayTest.addr(), 0x00));
+        expected_transactions.extend(write_reg(Register::ScanLimit.addr(), NUM_DIGITS - 1));
+        expected_transactions.extend(write_reg(Register::DecodeMode.addr(), 0x00));
+        expected_transactions.extend(write_reg(Register::Intensity.addr(), 0x07));
+        for digit in Register::digits() {
+            expected_transactions.extend(write_reg(digit.addr(), 0x00));
+        }
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let matrix = SingleMatrix::from_driver_with_init(driver, true).unwrap();
+        assert!(matrix.is_powered());
+        spi.done();
+    }
+
+    #[test]
+    fn test_repair_after_failed_flush() {
+        // Initialization sends a no-op frame, 5 settings and 8 cleared rows, the
+        // flush then fails on its row 3
+        let mut spi = FailingSpi::failing_on(14 + 3);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> =
+            LedMatrix::from_driver_with_init(driver, true).unwrap();
+        matrix.write_column(0, 0xFF).unwrap();
+
+        // Rejected arguments leave the chain untouched
+        assert!(matrix.write_buffer(2, &MatrixBuffer::new()).is_err());
+        assert!(!matrix.needs_repair());
+
+        assert!(matches!(matrix.flush(), Err(Error::SpiError(_))));
+        assert!(matrix.needs_repair());
+
+        matrix.repair().unwrap();
+        assert!(!matrix.needs_repair());
+        let intensity = matrix.driver().intensity(0).unwrap();
+
+        let both =
+            |register: Register, value: u8| vec![register.addr(), value, register.addr(), value];
+        let mut expected = vec![
+            both(Register::Shutdown, 0x01),
+            both(Register::DisplayTest, 0x00),
+            both(Register::ScanLimit, NUM_DIGITS - 1),
+            both(Register::DecodeMode, 0x00),
+            both(Register::Intensity, intensity),
+        ];
+        for digit in Register::digits() {
+            expected.push(vec![digit.addr(), 0x80, digit.addr(), 0x00]);
+        }
+        assert_eq!(spi.writes.len(), 18 + expected.len());
+        assert_eq!(spi.writes[18..], expected);
+    }
+
+    #[test]
+    fn test_verify_matrix_config() {
+        let scan_limit = |value| {
+            Transaction::write_vec(vec![
+                Register::ScanLimit.addr(),
+                value,
+                Register::ScanLimit.addr(),
+                value,
+            ])
+        };
+        let expected_transactions = [
+            // Misconfiguration through driver(): only 4 rows scanned
+            Transaction::transaction_start(),
+            scan_limit(3),
+            Transaction::transaction_end(),
+            // verify_matrix_config() restores all 8 rows and raw row data
+            Transaction::transaction_start(),
+            scan_limit(NUM_DIGITS - 1),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::DecodeMode.addr(),
+                0x00,
+                Register::DecodeMode.addr(),
+                0x00,
+            ]),
+            Transaction::transaction_end(),
+        ];
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+
+        matrix.driver().set_scan_limit_all(4).unwrap();
+        matrix.verify_matrix_config().unwrap();
+        spi.done();
+    }
+
     #[test]
     fn test_from_spi_invalid_count() {
         let mut spi = SpiMock::new(&[]);
@@ -461,6 +1874,27 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_buffer_size_mismatch() {
+        let mut spi = SpiMock::new(&[]);
+
+        // Buffer too small for 4 devices
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let result = LedMatrix::<_, 64, 4>::from_driver(driver);
+        assert!(matches!(result, Err(Error::BufferSizeMismatch)));
+
+        // Buffer larger than the devices can show
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let result = LedMatrix::<_, 256, 2>::from_driver_with_init(driver, true);
+        assert!(matches!(result, Err(Error::BufferSizeMismatch)));
+
+        // Rejected before init() sends anything
+        let result = LedMatrix::<_, 64, 4>::from_spi(&mut spi);
+        assert!(matches!(result, Err(Error::BufferSizeMismatch)));
+
+        spi.done();
+    }
+
     #[test]
     fn test_clear() {
         let mut expected_transactions = vec![];
@@ -530,6 +1964,64 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_write_buffer_all() {
+        let buffer = MatrixBuffer::from_data([0x18, 0x3C, 0x7E, 0xFF, 0xFF, 0x7E, 0x3C, 0x18]);
+
+        let mut expected_transactions = Vec::new();
+        for (digit_register, &data) in Register::digits().zip(buffer.data()) {
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(
+                [digit_register.addr(), data].repeat(4),
+            ));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        matrix.write_buffer_all(&buffer).unwrap();
+        for device_index in 0..4 {
+            let block = &matrix.framebuffer[device_index * 64..][..64];
+            assert_eq!(&block[3 * 8..4 * 8], &[1; 8]);
+            assert_eq!(&block[..8], &[0, 0, 0, 1, 1, 0, 0, 0]);
+        }
+        spi.done();
+    }
+
+    #[test]
+    fn test_draw_symbol_all_with_rotated_device() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+        matrix
+            .set_pixel_layout(1, PixelLayout::ColumnMajor)
+            .unwrap();
+
+        let column_major = matrix.chain_index(1);
+
+        let corner = MatrixBuffer::from_data([0x80, 0, 0, 0, 0, 0, 0, 0xFF]);
+        matrix.draw_symbol_all(&corner).unwrap();
+
+        let transposed = PixelLayout::ColumnMajor.apply(*corner.data());
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 8);
+        for ((row, frame), digit_register) in frames.iter().enumerate().zip(Register::digits()) {
+            let expected: Vec<_> = (0..2)
+                .map(|chain_index| {
+                    let rows = if chain_index == column_major {
+                        transposed
+                    } else {
+                        *corner.data()
+                    };
+                    (chain_index, digit_register, rows[row])
+                })
+                .collect();
+            assert_eq!(*frame, expected);
+        }
+    }
+
     #[test]
     fn test_write_buffer_invalid_index() {
         let mut spi = SpiMock::new(&[]); // No SPI calls expected
@@ -539,7 +2031,10 @@ mod tests {
 
         let result = matrix.write_buffer(1, &buffer); // Index 1 is invalid for device_count=1
         // This error comes from Max7219::write_raw_digit via write_device_register
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 1, count: 1 })
+        );
         spi.done();
     }
 
@@ -568,6 +2063,78 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_rotation_consistent_across_draw_paths() {
+        let bitmap = STANDARD_LED_FONT.get_char('F');
+        let rotated = Rotation::Cw180.apply(bitmap);
+        assert_ne!(rotated, bitmap);
+        let frame: Vec<_> = Register::digits()
+            .zip(rotated)
+            .flat_map(|(digit_register, data)| write_reg(digit_register.addr(), data))
+            .collect();
+
+        // draw_char, draw_text and framebuffer + flush must all send the same rows
+        let mut expected_transactions = frame.clone();
+        expected_transactions.extend(frame.clone());
+        expected_transactions.extend(write_reg(Register::Shutdown.addr(), 0x01));
+        expected_transactions.extend(frame);
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver)
+            .unwrap()
+            .with_rotation(Rotation::Cw180);
+        assert_eq!(matrix.rotation(), Rotation::Cw180);
+
+        matrix.draw_char(0, 'F').unwrap();
+        matrix.draw_text("F").unwrap();
+
+        for (row, bits) in bitmap.iter().enumerate() {
+            for col in 0..8 {
+                matrix.framebuffer[row * 8 + col] = (bits >> (7 - col)) & 1;
+            }
+        }
+        matrix.flush().unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_flush_column_major() {
+        // An L: the left column and the bottom row
+        const L: [u8; 8] = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xFF];
+        let mut matrix = FakeMatrix::<128, 2>::new().unwrap();
+        matrix
+            .set_pixel_layout(1, PixelLayout::ColumnMajor)
+            .unwrap();
+        assert_eq!(matrix.pixel_layout(1), PixelLayout::ColumnMajor);
+        assert_eq!(matrix.pixel_layout(0), PixelLayout::RowMajor);
+
+        matrix.blit_rows(0, &L);
+        matrix.blit_rows(1, &L);
+        matrix.flush().unwrap();
+        assert_eq!(matrix.frame().device_rows(0), Some(L));
+        // Digit 0 is the left column, all lit; the others only light the bottom
+        assert_eq!(
+            matrix.frame().device_rows(1),
+            Some([0xFF, 1, 1, 1, 1, 1, 1, 1])
+        );
+
+        // Rotation comes first: the L turned 90 degrees, then transposed
+        matrix.set_rotation(Rotation::Cw90);
+        matrix.flush().unwrap();
+        let rotated = Rotation::Cw90.apply(L);
+        assert_eq!(matrix.frame().device_rows(0), Some(rotated));
+        assert_eq!(
+            matrix.frame().device_rows(1),
+            Some(PixelLayout::ColumnMajor.apply(rotated))
+        );
+
+        assert_eq!(
+            matrix.set_pixel_layout(2, PixelLayout::ColumnMajor),
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
+    }
+
     #[test]
     fn test_draw_char_with_font() {
         let device_index = 0;
@@ -595,6 +2162,62 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    #[cfg(feature = "symbols-basic")]
+    fn test_draw_symbol_custom_glyph() {
+        struct Logo;
+
+        impl Glyph for Logo {
+            fn to_buffer(&self) -> MatrixBuffer {
+                MatrixBuffer::from_data([0x18, 0x3C, 0x7E, 0xFF, 0xFF, 0x7E, 0x3C, 0x18])
+            }
+        }
+
+        let mut expected_transactions = Vec::new();
+        for (row, &data) in Logo.to_buffer().data().iter().enumerate() {
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(vec![
+                Register::try_digit(row as u8).unwrap().addr(),
+                data,
+            ]));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+        for (row, &data) in Symbol::Heart.to_buffer().data().iter().enumerate() {
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(vec![
+                Register::try_digit(row as u8).unwrap().addr(),
+                data,
+            ]));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver).expect("valid initialization");
+
+        matrix.draw_symbol(0, Logo).expect("custom glyph");
+        matrix.draw_symbol(0, Symbol::Heart).expect("symbol glyph");
+        spi.done();
+    }
+
+    #[test]
+    fn test_draw_clock_face() {
+        let face = crate::led_matrix::clock::clock_face(3, 0).unwrap();
+        let expected_transactions: Vec<_> = Register::digits()
+            .zip(*face.data())
+            .flat_map(|(digit_register, data)| write_reg(digit_register.addr(), data))
+            .collect();
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+
+        matrix.draw_clock_face(0, 3, 0).unwrap();
+        assert_eq!(matrix.draw_clock_face(0, 3, 75), Err(Error::InvalidTime));
+        assert_eq!(matrix.draw_clock_face(0, 24, 0), Err(Error::InvalidTime));
+        spi.done();
+    }
+
     #[test]
     fn test_draw_char_invalid_index() {
         let mut spi = SpiMock::new(&[]); // No SPI calls expected
@@ -603,7 +2226,10 @@ mod tests {
 
         let result = matrix.draw_char(1, 'A'); // Index 1 is invalid for device_count=1
         // This error comes from Max7219::write_raw_digit via write_device_register
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 1, count: 1 })
+        );
         spi.done();
     }
 
@@ -633,6 +2259,24 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_draw_text_longer_than_chain() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        matrix.draw_text("ABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap();
+
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 8);
+        assert!(frames.iter().all(|frame| frame.len() == 4));
+        let last: Vec<u8> = frames
+            .iter()
+            .map(|frame| frame.iter().find(|write| write.0 == 3).unwrap().2)
+            .collect();
+        assert_eq!(last, STANDARD_LED_FONT.get_char('D'));
+    }
+
     #[test]
     fn test_draw_text_multi_device() {
         let device_count = 4;
@@ -667,15 +2311,155 @@ mod tests {
             expected_transactions.push(Transaction::transaction_end());
         }
 
-        let mut spi = SpiMock::new(&expected_transactions);
-        let driver = Max7219::new(&mut spi)
-            .with_device_count(device_count)
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi)
+            .with_device_count(device_count)
+            .unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        let result = matrix.draw_text(text);
+        assert!(result.is_ok());
+        spi.done();
+    }
+
+    #[test]
+    #[cfg(feature = "font-ascii-full")]
+    fn test_draw_text_styled() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+        matrix.set_text_style(TextStyle::Outline);
+        assert_eq!(matrix.text_style(), TextStyle::Outline);
+
+        matrix.draw_text("O").unwrap();
+
+        // Rows of an outlined 'O'; the second device stays blank
+        let expected = [
+            0b01100110, 0b11011011, 0b10111101, 0b10100101, 0b10111101, 0b11011011, 0b01100110,
+            0b00111100,
+        ];
+        let writes = recorder.writes();
+        let first: Vec<u8> = writes
+            .iter()
+            .filter(|&&(device, _, _)| device == 0)
+            .map(|&(_, _, data)| data)
+            .collect();
+        assert_eq!(first, expected);
+        assert!(
+            writes
+                .iter()
+                .filter(|&&(device, _, _)| device == 1)
+                .all(|&(_, _, data)| data == 0)
+        );
+    }
+
+    #[test]
+    fn test_full_chain_edge_indices() {
+        // The largest chain, reversed, styled, with a pinned device: every index
+        // of draw_text and flush at its extreme
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(8).unwrap();
+        let mut matrix = Matrix8::from_driver(driver).unwrap();
+        matrix.set_orientation(Orientation {
+            reversed: true,
+            ..Orientation::default()
+        });
+        let style = TextStyle::Shadow { dx: 1, dy: 1 };
+        matrix.set_text_style(style);
+
+        matrix.write_column(63, 0x81).unwrap();
+        assert_eq!(matrix.get_column(63), Ok(0x81));
+        assert!(matrix.write_column(64, 0xFF).is_err());
+        assert!(matrix.get_column(64).is_err());
+        assert!(matrix.set_gray_pixel(63, 8, GrayLevel::Full).is_err());
+        assert!(
+            matrix
+                .set_pixel_layout(8, PixelLayout::ColumnMajor)
+                .is_err()
+        );
+        assert_eq!(matrix.pixel_layout(8), PixelLayout::RowMajor);
+        assert!(!matrix.is_blanked(8));
+        assert!(matrix.pin_value(8, 1).is_err());
+
+        matrix.pin_value(7, 42).unwrap();
+        let pinned = matrix.value_rows(42).unwrap();
+        matrix.draw_text("ABCDEFGHIJKL").unwrap();
+
+        let glyphs: Vec<[u8; 8]> = "ABCDEFGH"
+            .chars()
+            .map(|ch| STANDARD_LED_FONT.get_char(ch))
+            .collect();
+        let text_frames = &recorder.frames()[recorder.frames().len() - 8..];
+        for (row, frame) in text_frames.iter().enumerate() {
+            assert_eq!(frame.len(), 8);
+            for &(device, register, data) in frame {
+                assert_eq!(register.digit_index(), Some(row as u8));
+                // Reversed: text index i is on chain device 7 - i
+                let expected = if device == 0 {
+                    pinned
+                } else {
+                    style.render(&glyphs, 7 - device)
+                };
+                assert_eq!(data, expected[row], "device {device} row {row}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_text_empty_clears_devices() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        matrix.draw_text("").unwrap();
+
+        let writes = recorder.writes();
+        assert_eq!(writes.len(), 32);
+        assert!(writes.iter().all(|&(_, _, data)| data == 0));
+    }
+
+    #[test]
+    fn test_draw_text_skips_combining_marks() {
+        let mut precomposed = FrameRecorder::new();
+        let driver = Max7219::new(&mut precomposed).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix.draw_text("cafe").unwrap();
+
+        let mut combining = FrameRecorder::new();
+        let driver = Max7219::new(&mut combining).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix.draw_text("cafe\u{301}").unwrap();
+        assert_eq!(combining.frames(), precomposed.frames());
+
+        // Emoji are drawn as the fallback glyph, once per visible code point
+        let mut emoji = FrameRecorder::new();
+        let driver = Max7219::new(&mut emoji).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix
+            .draw_text("\u{1F469}\u{200D}\u{1F467}\u{FE0F}!")
             .unwrap();
+        let fallback = STANDARD_LED_FONT.get_char('\u{1F469}');
+        let bang = STANDARD_LED_FONT.get_char('!');
+        for (row, frame) in emoji.frames().iter().enumerate() {
+            let data: Vec<u8> = frame.iter().map(|&(_, _, data)| data).collect();
+            assert_eq!(data, [fallback[row], fallback[row], bang[row], 0]);
+        }
+    }
+
+    #[test]
+    fn test_scroll_empty_text_finishes_immediately() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
         let mut matrix = Matrix4::from_driver(driver).unwrap();
 
-        let result = matrix.draw_text(text);
-        assert!(result.is_ok());
-        spi.done();
+        let config = ScrollConfig {
+            loop_text: false,
+            ..ScrollConfig::default()
+        };
+        let mut delay = CountingDelay::default();
+        matrix.scroll_text(&mut delay, "", config).unwrap();
+        assert_eq!(delay.calls, 0);
+        assert!(recorder.writes().iter().all(|&(_, _, data)| data == 0));
     }
 
     #[test]
@@ -717,6 +2501,236 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_dimensions() {
+        let single: FakeMatrix = FakeMatrix::new().unwrap();
+        assert_eq!(single.device_count(), 1);
+        assert_eq!(single.width_pixels(), 8);
+        assert_eq!(single.height_pixels(), 8);
+
+        let four = FakeMatrix::<256, 4>::new().unwrap();
+        assert_eq!(four.device_count(), 4);
+        assert_eq!(four.width_pixels(), 32);
+
+        let eight = FakeMatrix::<512, 8>::new().unwrap();
+        assert_eq!(eight.device_count(), 8);
+        assert_eq!(eight.width_pixels(), 64);
+        assert_eq!(eight.height_pixels(), 8);
+    }
+
+    #[test]
+    fn test_default_font() {
+        static TEST_FONT: LedFont = LedFont::new(&[([0b1010_1010; 8], 'A')]);
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        assert_eq!(
+            matrix.default_font().get_char('A'),
+            STANDARD_LED_FONT.get_char('A')
+        );
+
+        matrix.draw_text("A").unwrap();
+        matrix.set_default_font(&TEST_FONT);
+        matrix.draw_text("A").unwrap();
+        matrix.draw_char(0, 'A').unwrap();
+        // Explicit fonts are unaffected
+        matrix.draw_text_with_font("A", &STANDARD_LED_FONT).unwrap();
+
+        let rows: Vec<u8> = recorder
+            .writes()
+            .iter()
+            .filter(|&&(_, register, _)| register.is_digit())
+            .map(|&(_, _, data)| data)
+            .collect();
+        assert_eq!(rows[..8], STANDARD_LED_FONT.get_char('A'));
+        assert_eq!(rows[8..24], [0b1010_1010; 16]);
+        assert_eq!(rows[24..], STANDARD_LED_FONT.get_char('A'));
+    }
+
+    #[test]
+    fn test_cursor_xor_at_flush() {
+        let mut fake: FakeMatrix<256, 4> = FakeMatrix::new().unwrap();
+        fake.write_column(8, 0xFF).unwrap();
+        fake.set_cursor(Some(CursorStyle::Underline), 1).unwrap();
+        fake.flush().unwrap();
+
+        // Cell 1 is framebuffer block 1, shown on device 1
+        let mut underlined = [0x80; 8];
+        underlined[7] = 0x7F;
+        assert_eq!(fake.frame().device_rows(1), Some(underlined));
+        assert_eq!(fake.get_column(8), Ok(0xFF));
+
+        fake.blink_cursor_tick().unwrap();
+        assert_eq!(fake.frame().device_rows(1), Some([0x80; 8]));
+        fake.blink_cursor_tick().unwrap();
+        assert_eq!(fake.frame().device_rows(1), Some(underlined));
+
+        // Removing the cursor restores the content under it
+        fake.set_cursor(None, 0).unwrap();
+        fake.flush().unwrap();
+        assert_eq!(fake.cursor(), None);
+        assert_eq!(fake.frame().device_rows(1), Some([0x80; 8]));
+
+        fake.set_cursor(Some(CursorStyle::Frame), 0).unwrap();
+        fake.flush().unwrap();
+        assert_eq!(
+            fake.frame().device_rows(0),
+            Some([0xFF, 0x81, 0x81, 0x81, 0x81, 0x81, 0x81, 0xFF])
+        );
+        assert_eq!(fake.frame().device_rows(1), Some([0x80; 8]));
+    }
+
+    #[test]
+    fn test_cursor_rejects_missing_cell() {
+        let mut fake: FakeMatrix<256, 4> = FakeMatrix::new().unwrap();
+        fake.set_cursor(Some(CursorStyle::Block), 3).unwrap();
+        assert_eq!(
+            fake.set_cursor(Some(CursorStyle::Block), 4),
+            Err(Error::OutOfBounds { x: 32, y: 0 })
+        );
+        assert_eq!(fake.cursor(), Some((CursorStyle::Block, 3)));
+
+        fake.flush().unwrap();
+        assert_eq!(fake.frame().device_rows(3), Some([0xFF; 8]));
+    }
+
+    #[test]
+    fn test_chain_order() {
+        let mut recorders = [FrameRecorder::new(), FrameRecorder::new()];
+        for (recorder, order) in recorders
+            .iter_mut()
+            .zip([ChainOrder::FarthestFirst, ChainOrder::NearestFirst])
+        {
+            let driver = Max7219::new(recorder)
+                .with_device_count(2)
+                .unwrap()
+                .with_chain_order(order);
+            let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+            matrix.power_on().unwrap();
+            matrix.draw_text("AB").unwrap();
+            matrix.write_column(0, 0xFF).unwrap();
+            matrix.flush().unwrap();
+        }
+        let [farthest, nearest] = recorders.map(|recorder| recorder.frames().to_vec());
+
+        // Text and framebuffer follow the device index, so both land mirrored
+        let a = STANDARD_LED_FONT.get_char('A');
+        assert_eq!(farthest[1][0], (0, Register::Digit0, a[0]));
+        assert_eq!(nearest[1][1], (1, Register::Digit0, a[0]));
+        assert_eq!(
+            farthest[9],
+            [(0, Register::Digit0, 0x80), (1, Register::Digit0, 0)]
+        );
+        assert_eq!(
+            nearest[9],
+            [(0, Register::Digit0, 0), (1, Register::Digit0, 0x80)]
+        );
+        assert_eq!(farthest.len(), nearest.len());
+        for (far, near) in farthest[1..].iter().zip(&nearest[1..]) {
+            assert_eq!(far[0].2, near[1].2);
+            assert_eq!(far[1].2, near[0].2);
+        }
+    }
+
+    #[test]
+    fn test_flush_agrees_with_draw_text() {
+        for order in [ChainOrder::FarthestFirst, ChainOrder::NearestFirst] {
+            let mut recorders = [FrameRecorder::new(), FrameRecorder::new()];
+            let [text, flushed] = &mut recorders;
+            let matrix = |recorder| {
+                let driver = Max7219::new(recorder)
+                    .with_device_count(2)
+                    .unwrap()
+                    .with_chain_order(order);
+                let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+                matrix.power_on().unwrap();
+                matrix
+            };
+
+            matrix(text).draw_text("AB").unwrap();
+            let mut framebuffer = matrix(flushed);
+            framebuffer.blit_rows(0, &STANDARD_LED_FONT.get_char('A'));
+            framebuffer.blit_rows(1, &STANDARD_LED_FONT.get_char('B'));
+            framebuffer.flush().unwrap();
+
+            // Framebuffer block i lands where draw_text puts character i
+            let [text, flushed] = recorders.map(|recorder| recorder.frames().to_vec());
+            assert_eq!(text.len(), 9);
+            assert_eq!(text, flushed, "{order:?}");
+        }
+    }
+
+    #[test]
+    fn test_pin_value_survives_draw_text_and_flush() {
+        let mut fake: FakeMatrix<256, 4> = FakeMatrix::new().unwrap();
+        fake.pin_value(3, 9).unwrap();
+        fake.draw_text("ABCD").unwrap();
+
+        let nine = STANDARD_LED_FONT.get_char('9');
+        let frame = fake.frame();
+        assert_eq!(frame.device_rows(0), Some(STANDARD_LED_FONT.get_char('A')));
+        assert_eq!(frame.device_rows(2), Some(STANDARD_LED_FONT.get_char('C')));
+        assert_eq!(frame.device_rows(3), Some(nine));
+
+        fake.clear_buffer();
+        fake.flush().unwrap();
+        assert_eq!(fake.frame().device_rows(0), Some([0; 8]));
+        assert_eq!(fake.frame().device_rows(3), Some(nine));
+
+        fake.pin_value(3, 10).unwrap();
+        let ten = [
+            0,
+            0b0010_0111,
+            0b0110_0101,
+            0b0010_0101,
+            0b0010_0101,
+            0b0111_0111,
+            0,
+            0,
+        ];
+        assert_eq!(fake.frame().device_rows(3), Some(ten));
+
+        fake.unpin_value(3).unwrap();
+        fake.draw_text("ABCD").unwrap();
+        assert_eq!(
+            fake.frame().device_rows(3),
+            Some(STANDARD_LED_FONT.get_char('D'))
+        );
+    }
+
+    #[test]
+    fn test_pin_value_writes_only_its_device() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        matrix.pin_value(3, -5).unwrap();
+        assert_eq!(
+            matrix.pin_value(4, 1),
+            Err(Error::InvalidDeviceIndex { index: 4, count: 4 })
+        );
+        assert_eq!(matrix.pin_value(0, 100), Err(Error::ValueOutOfRange));
+        assert_eq!(matrix.pin_value(0, -10), Err(Error::ValueOutOfRange));
+
+        let writes = recorder.writes();
+        assert_eq!(writes.len(), 8);
+        assert!(writes.iter().all(|&(device_index, _, _)| device_index == 3));
+        let rows: Vec<u8> = writes.iter().map(|&(_, _, data)| data).collect();
+        assert_eq!(
+            rows,
+            [
+                0,
+                0b0000_0111,
+                0b0000_0100,
+                0b0111_0111,
+                0b0000_0001,
+                0b0000_0111,
+                0,
+                0
+            ]
+        );
+    }
+
     #[test]
     fn test_clear_buffer() {
         let mut spi = SpiMock::new(&[]); // No SPI interaction
@@ -737,8 +2751,9 @@ mod tests {
 
     #[test]
     fn test_clear_screen() {
-        // All digits 0..7 will be written with 0x00 for a single device
-        let mut expected_transactions = Vec::new();
+        // All digits 0..7 will be written with 0x00 for a single device,
+        // after the default power policy switches the fresh driver on
+        let mut expected_transactions = write_reg(Register::Shutdown.addr(), 0x01);
         for row in 0..8 {
             let digit_register = Register::try_digit(row).unwrap();
             expected_transactions.push(Transaction::transaction_start());
@@ -767,8 +2782,9 @@ mod tests {
         // We expect the flush to send 8 SPI transactions, one for each row (DIGIT0 to DIGIT7)
         // Only rows 0 and 7 have pixel data: 0b10101010 (columns 0,2,4,6 lit)
         // All other rows should be cleared (0b00000000)
+        // The driver was never powered on, so flush starts with a Shutdown write
 
-        let mut expected_transactions = Vec::new();
+        let mut expected_transactions = write_reg(Register::Shutdown.addr(), 0x01);
         for (row, digit_register) in Register::digits().enumerate() {
             // For rows 0 and 7, the framebuffer will result in this pattern:
             // Columns 0, 2, 4, 6 are ON => bits 7, 5, 3, 1 set => 0b10101010
@@ -825,6 +2841,30 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_write_frame_matches_flush() {
+        let blocks: [[u8; 8]; 4] =
+            core::array::from_fn(|block| core::array::from_fn(|row| (0x80 >> block) | (1 << row)));
+
+        let mut flushed = FrameRecorder::new();
+        let driver = Max7219::new(&mut flushed).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix.power_on().unwrap();
+        for (block, rows) in blocks.iter().enumerate() {
+            matrix.blit_rows(block, rows);
+        }
+        matrix.flush().unwrap();
+
+        // flush shows framebuffer block i on device i
+        let mut written = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut written).with_device_count(4).unwrap();
+        driver.power_on().unwrap();
+        driver.write_frame(&blocks).unwrap();
+
+        assert_eq!(flushed.frames().len(), 9);
+        assert_eq!(flushed.frames(), written.frames());
+    }
+
     #[test]
     fn test_driver_mut_access() {
         let expected_transactions = [
@@ -841,12 +2881,624 @@ mod tests {
         driver.power_on().expect("Power on should succeed");
         spi.done();
     }
+
+    fn blank_frame() -> Vec<Transaction<u8>> {
+        Register::digits()
+            .flat_map(|digit_register| write_reg(digit_register.addr(), 0x00))
+            .collect()
+    }
+
+    #[test]
+    fn test_power_on_off() {
+        let mut expected_transactions = write_reg(Register::Shutdown.addr(), 0x01);
+        expected_transactions.extend(write_reg(Register::Shutdown.addr(), 0x00));
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+
+        assert!(!matrix.is_powered());
+        matrix.power_on().unwrap();
+        assert!(matrix.is_powered());
+        matrix.power_off().unwrap();
+        assert!(!matrix.is_powered());
+        spi.done();
+    }
+
+    #[test]
+    fn test_flush_auto_power_on() {
+        let mut expected_transactions = write_reg(Register::Shutdown.addr(), 0x01);
+        expected_transactions.extend(write_reg(Register::Shutdown.addr(), 0x00));
+        expected_transactions.extend(write_reg(Register::Shutdown.addr(), 0x01));
+        expected_transactions.extend(blank_frame());
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        assert_eq!(matrix.power_policy(), PowerPolicy::AutoPowerOn);
+
+        matrix.power_on().unwrap();
+        matrix.driver().power_off().unwrap();
+        matrix.flush().unwrap();
+        assert!(matrix.is_powered());
+        spi.done();
+    }
+
+    #[test]
+    fn test_flush_powered_off_error() {
+        let expected_transactions = write_reg(Register::Shutdown.addr(), 0x00);
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver)
+            .unwrap()
+            .with_power_policy(PowerPolicy::Error);
+
+        matrix.power_off().unwrap();
+        assert_eq!(matrix.flush(), Err(Error::PoweredOff));
+        spi.done();
+    }
+
+    #[test]
+    fn test_blank_device_preserves_framebuffer() {
+        let frame = |bytes: [u8; 4]| {
+            vec![
+                Transaction::transaction_start(),
+                Transaction::write_vec(bytes.to_vec()),
+                Transaction::transaction_end(),
+            ]
+        };
+
+        let mut expected_transactions = frame([
+            Register::Shutdown.addr(),
+            0x01,
+            Register::Shutdown.addr(),
+            0x01,
+        ]);
+        // blank_device(1, true): zeros to device 1 only
+        for digit_register in Register::digits() {
+            expected_transactions.extend(frame([0x00, 0x00, digit_register.addr(), 0x00]));
+        }
+        // flush: device 1 keeps getting no-ops
+        for digit_register in Register::digits() {
+            let data = if digit_register == Register::Digit0 {
+                0x81
+            } else {
+                0x00
+            };
+            expected_transactions.extend(frame([digit_register.addr(), data, 0x00, 0x00]));
+        }
+        // blank_device(1, false): rows restored from the framebuffer
+        for digit_register in Register::digits() {
+            let data = if digit_register == Register::Digit0 {
+                0x80
+            } else {
+                0x00
+            };
+            expected_transactions.extend(frame([0x00, 0x00, digit_register.addr(), data]));
+        }
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+        matrix.power_on().unwrap();
+
+        matrix.blank_device(1, true).unwrap();
+        assert!(matrix.is_blanked(1));
+        assert!(!matrix.is_blanked(0));
+
+        // Framebuffer block 0 is shown on device 0, block 1 on device 1
+        matrix.framebuffer[0] = 1;
+        matrix.framebuffer[7] = 1;
+        matrix.framebuffer[64] = 1;
+        matrix.flush().unwrap();
+
+        matrix.blank_device(1, false).unwrap();
+        assert!(!matrix.is_blanked(1));
+
+        assert_eq!(
+            matrix.blank_device(2, true),
+            Err(Error::InvalidDeviceIndex { index: 2, count: 2 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_column_across_devices() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+
+        // Last column of device 0 and first column of device 1
+        matrix.write_column(7, 0b1000_0001).unwrap();
+        matrix.write_column(8, 0b0000_0110).unwrap();
+
+        assert_eq!(matrix.framebuffer[7], 1);
+        assert_eq!(matrix.framebuffer[7 * 8 + 7], 1);
+        for row in 1..7 {
+            assert_eq!(matrix.framebuffer[row * 8 + 7], 0);
+        }
+        assert_eq!(matrix.framebuffer[64], 0);
+        assert_eq!(matrix.framebuffer[64 + 8], 1);
+        assert_eq!(matrix.framebuffer[64 + 16], 1);
+        assert_eq!(matrix.framebuffer[64 + 24], 0);
+        assert_eq!(matrix.framebuffer.iter().filter(|&&v| v != 0).count(), 4);
+
+        assert_eq!(matrix.get_column(7), Ok(0b1000_0001));
+        assert_eq!(matrix.get_column(8), Ok(0b0000_0110));
+        assert_eq!(matrix.get_column(6), Ok(0));
+
+        assert_eq!(
+            matrix.write_column(16, 0xFF),
+            Err(Error::OutOfBounds { x: 16, y: 0 })
+        );
+        assert_eq!(
+            matrix.get_column(16),
+            Err(Error::OutOfBounds { x: 16, y: 0 })
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_mirrored_copy() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(8).unwrap();
+        let mut matrix = Matrix8::from_driver(driver).unwrap();
+        for x in 0..32 {
+            matrix.write_column(x, x as u8 + 1).unwrap();
+        }
+
+        matrix.mirrored_copy(0..32, 32..64).unwrap();
+        for k in 0..32 {
+            assert_eq!(matrix.get_column(32 + k), Ok(32 - k as u8));
+            assert_eq!(matrix.get_column(k), Ok(k as u8 + 1));
+        }
+
+        // Ranges need not be block aligned
+        matrix.mirrored_copy(1..4, 60..63).unwrap();
+        assert_eq!(matrix.get_column(59), Ok(5));
+        assert_eq!(matrix.get_column(60), Ok(4));
+        assert_eq!(matrix.get_column(61), Ok(3));
+        assert_eq!(matrix.get_column(62), Ok(2));
+        assert_eq!(matrix.get_column(63), Ok(1));
+        spi.done();
+    }
+
+    #[test]
+    fn test_mirrored_copy_rejects_bad_ranges() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(8).unwrap();
+        let mut matrix = Matrix8::from_driver(driver).unwrap();
+        matrix.write_column(0, 0xFF).unwrap();
+
+        assert_eq!(
+            matrix.mirrored_copy(0..32, 16..48),
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            matrix.mirrored_copy(0..32, 32..48),
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            matrix.mirrored_copy(0..8, 60..68),
+            Err(Error::OutOfBounds { x: 67, y: 0 })
+        );
+        assert!(matrix.framebuffer[64..].iter().all(|&v| v == 0));
+        spi.done();
+    }
+
+    /// Delay that counts how often it was asked to wait.
+    #[derive(Default)]
+    struct CountingDelay {
+        calls: usize,
+    }
+
+    impl embedded_hal::delay::DelayNs for CountingDelay {
+        fn delay_ns(&mut self, _ns: u32) {
+            self.calls += 1;
+        }
+    }
+
+    /// Expected `write_buffer` traffic for one device of a `device_count` chain.
+    fn device_rows(
+        device_index: usize,
+        device_count: usize,
+        rows: &[u8; 8],
+    ) -> Vec<Transaction<u8>> {
+        let mut transactions = Vec::new();
+        for (digit_register, &data) in Register::digits().zip(rows) {
+            let mut frame = vec![0u8; device_count * 2];
+            frame[device_index * 2] = digit_register.addr();
+            frame[device_index * 2 + 1] = data;
+            transactions.push(Transaction::transaction_start());
+            transactions.push(Transaction::write_vec(frame));
+            transactions.push(Transaction::transaction_end());
+        }
+        transactions
+    }
+
+    #[test]
+    fn test_into_driver_and_rebuild() {
+        let mut expected_transactions = write_reg(Register::Digit0.addr(), 0x42);
+        expected_transactions.extend(write_reg(Register::Digit0.addr(), 0x24));
+        let spi = SpiMock::new(&expected_transactions);
+
+        let driver = Max7219::new(spi);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        matrix.driver().write_raw_digit(0, 0, 0x42).unwrap();
+        let spi = matrix.into_driver().release();
+
+        let mut matrix = SingleMatrix::from_driver(Max7219::new(spi)).unwrap();
+        matrix.driver().write_raw_digit(0, 0, 0x24).unwrap();
+        let mut spi = matrix.into_driver().release();
+        spi.done();
+    }
+
+    #[test]
+    fn test_flush_with_settle_delay() {
+        let mut recorder = FrameRecorder::new();
+        let mut delay = CountingDelay::default();
+        let driver = Max7219::new(&mut recorder)
+            .with_device_count(2)
+            .unwrap()
+            .attach_delay(&mut delay);
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+
+        // No settle time configured: power on and rows go out without waits
+        matrix.flush().unwrap();
+        matrix.driver().set_inter_write_delay_us(4);
+        matrix.write_column(3, 0xFF).unwrap();
+        matrix.flush().unwrap();
+
+        // One wait after each row of the second flush
+        assert_eq!(recorder.frames().len(), 1 + 8 + 8);
+        assert_eq!(delay.calls, 8);
+    }
+
+    #[test]
+    fn test_show_message_fits() {
+        // One glyph centered on two devices: four pixels of margin on each side
+        let a = STANDARD_LED_FONT.get_char('A');
+        let left = a.map(|row| row >> 4);
+        let right = a.map(|row| row << 4);
+        let mut expected_transactions = device_rows(0, 2, &left);
+        expected_transactions.extend(device_rows(1, 2, &right));
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+
+        let mut delay = CountingDelay::default();
+        matrix
+            .show_message(&mut delay, "A", &STANDARD_LED_FONT, ScrollConfig::default())
+            .unwrap();
+        assert_eq!(delay.calls, 0);
+        spi.done();
+    }
+
+    #[test]
+    fn test_optimize_scan_limit() {
+        let scan_limit = |limit: u8| {
+            vec![
+                (0, Register::ScanLimit, limit - 1),
+                (1, Register::ScanLimit, limit - 1),
+            ]
+        };
+
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+        matrix.optimize_scan_limit(true).unwrap();
+
+        // Top row only, then content two rows further down
+        matrix.framebuffer[3] = 1;
+        matrix.flush().unwrap();
+        matrix.framebuffer[64 + 2 * 8] = 1;
+        matrix.flush().unwrap();
+        // Unchanged height, then empty
+        matrix.flush().unwrap();
+        matrix.clear_buffer();
+        matrix.flush().unwrap();
+        matrix.optimize_scan_limit(false).unwrap();
+        matrix.flush().unwrap();
+
+        let limits: Vec<_> = recorder
+            .frames()
+            .iter()
+            .filter(|frame| frame[0].1 == Register::ScanLimit)
+            .cloned()
+            .collect();
+        assert_eq!(
+            limits,
+            [scan_limit(1), scan_limit(3), scan_limit(1), scan_limit(8)]
+        );
+
+        // The limit goes out before the rows of the same flush, right after the
+        // first flush powers the chain on
+        let frames = recorder.frames();
+        assert_eq!(frames[1], scan_limit(1));
+        assert_eq!(frames[10], scan_limit(3));
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_flush_stats() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+
+        // The first flush powers the chain on
+        matrix.flush().unwrap();
+        let stats = matrix.flush_stats();
+        assert_eq!(
+            (stats.transactions, stats.bytes, stats.rows_skipped),
+            (9, 36, 0)
+        );
+
+        // One blanked device still needs every row for the other one
+        matrix.reset_stats();
+        matrix.blank_device(0, true).unwrap();
+        matrix.flush().unwrap();
+        assert_eq!(
+            matrix.flush_stats(),
+            super::FlushStats {
+                transactions: 8,
+                bytes: 32,
+                rows_skipped: 0,
+            }
+        );
+
+        // With every device blanked nothing is sent
+        matrix.reset_stats();
+        matrix.blank_device(1, true).unwrap();
+        matrix.flush().unwrap();
+        assert_eq!(
+            matrix.flush_stats(),
+            super::FlushStats {
+                transactions: 0,
+                bytes: 0,
+                rows_skipped: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fits() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+
+        assert!(matrix.fits("", &STANDARD_LED_FONT));
+        assert!(matrix.fits("AB", &STANDARD_LED_FONT));
+        assert!(!matrix.fits("ABC", &STANDARD_LED_FONT));
+        spi.done();
+    }
+
+    #[test]
+    fn test_show_message_scrolls_once() {
+        let config = ScrollConfig::default();
+        let single_pass = ScrollConfig {
+            loop_text: false,
+            ..config
+        };
+        let mut reference = ScrollingText::new("AB", &STANDARD_LED_FONT, single_pass);
+        reference.reset();
+        let mut expected_transactions = Vec::new();
+        let mut frames = 0;
+        loop {
+            let frame = *reference.get_frame().unwrap().data();
+            expected_transactions.extend(device_rows(0, 1, &frame));
+            frames += 1;
+            if !reference.step() {
+                break;
+            }
+        }
+        // In from the right edge, across both glyphs and out again
+        assert_eq!(frames, 32);
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+
+        let mut delay = CountingDelay::default();
+        matrix
+            .show_message(&mut delay, "AB", &STANDARD_LED_FONT, config)
+            .unwrap();
+        assert_eq!(delay.calls, frames - 1);
+        spi.done();
+    }
+
+    #[test]
+    fn test_idle_hook_runs_every_scroll_step() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn count() {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let config = ScrollConfig {
+            loop_text: false,
+            ..ScrollConfig::default()
+        };
+        let mut spi = FailingSpi::default();
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        matrix.set_idle_hook(Some(count));
+
+        let mut delay = CountingDelay::default();
+        matrix.scroll_text(&mut delay, "AB", config).unwrap();
+
+        // 32 frames with a wait between each pair, and the hook before every wait
+        assert_eq!(delay.calls, 31);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 31);
+        assert_eq!(spi.writes.len(), 32 * 8);
+    }
+
+    fn intensities(recorder: &FrameRecorder) -> Vec<u8> {
+        recorder
+            .writes()
+            .iter()
+            .map(|&(_, _, level)| level)
+            .collect()
+    }
+
+    #[test]
+    fn test_intensity_gradient() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix.set_intensity_gradient(0, 15).unwrap();
+        assert_eq!(recorder.frames().len(), 1);
+        assert_eq!(intensities(&recorder), [0, 5, 10, 15]);
+
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(8).unwrap();
+        let mut matrix = Matrix8::from_driver(driver).unwrap();
+        matrix.set_intensity_gradient(0, 15).unwrap();
+        matrix.set_intensity_gradient(15, 0).unwrap();
+        matrix.set_intensity_gradient(1, 2).unwrap();
+        assert_eq!(
+            intensities(&recorder),
+            [
+                0, 2, 4, 6, 9, 11, 13, 15, //
+                15, 13, 11, 9, 6, 4, 2, 0, //
+                1, 1, 1, 1, 2, 2, 2, 2,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intensity_profile() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        matrix.set_intensity_profile(&[3, 0, 15, 7]).unwrap();
+        assert_eq!(
+            matrix.set_intensity_profile(&[3, 0, 15]),
+            Err(Error::InvalidDeviceCount)
+        );
+        assert_eq!(
+            matrix.set_intensity_profile(&[3, 0, 16, 7]),
+            Err(Error::InvalidIntensity)
+        );
+        assert_eq!(
+            matrix.set_intensity_gradient(0, 16),
+            Err(Error::InvalidIntensity)
+        );
+        assert_eq!(
+            recorder.writes(),
+            [
+                (0, Register::Intensity, 3),
+                (1, Register::Intensity, 0),
+                (2, Register::Intensity, 15),
+                (3, Register::Intensity, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_char_map_static_and_scrolled() {
+        const OMEGA: [u8; 8] = [0x00, 0x3C, 0x42, 0x42, 0x42, 0x24, 0x66, 0x00];
+        let digit_data = |recorder: &FrameRecorder| -> Vec<u8> {
+            recorder.writes().iter().map(|&(_, _, data)| data).collect()
+        };
+
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        matrix.set_char_map(&[('µ', 'u')]);
+        matrix.set_custom_glyphs(&[('Ω', OMEGA)]);
+        matrix.draw_text("Ω").unwrap();
+        matrix.draw_char(0, 'µ').unwrap();
+        let mut expected = OMEGA.to_vec();
+        expected.extend(STANDARD_LED_FONT.get_char('u'));
+        assert_eq!(digit_data(&recorder), expected);
+
+        let config = ScrollConfig {
+            loop_text: false,
+            ..ScrollConfig::default()
+        };
+        let mut scrolled = FrameRecorder::new();
+        let driver = Max7219::new(&mut scrolled);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        matrix.set_char_map(&[('µ', 'u')]);
+        matrix.set_custom_glyphs(&[('Ω', OMEGA)]);
+        matrix
+            .scroll_text(&mut CountingDelay::default(), "µΩ", config)
+            .unwrap();
+
+        let mut reference = FrameRecorder::new();
+        let driver = Max7219::new(&mut reference);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        matrix.set_custom_glyphs(&[('Ω', OMEGA)]);
+        matrix
+            .scroll_text(&mut CountingDelay::default(), "uΩ", config)
+            .unwrap();
+
+        assert_eq!(digit_data(&scrolled), digit_data(&reference));
+        // The custom glyph shows up fully aligned once it has scrolled in
+        assert!(digit_data(&scrolled).chunks(8).any(|rows| rows == OMEGA));
+    }
+
+    #[test]
+    fn test_flush_dithered() {
+        // Row 0 across four frames: x0 Low, x1 Medium, x2 Full, x4 drawn as plain "on"
+        let row0 = [0b1010_1000, 0b0010_1000, 0b0110_1000, 0b0110_1000];
+
+        let mut expected_transactions = write_reg(Register::Shutdown.addr(), 0x01);
+        for byte in row0 {
+            for digit_register in Register::digits() {
+                let data = if digit_register == Register::Digit0 {
+                    byte
+                } else {
+                    0x00
+                };
+                expected_transactions.extend(write_reg(digit_register.addr(), data));
+            }
+        }
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        matrix.power_on().unwrap();
+
+        matrix.set_gray_pixel(0, 0, GrayLevel::Low).unwrap();
+        matrix.set_gray_pixel(1, 0, GrayLevel::Medium).unwrap();
+        matrix.set_gray_pixel(2, 0, GrayLevel::Full).unwrap();
+        matrix.set_gray_pixel(3, 0, GrayLevel::Off).unwrap();
+        matrix.framebuffer[4] = 1;
+        assert_eq!(
+            matrix.set_gray_pixel(8, 0, GrayLevel::Low),
+            Err(Error::OutOfBounds { x: 8, y: 0 })
+        );
+
+        for frame_index in 0..4 {
+            matrix.flush_dithered(frame_index).unwrap();
+        }
+        spi.done();
+    }
+
+    #[test]
+    fn test_flush_powered_off_skip() {
+        let mut expected_transactions = write_reg(Register::Shutdown.addr(), 0x01);
+        expected_transactions.extend(blank_frame());
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut matrix = SingleMatrix::from_driver(driver)
+            .unwrap()
+            .with_power_policy(PowerPolicy::Skip);
+
+        matrix.framebuffer[0] = 1;
+        matrix.flush().unwrap();
+
+        matrix.clear_buffer();
+        matrix.power_on().unwrap();
+        matrix.flush().unwrap();
+        spi.done();
+    }
 }
 
 #[cfg(all(test, feature = "graphics"))]
 mod graphis_tests {
     use super::*;
-    use embedded_graphics_core::geometry::Point;
+    use embedded_graphics_core::primitives::Rectangle;
 
     use embedded_hal_mock::eh1::spi::Mock as SpiMock;
 
@@ -924,4 +3576,34 @@ mod graphis_tests {
 
         spi.done();
     }
+
+    #[test]
+    fn test_get_pixel_rectangle() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+
+        // Spans both devices: x 6..=9, y 2..=4
+        let area = Rectangle::new(Point::new(6, 2), Size::new(4, 3));
+        matrix.fill_solid(&area, BinaryColor::On).unwrap();
+
+        for corner in [
+            Point::new(6, 2),
+            Point::new(9, 2),
+            Point::new(6, 4),
+            Point::new(9, 4),
+        ] {
+            assert_eq!(matrix.pixel(corner), Some(BinaryColor::On));
+        }
+        assert_eq!(matrix.pixel(Point::new(5, 2)), Some(BinaryColor::Off));
+        assert_eq!(matrix.pixel(Point::new(10, 4)), Some(BinaryColor::Off));
+        assert_eq!(matrix.pixel(Point::new(6, 5)), Some(BinaryColor::Off));
+
+        // Out of bounds
+        assert_eq!(matrix.pixel(Point::new(16, 0)), None);
+        assert_eq!(matrix.pixel(Point::new(0, 8)), None);
+        assert_eq!(matrix.pixel(Point::new(-1, 0)), None);
+
+        spi.done();
+    }
 }
@@ -0,0 +1,255 @@
+//! Randomness for visual effects
+//!
+//! [`XorShift32`] is a tiny seedable generator, so effects such as [`noise`] need
+//! no `rand` dependency and repeat exactly for a given seed. Use the same generator
+//! in your own effects to keep them in step with the crate's visuals in tests.
+//!
+//! Effects that run on an [`LedMatrix`] draw from the matrix's own generator,
+//! which [`LedMatrix::seed_effects`] resets.
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    Result,
+    led_matrix::{LedMatrix, buffer::MatrixBuffer},
+};
+
+/// Seed used instead of `0`, which would keep the generator at zero forever.
+const FALLBACK_SEED: u32 = 0x9E37_79B9;
+
+/// Marsaglia's 32-bit xorshift generator.
+///
+/// Fast and small, with a period of 2³² − 1, but **not cryptographic**: the next
+/// values are trivial to predict from a single output. Only use it for visuals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorShift32 {
+    state: u32,
+}
+
+impl XorShift32 {
+    /// Creates a generator from `seed`.
+    ///
+    /// A seed of `0` is replaced by a fixed non-zero one, since the all-zero state
+    /// never changes.
+    pub const fn new(seed: u32) -> Self {
+        let state = if seed == 0 { FALLBACK_SEED } else { seed };
+        Self { state }
+    }
+
+    /// Returns the next 32 random bits.
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Returns `true` with a probability of `probability_num / den`.
+    ///
+    /// A numerator of at least `den` always gives `true`, and a `den` of `0`
+    /// always gives `false`.
+    pub fn next_bool(&mut self, probability_num: u32, den: u32) -> bool {
+        if den == 0 {
+            return false;
+        }
+        let scaled = (u64::from(self.next_u32()) * u64::from(den)) >> 32;
+        scaled < u64::from(probability_num)
+    }
+}
+
+impl Default for XorShift32 {
+    fn default() -> Self {
+        Self::new(FALLBACK_SEED)
+    }
+}
+
+/// Random 8x8 noise where each pixel is lit with a probability of
+/// `density_num / den`, see [`XorShift32::next_bool`].
+///
+/// Pixels are drawn row by row from the top, leftmost column first.
+pub fn noise(rng: &mut XorShift32, density_num: u32, den: u32) -> MatrixBuffer {
+    let mut rows = [0u8; 8];
+    for row in rows.iter_mut() {
+        for col in 0..8 {
+            if rng.next_bool(density_num, den) {
+                *row |= 0x80 >> col;
+            }
+        }
+    }
+    MatrixBuffer::from_data(rows)
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Restarts the random source of the built-in effects from `seed`.
+    ///
+    /// Effects repeat exactly after the same seed, which makes them testable.
+    /// Every matrix starts from [`XorShift32::default`].
+    pub fn seed_effects(&mut self, seed: u32) {
+        self.effects_rng = XorShift32::new(seed);
+    }
+
+    /// Twinkling starfield that runs while `keep_going` returns `true`.
+    ///
+    /// Each frame, every lit pixel goes out with a probability of one half and
+    /// every dark pixel lights up with a probability of `density / 255`, so
+    /// `0` fades the display out and `255` lights all of it. Pixels are visited in
+    /// framebuffer order. The frame is drawn into the framebuffer and shown with
+    /// [`Self::flush`], so the orientation applies; the idle hook, if any, runs
+    /// before waiting `frame_ms`. `keep_going` is checked before every frame.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.seed_effects(0xC0FFEE);
+    /// matrix.sparkle(&mut delay, 20, 80, || !button.is_pressed())?;
+    /// ```
+    pub fn sparkle<D: DelayNs, F: FnMut() -> bool>(
+        &mut self,
+        delay: &mut D,
+        density: u8,
+        frame_ms: u32,
+        mut keep_going: F,
+    ) -> Result<()> {
+        while keep_going() {
+            for pixel in self.framebuffer.iter_mut() {
+                let lit = *pixel != 0 && self.effects_rng.next_bool(1, 2);
+                let lit = lit || self.effects_rng.next_bool(u32::from(density), 255);
+                *pixel = u8::from(lit);
+            }
+
+            self.flush()?;
+            self.driver().idle();
+            delay.delay_ms(frame_ms);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::SingleMatrix;
+    use crate::test_util::{FakeMatrix, FrameRecorder};
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+
+    #[test]
+    fn test_known_sequence() {
+        // First outputs of the reference xorshift32 for seed 1
+        let mut rng = XorShift32::new(1);
+        assert_eq!(rng.next_u32(), 270_369);
+        assert_eq!(rng.next_u32(), 67_634_689);
+        assert_eq!(rng.next_u32(), 2_647_435_461);
+    }
+
+    #[test]
+    fn test_zero_seed_is_not_stuck() {
+        let mut rng = XorShift32::new(0);
+        assert_eq!(rng, XorShift32::default());
+        assert_ne!(rng.next_u32(), 0);
+    }
+
+    #[test]
+    fn test_period_is_not_short() {
+        let mut rng = XorShift32::new(42);
+        let start = rng.clone();
+        for _ in 0..100_000 {
+            rng.next_u32();
+            assert_ne!(rng, start);
+        }
+    }
+
+    #[test]
+    fn test_bits_are_balanced() {
+        let mut rng = XorShift32::new(7);
+        let mut ones = [0u32; 32];
+        for _ in 0..10_000 {
+            let value = rng.next_u32();
+            for (bit, count) in ones.iter_mut().enumerate() {
+                *count += (value >> bit) & 1;
+            }
+        }
+        for count in ones {
+            assert!((4_700..=5_300).contains(&count), "{count}");
+        }
+    }
+
+    #[test]
+    fn test_next_bool() {
+        let mut rng = XorShift32::new(3);
+        let hits = (0..10_000).filter(|_| rng.next_bool(1, 4)).count();
+        assert!((2_300..=2_700).contains(&hits), "{hits}");
+
+        assert!((0..100).all(|_| rng.next_bool(5, 5)));
+        assert!((0..100).all(|_| !rng.next_bool(0, 5)));
+        assert!(!rng.next_bool(1, 0));
+    }
+
+    #[test]
+    fn test_noise() {
+        let mut a = XorShift32::new(99);
+        let mut b = XorShift32::new(99);
+        assert_eq!(noise(&mut a, 1, 2).data(), noise(&mut b, 1, 2).data());
+
+        assert_eq!(noise(&mut a, 1, 1).data(), &[0xFF; 8]);
+        assert_eq!(noise(&mut a, 0, 1).data(), &[0x00; 8]);
+    }
+
+    #[test]
+    fn test_sparkle_first_frames() {
+        let mut recorder = FrameRecorder::new();
+        let mut matrix = SingleMatrix::from_driver(Max7219::new(&mut recorder)).unwrap();
+        matrix.seed_effects(1234);
+        let mut frames_left = 3;
+        matrix
+            .sparkle(&mut NoopDelay::new(), 40, 50, || {
+                frames_left -= 1;
+                frames_left >= 0
+            })
+            .unwrap();
+
+        let rows: Vec<_> = recorder
+            .writes()
+            .into_iter()
+            .filter(|&(_, register, _)| register.is_digit())
+            .map(|(_, _, data)| data)
+            .collect();
+        let shown: Vec<[u8; 8]> = rows.chunks(8).map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(
+            shown,
+            [
+                [0x80, 0x00, 0x84, 0x09, 0x01, 0x32, 0x11, 0xA0],
+                // 0x80 in the first row survived the fade
+                [0x80, 0x09, 0x01, 0x07, 0x40, 0x34, 0x12, 0x00],
+                [0xA4, 0x49, 0x00, 0x05, 0x51, 0x04, 0x83, 0x90],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sparkle_density_extremes() {
+        let mut fake: FakeMatrix = FakeMatrix::new().unwrap();
+        let mut frames = 0;
+        fake.sparkle(&mut NoopDelay::new(), 255, 0, || {
+            frames += 1;
+            frames <= 1
+        })
+        .unwrap();
+        assert_eq!(fake.frame().device_rows(0), Some([0xFF; 8]));
+
+        // Without new stars the display fades out
+        let mut frames = 0;
+        fake.sparkle(&mut NoopDelay::new(), 0, 0, || {
+            frames += 1;
+            frames <= 64
+        })
+        .unwrap();
+        assert_eq!(fake.frame().device_rows(0), Some([0x00; 8]));
+    }
+}
@@ -1,30 +1,323 @@
 //! Font definitions for use with 8x8 LED matrix displays.
 
 /// 8x8 font mapping for LED matrix displays
+///
+/// A glyph can be moved up or down when it is drawn, see [`LedFont::with_offsets`].
 #[derive(Copy, Clone)]
 pub struct LedFont {
     char_map: &'static [([u8; 8], char)],
+    offsets: &'static [(char, i8)],
 }
 
 impl LedFont {
     /// Create a new font
     pub const fn new(char_map: &'static [([u8; 8], char)]) -> Self {
-        Self { char_map }
+        Self {
+            char_map,
+            offsets: &[],
+        }
+    }
+
+    /// Replaces the vertical glyph offsets.
+    ///
+    /// A positive offset moves a glyph down by that many rows and a negative one
+    /// moves it up; rows pushed past the top or bottom edge are dropped. Glyphs
+    /// without an entry are drawn as stored. This lets a descender be stored
+    /// top-aligned and placed below the baseline, see [`DESCENDER_OFFSETS`].
+    pub const fn with_offsets(mut self, offsets: &'static [(char, i8)]) -> Self {
+        self.offsets = offsets;
+        self
     }
 
-    /// Get bitmap pattern for a character
+    /// Get bitmap pattern for a character, moved by its vertical offset
+    ///
+    /// The fallback for a missing character is never moved.
     pub fn get_char(&self, ch: char) -> [u8; 8] {
-        for &(bitmap, c) in self.char_map {
-            if c == ch {
-                return bitmap;
+        self.find(ch).map_or(FONT8X8_UNKNOWN, |bitmap| {
+            shift_rows(bitmap, self.offset(ch))
+        })
+    }
+
+    /// Bitmap of `ch` as stored in the font, ignoring its offset.
+    pub fn raw_char(&self, ch: char) -> [u8; 8] {
+        self.find(ch).unwrap_or(FONT8X8_UNKNOWN)
+    }
+
+    fn find(&self, ch: char) -> Option<[u8; 8]> {
+        self.char_map
+            .iter()
+            .find(|&&(_, c)| c == ch)
+            .map(|&(bitmap, _)| bitmap)
+    }
+
+    /// Vertical offset of `ch` in rows, positive being down. `0` if it has none.
+    pub fn offset(&self, ch: char) -> i8 {
+        self.offsets
+            .iter()
+            .find(|&&(c, _)| c == ch)
+            .map_or(0, |&(_, offset)| offset)
+    }
+
+    /// Width of `ch` in columns.
+    ///
+    /// Every bitmap of an `LedFont` is 8 columns wide, including the fallback
+    /// drawn for missing characters. Zero-width characters the font has no
+    /// bitmap for, see [`is_zero_width`], take no columns at all.
+    pub fn char_width(&self, ch: char) -> usize {
+        if is_zero_width(ch) && self.find(ch).is_none() {
+            0
+        } else {
+            8
+        }
+    }
+
+    /// The characters of `text` that take up a cell, in order.
+    ///
+    /// Every text API of the crate draws and measures text with this, so a
+    /// combining accent or a joiner never takes a cell of its own. Graphemes are
+    /// not clustered: `"e\u{301}"` shows a plain `e`, and an emoji, which no
+    /// built-in font has, is drawn as the fallback glyph, once per code point of
+    /// a multi-code-point sequence.
+    pub fn visible_chars<'t>(&self, text: &'t str) -> impl Iterator<Item = char> + 't {
+        let font = *self;
+        text.chars().filter(move |&ch| font.char_width(ch) > 0)
+    }
+}
+
+/// Returns `true` for characters that modify their neighbours instead of being
+/// drawn on their own.
+///
+/// These are the combining diacritical marks, the variation selectors, and the
+/// zero-width space, joiners and byte order mark.
+pub fn is_zero_width(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{E0100}'..='\u{E01EF}'
+            | '\u{200B}'..='\u{200D}'
+            | '\u{2060}'
+            | '\u{FEFF}'
+    )
+}
+
+/// Width of `text` in columns when drawn with `font`.
+///
+/// `spacing` blank columns are added between neighbouring characters. The
+/// scrolling and message helpers of [`LedMatrix`](crate::led_matrix::LedMatrix)
+/// measure text with this function and no spacing. Zero-width characters are
+/// skipped, see [`LedFont::visible_chars`]. The width saturates at `usize::MAX`
+/// rather than overflowing, which long text could do on a 16-bit target.
+///
+/// # Example
+///
+/// ```rust
+/// use max7219_display::led_matrix::fonts::{STANDARD_LED_FONT, text_width};
+///
+/// assert_eq!(text_width("Hi", &STANDARD_LED_FONT, 0), 16);
+/// assert_eq!(text_width("Hi", &STANDARD_LED_FONT, 1), 17);
+/// ```
+pub fn text_width(text: &str, font: &LedFont, spacing: usize) -> usize {
+    let glyphs = font.visible_chars(text).fold(0usize, |width, ch| {
+        width.saturating_add(font.char_width(ch))
+    });
+    let gaps = font.visible_chars(text).count().saturating_sub(1);
+    glyphs.saturating_add(spacing.saturating_mul(gaps))
+}
+
+/// Character substitutions and custom bitmaps applied before font lookup.
+///
+/// A character is first replaced according to the substitutions, e.g. `'µ'` by
+/// `'u'`. If the result has a custom bitmap, that bitmap is drawn; otherwise the
+/// font is asked for it. Only the first matching entry of each table is used.
+///
+/// # Example
+///
+/// ```rust
+/// use max7219_display::led_matrix::fonts::{CharMap, STANDARD_LED_FONT};
+///
+/// const OMEGA: [u8; 8] = [0x00, 0x3C, 0x42, 0x42, 0x42, 0x24, 0x66, 0x00];
+/// let map = CharMap::new(&[('µ', 'u')]).with_glyphs(&[('Ω', OMEGA)]);
+///
+/// assert_eq!(map.bitmap('µ', &STANDARD_LED_FONT), STANDARD_LED_FONT.get_char('u'));
+/// assert_eq!(map.bitmap('Ω', &STANDARD_LED_FONT), OMEGA);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CharMap {
+    substitutions: &'static [(char, char)],
+    glyphs: &'static [(char, [u8; 8])],
+}
+
+impl CharMap {
+    /// Creates a map with the given substitutions and no custom bitmaps.
+    pub const fn new(substitutions: &'static [(char, char)]) -> Self {
+        Self {
+            substitutions,
+            glyphs: &[],
+        }
+    }
+
+    /// Replaces the substitutions.
+    pub const fn with_substitutions(mut self, substitutions: &'static [(char, char)]) -> Self {
+        self.substitutions = substitutions;
+        self
+    }
+
+    /// Replaces the custom bitmaps, bit 7 of each row being the leftmost column.
+    pub const fn with_glyphs(mut self, glyphs: &'static [(char, [u8; 8])]) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Applies the substitutions to `ch`.
+    pub fn substitute(&self, ch: char) -> char {
+        self.substitutions
+            .iter()
+            .find(|&&(from, _)| from == ch)
+            .map_or(ch, |&(_, to)| to)
+    }
+
+    /// Returns the bitmap drawn for `ch` with `font`.
+    pub fn bitmap(&self, ch: char, font: &LedFont) -> [u8; 8] {
+        let ch = self.substitute(ch);
+        self.glyphs
+            .iter()
+            .find(|&&(c, _)| c == ch)
+            .map_or_else(|| font.get_char(ch), |&(_, bitmap)| bitmap)
+    }
+}
+
+/// Concatenates two glyph tables at compile time into a `&'static` table.
+macro_rules! concat_glyphs {
+    ($first:expr, $second:expr) => {{
+        const FIRST: &[([u8; 8], char)] = $first;
+        const SECOND: &[([u8; 8], char)] = $second;
+        #[allow(
+            clippy::indexing_slicing,
+            reason = "evaluated at compile time, where a bad index is a build error"
+        )]
+        const ENTRIES: [([u8; 8], char); FIRST.len() + SECOND.len()] = {
+            let mut entries = [([0; 8], '\0'); FIRST.len() + SECOND.len()];
+            let mut i = 0;
+            while i < FIRST.len() {
+                entries[i] = FIRST[i];
+                i += 1;
+            }
+            let mut i = 0;
+            while i < SECOND.len() {
+                entries[FIRST.len() + i] = SECOND[i];
+                i += 1;
             }
+            entries
+        };
+        &ENTRIES
+    }};
+}
+
+/// Builds an [`LedFont`] at compile time from a base font table plus extra entries.
+///
+/// Each extra entry maps a character to an 8-row bitmap: a hand-drawn `[u8; 8]`
+/// or a [`Symbol`](crate::led_matrix::symbols::Symbol) via its const
+/// [`to_bitmap`](crate::led_matrix::symbols::Symbol::to_bitmap). Extra entries
+/// take precedence over base entries for the same character. Pass `&[]` as the
+/// base to build a font from the extra entries alone.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(all(feature = "font-ascii-full", feature = "symbols-basic"))]
+/// # fn main() {
+/// use max7219_display::font_with_symbols;
+/// use max7219_display::led_matrix::fonts::{FONT8X8, LedFont};
+/// use max7219_display::led_matrix::symbols::Symbol;
+///
+/// const DOT: [u8; 8] = [0, 0, 0, 0x18, 0x18, 0, 0, 0];
+/// const FONT: LedFont = font_with_symbols!(FONT8X8;
+///     '\u{2665}' => Symbol::Heart.to_bitmap(),
+///     '\u{2022}' => DOT,
+/// );
+///
+/// assert_eq!(FONT.get_char('♥'), Symbol::Heart.to_bitmap());
+/// assert_eq!(FONT.get_char('•'), DOT);
+/// assert_eq!(FONT.get_char('I'), FONT8X8[..].iter().find(|e| e.1 == 'I').unwrap().0);
+/// # }
+/// # #[cfg(not(all(feature = "font-ascii-full", feature = "symbols-basic")))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! font_with_symbols {
+    ($base:expr; $($ch:expr => $bitmap:expr),* $(,)?) => {{
+        const BASE: &[([u8; 8], char)] = $base;
+        const EXTRA: &[([u8; 8], char)] = &[$(($bitmap, $ch)),*];
+        #[allow(
+            clippy::indexing_slicing,
+            reason = "evaluated at compile time, where a bad index is a build error"
+        )]
+        const ENTRIES: [([u8; 8], char); BASE.len() + EXTRA.len()] = {
+            let mut entries = [([0; 8], '\0'); BASE.len() + EXTRA.len()];
+            let mut i = 0;
+            while i < EXTRA.len() {
+                entries[i] = EXTRA[i];
+                i += 1;
+            }
+            let mut i = 0;
+            while i < BASE.len() {
+                entries[EXTRA.len() + i] = BASE[i];
+                i += 1;
+            }
+            entries
+        };
+        $crate::led_matrix::fonts::LedFont::new(&ENTRIES)
+    }};
+}
+
+/// Moves the rows of `bitmap` down by `offset`, or up if it is negative.
+fn shift_rows(bitmap: [u8; 8], offset: i8) -> [u8; 8] {
+    let mut shifted = [0; 8];
+    for (row, &bits) in bitmap.iter().enumerate() {
+        let target = row as isize + isize::from(offset);
+        if let Some(slot) = usize::try_from(target)
+            .ok()
+            .and_then(|t| shifted.get_mut(t))
+        {
+            *slot = bits;
         }
-        FONT8X8_UNKNOWN
     }
+    shifted
 }
 
 /// Standard font for 8x8 LED matrix
-pub const STANDARD_LED_FONT: LedFont = LedFont::new(FONT8X8);
+///
+/// [`FONT8X8`] with `g`, `p`, `q` and `y` stored top-aligned and, like `j`,
+/// lowered below the baseline by [`DESCENDER_OFFSETS`]. It only holds the
+/// glyph blocks enabled by features, see [`FONT8X8`]; other characters are
+/// drawn as [`FONT8X8_UNKNOWN`].
+pub const STANDARD_LED_FONT: LedFont =
+    LedFont::new(concat_glyphs!(DESCENDERS, FONT8X8)).with_offsets(DESCENDER_OFFSETS);
+
+/// Top-aligned forms of the descenders, found before those of [`FONT8X8`].
+#[cfg(feature = "font-ascii-full")]
+#[rustfmt::skip]
+const DESCENDERS: &[([u8; 8], char)] = &[
+    ([0b01110110, 0b11001100, 0b11001100, 0b01111100, 0b00001100, 0b11111000, 0, 0], 'g'),
+    ([0b11011100, 0b01100110, 0b01100110, 0b01111100, 0b01100000, 0b11110000, 0, 0], 'p'),
+    ([0b01110110, 0b11001100, 0b11001100, 0b01111100, 0b00001100, 0b00011110, 0, 0], 'q'),
+    ([0b11001100, 0b11001100, 0b11001100, 0b01111100, 0b00001100, 0b11110000, 0, 0], 'y'),
+];
+
+#[cfg(not(feature = "font-ascii-full"))]
+const DESCENDERS: &[([u8; 8], char)] = &[];
+
+/// Vertical offsets of the descenders of [`STANDARD_LED_FONT`].
+///
+/// `g`, `p`, `q` and `y` start on the x-height row like `a`, and the hook of `j`
+/// reaches the bottom row.
+pub const DESCENDER_OFFSETS: &[(char, i8)] = &[('g', 2), ('j', 1), ('p', 2), ('q', 2), ('y', 2)];
 
 /// Fallback 8x8 glyph used when a character is not found in the font map.
 ///
@@ -36,9 +329,33 @@ pub const FONT8X8_UNKNOWN: [u8; 8] = [
     0b00000000, 0b00111100, 0b01000010, 0b00000010, 0b00001100, 0b00000000, 0b00001100, 0b00000000,
 ];
 
-/// A simple 8x8 font map, with bit-reversed (corrected) glyphs to fix mirroring
+/// Compact 3x5 digits `0` to `9`, one byte per row with bit 2 as the leftmost column.
+///
+/// Two of them fit side by side on one 8x8 module, see
+/// [`LedMatrix::pin_value`](crate::led_matrix::LedMatrix::pin_value).
+#[rustfmt::skip]
+pub const COMPACT_DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// Compact 3x5 minus sign in the layout of [`COMPACT_DIGITS`].
+pub const COMPACT_MINUS: [u8; 5] = [0b000, 0b000, 0b111, 0b000, 0b000];
+
+/// Digits, space and the signs of numbers and times: `+ - . / :`.
+///
+/// Part of [`FONT8X8`]; empty without the `font-digits` feature.
+#[cfg(feature = "font-digits")]
 #[rustfmt::skip]
-pub const FONT8X8: &[([u8; 8], char)] = &[
+pub const FONT8X8_DIGITS: &[([u8; 8], char)] = &[
     (
         [
             0b00000000,
@@ -52,136 +369,6 @@ pub const FONT8X8: &[([u8; 8], char)] = &[
         ],
         ' ',
     ), // (space)
-    (
-        [
-            0b00011000,
-            0b00011000,
-            0b00011000,
-            0b00011000,
-            0b00011000,
-            0b00000000,
-            0b00011000,
-            0b00000000,
-        ],
-        '!',
-    ),
-    (
-        [
-            0b01101100,
-            0b01101100,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-        ],
-        '"',
-    ),
-    (
-        [
-            0b01101100,
-            0b01101100,
-            0b11111110,
-            0b01101100,
-            0b11111110,
-            0b01101100,
-            0b01101100,
-            0b00000000,
-        ],
-        '#',
-    ),
-    (
-        [
-            0b00011000,
-            0b00111110,
-            0b01100000,
-            0b00111100,
-            0b00000110,
-            0b01111100,
-            0b00011000,
-            0b00000000,
-        ],
-        '$',
-    ),
-    (
-        [
-            0b01100010,
-            0b01100100,
-            0b00001000,
-            0b00010000,
-            0b00100000,
-            0b01000110,
-            0b10000110,
-            0b00000000,
-        ],
-        '%',
-    ),
-    (
-        [
-            0b00111000,
-            0b01101100,
-            0b00111000,
-            0b01110110,
-            0b11011100,
-            0b11001100,
-            0b01110110,
-            0b00000000,
-        ],
-        '&',
-    ),
-    (
-        [
-            0b00011000,
-            0b00011000,
-            0b00010000,
-            0b00100000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-        ],
-        '\'',
-    ),
-    (
-        [
-            0b00001100,
-            0b00011000,
-            0b00110000,
-            0b00110000,
-            0b00110000,
-            0b00011000,
-            0b00001100,
-            0b00000000,
-        ],
-        '(',
-    ),
-    (
-        [
-            0b00110000,
-            0b00011000,
-            0b00001100,
-            0b00001100,
-            0b00001100,
-            0b00011000,
-            0b00110000,
-            0b00000000,
-        ],
-        ')',
-    ),
-    (
-        [
-            0b00000000,
-            0b01100110,
-            0b00111100,
-            0b01111110,
-            0b00111100,
-            0b01100110,
-            0b00000000,
-            0b00000000,
-        ],
-        '*',
-    ),
     (
         [
             0b00000000,
@@ -195,19 +382,6 @@ pub const FONT8X8: &[([u8; 8], char)] = &[
         ],
         '+',
     ),
-    (
-        [
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00011000,
-            0b00011000,
-            0b00010000,
-            0b00100000,
-        ],
-        ',',
-    ),
     (
         [
             0b00000000,
@@ -247,7 +421,7 @@ pub const FONT8X8: &[([u8; 8], char)] = &[
         ],
         '/',
     ),
-      (
+    (
         [
             0b00111100,
             0b01100110,
@@ -390,6 +564,163 @@ pub const FONT8X8: &[([u8; 8], char)] = &[
         ],
         ':',
     ),
+];
+
+/// Digits, space and the signs of numbers and times: `+ - . / :`.
+///
+/// Part of [`FONT8X8`]; empty without the `font-digits` feature.
+#[cfg(not(feature = "font-digits"))]
+pub const FONT8X8_DIGITS: &[([u8; 8], char)] = &[];
+
+/// Letters and the remaining printable ASCII characters.
+///
+/// Part of [`FONT8X8`]; empty without the `font-ascii-full` feature.
+#[cfg(feature = "font-ascii-full")]
+#[rustfmt::skip]
+pub const FONT8X8_TEXT: &[([u8; 8], char)] = &[
+    (
+        [
+            0b00011000,
+            0b00011000,
+            0b00011000,
+            0b00011000,
+            0b00011000,
+            0b00000000,
+            0b00011000,
+            0b00000000,
+        ],
+        '!',
+    ),
+    (
+        [
+            0b01101100,
+            0b01101100,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+        ],
+        '"',
+    ),
+    (
+        [
+            0b01101100,
+            0b01101100,
+            0b11111110,
+            0b01101100,
+            0b11111110,
+            0b01101100,
+            0b01101100,
+            0b00000000,
+        ],
+        '#',
+    ),
+    (
+        [
+            0b00011000,
+            0b00111110,
+            0b01100000,
+            0b00111100,
+            0b00000110,
+            0b01111100,
+            0b00011000,
+            0b00000000,
+        ],
+        '$',
+    ),
+    (
+        [
+            0b01100010,
+            0b01100100,
+            0b00001000,
+            0b00010000,
+            0b00100000,
+            0b01000110,
+            0b10000110,
+            0b00000000,
+        ],
+        '%',
+    ),
+    (
+        [
+            0b00111000,
+            0b01101100,
+            0b00111000,
+            0b01110110,
+            0b11011100,
+            0b11001100,
+            0b01110110,
+            0b00000000,
+        ],
+        '&',
+    ),
+    (
+        [
+            0b00011000,
+            0b00011000,
+            0b00010000,
+            0b00100000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+        ],
+        '\'',
+    ),
+    (
+        [
+            0b00001100,
+            0b00011000,
+            0b00110000,
+            0b00110000,
+            0b00110000,
+            0b00011000,
+            0b00001100,
+            0b00000000,
+        ],
+        '(',
+    ),
+    (
+        [
+            0b00110000,
+            0b00011000,
+            0b00001100,
+            0b00001100,
+            0b00001100,
+            0b00011000,
+            0b00110000,
+            0b00000000,
+        ],
+        ')',
+    ),
+    (
+        [
+            0b00000000,
+            0b01100110,
+            0b00111100,
+            0b01111110,
+            0b00111100,
+            0b01100110,
+            0b00000000,
+            0b00000000,
+        ],
+        '*',
+    ),
+    (
+        [
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00011000,
+            0b00011000,
+            0b00010000,
+            0b00100000,
+        ],
+        ',',
+    ),
     (
         [
             0b00000000,
@@ -1276,6 +1607,19 @@ pub const FONT8X8: &[([u8; 8], char)] = &[
     ),
 ];
 
+/// Letters and the remaining printable ASCII characters.
+///
+/// Part of [`FONT8X8`]; empty without the `font-ascii-full` feature.
+#[cfg(not(feature = "font-ascii-full"))]
+pub const FONT8X8_TEXT: &[([u8; 8], char)] = &[];
+
+/// A simple 8x8 font map, with bit-reversed (corrected) glyphs to fix mirroring
+///
+/// Made of the glyph blocks enabled by features: [`FONT8X8_DIGITS`] with
+/// `font-digits` and [`FONT8X8_TEXT`] with `font-ascii-full`. Both are on by
+/// default.
+pub const FONT8X8: &[([u8; 8], char)] = concat_glyphs!(FONT8X8_DIGITS, FONT8X8_TEXT);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1305,6 +1649,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "font-ascii-full")]
     fn test_get_char_existing() {
         // Test getting bitmap for existing characters
         let bitmap_0 = STANDARD_LED_FONT.get_char('0');
@@ -1330,6 +1675,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "font-ascii-full")]
     fn test_get_char_case_sensitivity() {
         // Test that uppercase and lowercase are different
         let bitmap_upper_a = STANDARD_LED_FONT.get_char('A');
@@ -1352,6 +1698,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "font-ascii-full")]
     fn test_special_characters() {
         // Test a few special characters to ensure they're in the font
         let space_bitmap = STANDARD_LED_FONT.get_char(' ');
@@ -1368,4 +1715,137 @@ mod tests {
         ];
         assert_eq!(exclamation_bitmap, expected_exclamation);
     }
+
+    #[test]
+    fn test_text_width() {
+        let font = STANDARD_LED_FONT;
+        assert_eq!(text_width("", &font, 2), 0);
+        assert_eq!(text_width("A", &font, 2), 8);
+        assert_eq!(text_width("ABC", &font, 0), 24);
+        assert_eq!(text_width("ABC", &font, 2), 28);
+        // Missing characters are drawn with the 8-column fallback
+        assert_eq!(text_width("é€", &font, 0), 16);
+    }
+
+    #[test]
+    #[cfg(feature = "symbols-basic")]
+    fn test_font_with_symbols() {
+        use crate::led_matrix::{scroll::ScrollingText, symbols::Symbol};
+        use crate::test_util::FakeMatrix;
+
+        const TICK: [u8; 8] = [0, 1, 2, 4, 0x88, 0x50, 0x20, 0];
+        const FONT: LedFont = font_with_symbols!(FONT8X8;
+            '\u{2665}' => Symbol::Heart.to_bitmap(),
+            'I' => TICK,
+        );
+
+        assert_eq!(FONT.get_char('I'), TICK);
+        assert_eq!(FONT.get_char('U'), STANDARD_LED_FONT.get_char('U'));
+
+        // Static: the middle character lands on the middle device
+        let mut matrix = FakeMatrix::<192, 3>::new().unwrap();
+        matrix.draw_text_with_font("I\u{2665}U", &FONT).unwrap();
+        assert_eq!(
+            matrix.frame().device_rows(1),
+            Some(Symbol::Heart.to_bitmap())
+        );
+
+        // Scrolled: the heart's columns follow the first glyph
+        let scroller = ScrollingText::new_default("I\u{2665}U", &FONT);
+        assert_eq!(
+            scroller.get_frame_at(8).unwrap().data(),
+            &Symbol::Heart.to_bitmap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "font-ascii-full")]
+    fn test_descender_is_lowered() {
+        let a = STANDARD_LED_FONT.get_char('a');
+        let g = STANDARD_LED_FONT.get_char('g');
+        let raw_g = STANDARD_LED_FONT.raw_char('g');
+        assert_eq!(STANDARD_LED_FONT.offset('g'), 2);
+        assert_eq!(g[2..], raw_g[..6]);
+        assert_eq!(g[..2], [0, 0]);
+
+        // Same top row as 'a', but reaching one row further down
+        let lit_rows = |rows: [u8; 8]| {
+            let lit: Vec<_> = (0..8).filter(|&r| rows[r] != 0).collect();
+            (lit[0], lit[lit.len() - 1])
+        };
+        assert_eq!(lit_rows(a), (2, 6));
+        assert_eq!(lit_rows(g), (2, 7));
+        assert_eq!(STANDARD_LED_FONT.offset('a'), 0);
+    }
+
+    #[test]
+    fn test_offsets_are_clipped() {
+        const FONT: LedFont = LedFont::new(&[([1, 2, 3, 4, 5, 6, 7, 8], 'x'), ([1; 8], 'y')])
+            .with_offsets(&[('x', -3), ('y', 9)]);
+        assert_eq!(FONT.get_char('x'), [4, 5, 6, 7, 8, 0, 0, 0]);
+        assert_eq!(FONT.get_char('y'), [0; 8]);
+        assert_eq!(FONT.raw_char('x'), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(FONT.get_char('z'), FONT8X8_UNKNOWN);
+    }
+
+    #[test]
+    fn test_offsets_apply_when_drawing() {
+        use crate::led_matrix::scroll::ScrollingText;
+        use crate::test_util::FakeMatrix;
+
+        let g = STANDARD_LED_FONT.get_char('g');
+
+        let mut matrix = FakeMatrix::<128, 2>::new().unwrap();
+        matrix.draw_text("ag").unwrap();
+        assert_eq!(matrix.frame().device_rows(1), Some(g));
+
+        matrix.draw_char(0, 'g').unwrap();
+        assert_eq!(matrix.frame().device_rows(0), Some(g));
+
+        let scroller = ScrollingText::new_default("ag", &STANDARD_LED_FONT);
+        assert_eq!(scroller.get_frame_at(8).unwrap().data(), &g);
+    }
+
+    #[test]
+    fn test_font_blocks_follow_features() {
+        assert_eq!(FONT8X8.len(), FONT8X8_DIGITS.len() + FONT8X8_TEXT.len());
+        let digits = if cfg!(feature = "font-digits") { 16 } else { 0 };
+        assert_eq!(FONT8X8_DIGITS.len(), digits);
+        let text = if cfg!(feature = "font-ascii-full") {
+            79
+        } else {
+            0
+        };
+        assert_eq!(FONT8X8_TEXT.len(), text);
+
+        let missing = |ch| STANDARD_LED_FONT.get_char(ch) == FONT8X8_UNKNOWN;
+        assert_eq!(missing('7'), !cfg!(feature = "font-digits"));
+        assert_eq!(missing(':'), !cfg!(feature = "font-digits"));
+        assert_eq!(missing('A'), !cfg!(feature = "font-ascii-full"));
+        assert_eq!(missing('g'), !cfg!(feature = "font-ascii-full"));
+    }
+
+    #[test]
+    fn test_zero_width_chars() {
+        let font = &STANDARD_LED_FONT;
+        // Precomposed é is one (fallback) glyph, a combining accent adds nothing
+        assert_eq!(text_width("caf\u{e9}", font, 0), 32);
+        assert_eq!(text_width("cafe\u{301}", font, 0), 32);
+        assert_eq!(text_width("e\u{301}x", font, 1), 17);
+        assert_eq!(
+            font.visible_chars("cafe\u{301}").collect::<String>(),
+            "cafe"
+        );
+
+        // No grapheme clustering: one cell per visible code point
+        assert_eq!(text_width("\u{1F600}", font, 0), 8);
+        assert_eq!(text_width("\u{2764}\u{FE0F}", font, 0), 8);
+        assert_eq!(text_width("\u{1F469}\u{200D}\u{1F467}", font, 0), 16);
+        assert_eq!(text_width("\u{301}", font, 1), 0);
+
+        // A font that defines a mark draws it
+        const MARKS: LedFont = LedFont::new(&[([0x18; 8], '\u{301}')]);
+        assert_eq!(MARKS.char_width('\u{301}'), 8);
+        assert_eq!(text_width("\u{301}\u{302}", &MARKS, 0), 8);
+    }
 }
@@ -0,0 +1,94 @@
+//! Glyph trait for anything that can be drawn as an 8x8 pattern
+
+use crate::led_matrix::{buffer::MatrixBuffer, fonts::STANDARD_LED_FONT, symbols::Symbol};
+
+/// Anything that can be rendered as a single 8x8 pattern on one LED matrix device.
+///
+/// The crate implements this for [`Symbol`], [`MatrixBuffer`] and `char` (rendered with
+/// [`STANDARD_LED_FONT`]). Implement it on your own types (logos, custom arrows, status
+/// icons) to draw them through the same APIs, such as [`LedMatrix::draw_symbol`](crate::led_matrix::LedMatrix::draw_symbol).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// struct Logo;
+///
+/// impl Glyph for Logo {
+///     fn to_buffer(&self) -> MatrixBuffer {
+///         MatrixBuffer::from_data([0x18, 0x3C, 0x7E, 0xFF, 0xFF, 0x7E, 0x3C, 0x18])
+///     }
+/// }
+///
+/// matrix.draw_symbol(0, Logo)?;
+/// ```
+pub trait Glyph {
+    /// Returns the 8x8 pattern for this glyph.
+    fn to_buffer(&self) -> MatrixBuffer;
+}
+
+impl Glyph for Symbol {
+    fn to_buffer(&self) -> MatrixBuffer {
+        Symbol::to_buffer(self)
+    }
+}
+
+impl Glyph for MatrixBuffer {
+    fn to_buffer(&self) -> MatrixBuffer {
+        self.clone()
+    }
+}
+
+impl Glyph for char {
+    fn to_buffer(&self) -> MatrixBuffer {
+        MatrixBuffer::from_data(STANDARD_LED_FONT.get_char(*self))
+    }
+}
+
+impl<G: Glyph + ?Sized> Glyph for &G {
+    fn to_buffer(&self) -> MatrixBuffer {
+        (**self).to_buffer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Logo;
+
+    impl Glyph for Logo {
+        fn to_buffer(&self) -> MatrixBuffer {
+            MatrixBuffer::from_data([0x18, 0x3C, 0x7E, 0xFF, 0xFF, 0x7E, 0x3C, 0x18])
+        }
+    }
+
+    fn rows_of(glyph: impl Glyph) -> [u8; 8] {
+        *glyph.to_buffer().data()
+    }
+
+    #[test]
+    #[cfg(feature = "symbols-basic")]
+    fn test_symbol_glyph() {
+        assert_eq!(rows_of(Symbol::Heart), *Symbol::Heart.to_buffer().data());
+    }
+
+    #[test]
+    fn test_buffer_glyph() {
+        let buffer = MatrixBuffer::from_data([1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(rows_of(&buffer), [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_char_glyph() {
+        assert_eq!(rows_of('7'), STANDARD_LED_FONT.get_char('7'));
+    }
+
+    #[test]
+    fn test_custom_glyph() {
+        assert_eq!(
+            rows_of(Logo),
+            [0x18, 0x3C, 0x7E, 0xFF, 0xFF, 0x7E, 0x3C, 0x18]
+        );
+        assert_eq!(rows_of(&Logo), rows_of(Logo));
+    }
+}
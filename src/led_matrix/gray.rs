@@ -0,0 +1,136 @@
+//! Soft grayscale for LED matrices through temporal dithering
+//!
+//! The MAX7219 can only switch each LED on or off. Switching a pixel on for only
+//! some of the refreshes makes it look dimmer, which gives a few extra gray levels
+//! for icons and fades. Use [`LedMatrix::set_gray_pixel`] to store levels and call
+//! [`LedMatrix::flush_dithered`] from a timer with an incrementing frame index.
+//!
+//! # Flicker
+//!
+//! A full dither cycle takes four frames, so the refresh rate must be high enough
+//! that the eye averages them: at 200 Hz one cycle lasts 20 ms, which is about the
+//! lowest rate that looks steady. Below that, [`GrayLevel::Low`] pixels visibly
+//! blink. Every refresh rewrites all eight rows of every device, so the SPI bus and
+//! CPU load grows with the chain length and the refresh rate. Neighbouring pixels
+//! use different phases of the pattern to spread the flicker out.
+//!
+//! [`LedMatrix::set_gray_pixel`]: crate::led_matrix::LedMatrix::set_gray_pixel
+//! [`LedMatrix::flush_dithered`]: crate::led_matrix::LedMatrix::flush_dithered
+
+/// Number of frames in one dither cycle.
+pub const DITHER_FRAMES: u8 = 4;
+
+/// 2x2 ordered dither offsets, indexed by `[y % 2][x % 2]`.
+const BAYER_2X2: [[u8; 2]; 2] = [[0, 2], [3, 1]];
+
+/// Brightness of a pixel when flushed with dithering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GrayLevel {
+    /// Never lit.
+    #[default]
+    Off,
+    /// Lit one frame out of four.
+    Low,
+    /// Lit two frames out of four.
+    Medium,
+    /// Always lit.
+    Full,
+}
+
+impl GrayLevel {
+    /// Returns how many frames out of [`DITHER_FRAMES`] this level is lit.
+    pub const fn duty(self) -> u8 {
+        match self {
+            GrayLevel::Off => 0,
+            GrayLevel::Low => 1,
+            GrayLevel::Medium => 2,
+            GrayLevel::Full => DITHER_FRAMES,
+        }
+    }
+
+    /// Returns whether a pixel at `(x, y)` is lit in frame `frame_index`.
+    pub const fn is_lit(self, x: usize, y: usize, frame_index: u8) -> bool {
+        let [even_row, odd_row] = BAYER_2X2;
+        let [even_col, odd_col] = if y % 2 == 1 { odd_row } else { even_row };
+        let threshold = if x % 2 == 1 { odd_col } else { even_col };
+        let phase = (frame_index % DITHER_FRAMES + threshold) % DITHER_FRAMES;
+        phase < self.duty()
+    }
+
+    /// Encodes the level as a framebuffer value.
+    ///
+    /// `Full` is stored as `1`, the value binary drawing uses for a lit pixel, so
+    /// pixels drawn through the other APIs flush at full brightness.
+    pub(crate) const fn to_pixel(self) -> u8 {
+        match self {
+            GrayLevel::Off => 0,
+            GrayLevel::Full => 1,
+            GrayLevel::Low => 2,
+            GrayLevel::Medium => 3,
+        }
+    }
+
+    /// Decodes a framebuffer value; unknown non-zero values count as `Full`.
+    pub(crate) const fn from_pixel(value: u8) -> Self {
+        match value {
+            0 => GrayLevel::Off,
+            2 => GrayLevel::Low,
+            3 => GrayLevel::Medium,
+            _ => GrayLevel::Full,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(level: GrayLevel, x: usize, y: usize) -> [bool; 4] {
+        [0, 1, 2, 3].map(|frame| level.is_lit(x, y, frame))
+    }
+
+    #[test]
+    fn test_duty_pattern() {
+        assert_eq!(pattern(GrayLevel::Off, 0, 0), [false; 4]);
+        assert_eq!(pattern(GrayLevel::Low, 0, 0), [true, false, false, false]);
+        assert_eq!(pattern(GrayLevel::Medium, 0, 0), [true, true, false, false]);
+        assert_eq!(pattern(GrayLevel::Full, 0, 0), [true; 4]);
+    }
+
+    #[test]
+    fn test_duty_holds_for_every_phase() {
+        for level in [
+            GrayLevel::Off,
+            GrayLevel::Low,
+            GrayLevel::Medium,
+            GrayLevel::Full,
+        ] {
+            for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1), (6, 3)] {
+                let lit = pattern(level, x, y).iter().filter(|&&on| on).count();
+                assert_eq!(lit, level.duty() as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbours_use_different_phases() {
+        assert_eq!(pattern(GrayLevel::Low, 1, 0), [false, false, true, false]);
+        assert_eq!(pattern(GrayLevel::Low, 0, 1), [false, true, false, false]);
+        assert_eq!(pattern(GrayLevel::Low, 1, 1), [false, false, false, true]);
+    }
+
+    #[test]
+    fn test_pixel_encoding_round_trip() {
+        for level in [
+            GrayLevel::Off,
+            GrayLevel::Low,
+            GrayLevel::Medium,
+            GrayLevel::Full,
+        ] {
+            assert_eq!(GrayLevel::from_pixel(level.to_pixel()), level);
+        }
+        assert_eq!(GrayLevel::from_pixel(1), GrayLevel::Full);
+        assert_eq!(GrayLevel::from_pixel(200), GrayLevel::Full);
+    }
+}
@@ -0,0 +1,235 @@
+//! Conversions between `embedded-graphics` images and the crate's buffers
+//!
+//! Drawing an image through [`DrawTarget`] on every frame goes through the
+//! pixel iterator each time. These conversions do it once, so an asset loaded
+//! with e.g. `ImageRaw` or `tinybmp` can be kept as a [`MatrixBuffer`] or a
+//! prebuilt framebuffer and shown like any other. Column 0 of the image lands in
+//! the leftmost column, bit 7 of a row.
+//!
+//! [`MatrixBuffer`] also implements [`ImageDrawable`], so the crate's glyphs can
+//! go the other way and be drawn with `embedded_graphics::image::Image`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! const LOGO_DATA: &[u8] = include_bytes!("logo.raw");
+//! let logo = ImageRaw::<BinaryColor>::new(LOGO_DATA, 32);
+//! let frame: [u8; 256] = framebuffer_from_image(&logo, Point::zero());
+//! let mut pages = [Page::Frame(&frame)];
+//! ```
+
+use embedded_graphics_core::{
+    Pixel,
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::ImageDrawable,
+    pixelcolor::BinaryColor,
+    primitives::{PointsIter, Rectangle},
+};
+
+use crate::led_matrix::buffer::MatrixBuffer;
+
+/// Pixels of a converted image, one byte each, in framebuffer order.
+struct Canvas<const BUFFER_LENGTH: usize> {
+    pixels: [u8; BUFFER_LENGTH],
+    offset: Point,
+}
+
+impl<const BUFFER_LENGTH: usize> Canvas<BUFFER_LENGTH> {
+    fn draw<I>(image: &I, offset: Point) -> [u8; BUFFER_LENGTH]
+    where
+        I: ImageDrawable<Color = BinaryColor>,
+    {
+        let mut canvas = Self {
+            pixels: [0; BUFFER_LENGTH],
+            offset,
+        };
+        let Ok(()) = image.draw(&mut canvas);
+        canvas.pixels
+    }
+}
+
+impl<const BUFFER_LENGTH: usize> OriginDimensions for Canvas<BUFFER_LENGTH> {
+    fn size(&self) -> Size {
+        Size::new((BUFFER_LENGTH / 8) as u32, 8)
+    }
+}
+
+impl<const BUFFER_LENGTH: usize> DrawTarget for Canvas<BUFFER_LENGTH> {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> core::result::Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let width = (BUFFER_LENGTH / 8) as i32;
+        for Pixel(point, color) in pixels {
+            let Point { x, y } = point + self.offset;
+            // Anything outside the target is clipped
+            if !(0..width).contains(&x) || !(0..8).contains(&y) {
+                continue;
+            }
+            let (x, y) = (x as usize, y as usize);
+            if let Some(pixel) = self.pixels.get_mut((x / 8) * 64 + y * 8 + x % 8) {
+                *pixel = color.is_on() as u8;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders `image` into a framebuffer for a chain of `BUFFER_LENGTH / 64`
+/// devices, with the image's top-left corner at `offset`.
+///
+/// The result can be copied into [`LedMatrix`](crate::led_matrix::LedMatrix)'s
+/// framebuffer or shown with [`Page::Frame`](crate::led_matrix::pages::Page::Frame).
+/// Parts of the image outside the display are clipped, and pixels it does not
+/// cover stay off.
+pub fn framebuffer_from_image<I, const BUFFER_LENGTH: usize>(
+    image: &I,
+    offset: Point,
+) -> [u8; BUFFER_LENGTH]
+where
+    I: ImageDrawable<Color = BinaryColor>,
+{
+    Canvas::draw(image, offset)
+}
+
+impl MatrixBuffer {
+    /// Renders the 8x8 window of `image` starting at `offset` into a buffer.
+    ///
+    /// The image's top-left corner is drawn at `offset`, so a negative offset
+    /// picks a later part of a larger image. Parts outside the buffer are clipped,
+    /// and pixels the image does not cover stay off.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let sheet = ImageRaw::<BinaryColor>::new(ICONS, 32);
+    /// // Third icon of a horizontal sprite sheet
+    /// let icon = MatrixBuffer::from_image(&sheet, Point::new(-16, 0));
+    /// ```
+    pub fn from_image<I>(image: &I, offset: Point) -> Self
+    where
+        I: ImageDrawable<Color = BinaryColor>,
+    {
+        let pixels: [u8; 64] = Canvas::draw(image, offset);
+        let mut data = [0; 8];
+        for (row, line) in data.iter_mut().zip(pixels.chunks_exact(8)) {
+            for (col, &on) in line.iter().enumerate() {
+                *row |= on << (7 - col);
+            }
+        }
+        Self::from_data(data)
+    }
+}
+
+impl OriginDimensions for MatrixBuffer {
+    fn size(&self) -> Size {
+        Size::new(8, 8)
+    }
+}
+
+impl ImageDrawable for MatrixBuffer {
+    type Color = BinaryColor;
+
+    fn draw<D>(&self, target: &mut D) -> core::result::Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw_sub_image(target, &Rectangle::new(Point::zero(), self.size()))
+    }
+
+    fn draw_sub_image<D>(
+        &self,
+        target: &mut D,
+        area: &Rectangle,
+    ) -> core::result::Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = area.intersection(&Rectangle::new(Point::zero(), self.size()));
+        let pixels = area.points().map(|point| {
+            let on = self
+                .data()
+                .get(point.y as usize)
+                .is_some_and(|row| row & (0x80 >> point.x) != 0);
+            Pixel(point - area.top_left, BinaryColor::from(on))
+        });
+        target.draw_iter(pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::image::{Image, ImageRaw};
+    use embedded_graphics::prelude::*;
+
+    // A 10x3 image: a frame with a dot in the middle
+    #[rustfmt::skip]
+    const IMAGE_DATA: &[u8] = &[
+        0b1111_1111, 0b1100_0000,
+        0b1000_1000, 0b0100_0000,
+        0b1111_1111, 0b1100_0000,
+    ];
+
+    fn image() -> ImageRaw<'static, BinaryColor> {
+        ImageRaw::new(IMAGE_DATA, 10)
+    }
+
+    #[test]
+    fn test_matrix_buffer_from_image() {
+        let buffer = MatrixBuffer::from_image(&image(), Point::new(0, 2));
+        assert_eq!(buffer.data(), &[0, 0, 0xFF, 0b1000_1000, 0xFF, 0, 0, 0]);
+
+        // Shifted left, the right edge of the image comes into view
+        let buffer = MatrixBuffer::from_image(&image(), Point::new(-4, 0));
+        assert_eq!(
+            buffer.data(),
+            &[0b1111_1100, 0b1000_0100, 0b1111_1100, 0, 0, 0, 0, 0]
+        );
+
+        let outside = MatrixBuffer::from_image(&image(), Point::new(8, 0));
+        assert_eq!(outside.data(), &[0; 8]);
+    }
+
+    #[test]
+    fn test_framebuffer_from_image() {
+        let frame: [u8; 128] = framebuffer_from_image(&image(), Point::new(3, 6));
+
+        let mut expected = [0; 128];
+        for x in 3..13 {
+            expected[(x / 8) * 64 + 6 * 8 + x % 8] = 1;
+        }
+        for x in [3, 7, 12] {
+            expected[(x / 8) * 64 + 7 * 8 + x % 8] = 1;
+        }
+        // The bottom row of the image is clipped
+        assert_eq!(frame, expected);
+    }
+
+    #[test]
+    fn test_draw_matrix_buffer_as_image() {
+        let source = MatrixBuffer::from_data([0x80, 0x41, 0, 0, 0, 0, 0, 0xFF]);
+        let mut canvas = Canvas::<64> {
+            pixels: [0; 64],
+            offset: Point::zero(),
+        };
+        Image::new(&source, Point::zero())
+            .draw(&mut canvas)
+            .unwrap();
+        assert_eq!(canvas.pixels[0], 1);
+        assert_eq!(canvas.pixels[8..16], [0, 1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(canvas.pixels[56..], [1; 8]);
+        assert_eq!(
+            MatrixBuffer::from_image(&source, Point::zero()).data(),
+            source.data()
+        );
+
+        let sub = source.sub_image(&Rectangle::new(Point::new(1, 1), Size::new(7, 7)));
+        let cropped = MatrixBuffer::from_image(&sub, Point::zero());
+        assert_eq!(cropped.data(), &[0x82, 0, 0, 0, 0, 0, 0xFE, 0]);
+    }
+}
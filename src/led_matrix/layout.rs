@@ -0,0 +1,236 @@
+//! Marquee layouts mixing fixed glyphs with a scrolling region
+//!
+//! A [`Layout`] pins glyphs to some devices of the chain and scrolls text through a
+//! range of the others, composing the framebuffer and flushing it on every tick.
+
+use core::ops::Range;
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    MAX_DISPLAYS, Result,
+    error::Error,
+    led_matrix::{
+        LedMatrix,
+        buffer::MatrixBuffer,
+        glyph::Glyph,
+        scroll::{ScrollConfig, ScrollingText},
+    },
+};
+
+/// Builder returned by [`LedMatrix::layout`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// matrix
+///     .layout()
+///     .fixed(0, Symbol::Heart)
+///     .scroll(1..3, "long message", ScrollConfig::default())
+///     .fixed(3, '7')
+///     .run(&mut delay, || button.is_low())?;
+/// ```
+pub struct Layout<'m, 'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> {
+    matrix: &'m mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+    fixed: [Option<MatrixBuffer>; MAX_DISPLAYS],
+    scroll: Option<(Range<usize>, ScrollingText<'a>, ScrollConfig)>,
+    invalid_index: Option<usize>,
+}
+
+impl<'m, 'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    Layout<'m, 'a, SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    pub(crate) fn new(matrix: &'m mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>) -> Self {
+        Self {
+            matrix,
+            fixed: [const { None }; MAX_DISPLAYS],
+            scroll: None,
+            invalid_index: None,
+        }
+    }
+
+    /// Shows `glyph` on `device_index` for as long as the layout runs.
+    pub fn fixed(mut self, device_index: usize, glyph: impl Glyph) -> Self {
+        match self.fixed.get_mut(device_index) {
+            Some(slot) if device_index < DEVICE_COUNT => *slot = Some(glyph.to_buffer()),
+            _ => self.invalid_index = self.invalid_index.or(Some(device_index)),
+        }
+        self
+    }
+
+    /// Scrolls `text` through the devices in `devices` with the matrix's default
+    /// font, see [`LedMatrix::set_default_font`].
+    ///
+    /// Only one scrolling region is supported; calling this again replaces it.
+    /// The region's `step_delay_ns` sets the tick rate of the whole layout.
+    pub fn scroll(mut self, devices: Range<usize>, text: &'a str, config: ScrollConfig) -> Self {
+        if devices.end > DEVICE_COUNT {
+            self.invalid_index = self.invalid_index.or(Some(devices.end - 1));
+        }
+        let mut scroller = ScrollingText::new(text, self.matrix.default_font(), config)
+            .with_char_map(self.matrix.char_map())
+            .with_style(self.matrix.text_style())
+            .with_visible_width(devices.len() * 8);
+        scroller.reset();
+        self.scroll = Some((devices, scroller, config));
+        self
+    }
+
+    /// Runs the layout until `until` returns `true` or a non-looping scroll ends.
+    ///
+    /// Each tick composes the fixed glyphs and the current scroll frame into the
+    /// framebuffer, flushes it, checks `until`, then waits one scroll step. The idle
+    /// hook, if any, runs before every wait. Without
+    /// a scrolling region the layout is drawn once and the method returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceIndex` if a fixed glyph or the scroll region
+    /// refers to a device outside the chain, or any error from [`LedMatrix::flush`].
+    pub fn run<D: DelayNs>(mut self, delay: &mut D, mut until: impl FnMut() -> bool) -> Result<()> {
+        if let Some(index) = self.invalid_index {
+            return Err(Error::InvalidDeviceIndex {
+                index,
+                count: DEVICE_COUNT,
+            });
+        }
+
+        loop {
+            self.compose()?;
+            self.matrix.flush()?;
+
+            if until() {
+                return Ok(());
+            }
+
+            let Some((_, scroller, config)) = self.scroll.as_mut() else {
+                return Ok(());
+            };
+            if !scroller.step() {
+                return Ok(());
+            }
+            self.matrix.driver().idle();
+            delay.delay_ns(config.step_delay_ns);
+        }
+    }
+
+    /// Writes the fixed glyphs and the current scroll frame into the framebuffer.
+    fn compose(&mut self) -> Result<()> {
+        for (device_index, glyph) in self.fixed.iter().enumerate() {
+            if let Some(buffer) = glyph {
+                self.matrix.blit_rows(device_index, buffer.data());
+            }
+        }
+
+        if let Some((devices, scroller, _)) = self.scroll.as_ref() {
+            for (slot, device_index) in devices.clone().enumerate() {
+                let frame = scroller.get_frame_at(slot as i32 * 8)?;
+                self.matrix.blit_rows(device_index, frame.data());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Every layout test draws a fixed symbol
+#[cfg(all(test, feature = "symbols-basic"))]
+mod tests {
+    use super::*;
+    use crate::led_matrix::{display::Matrix4, fonts::STANDARD_LED_FONT, symbols::Symbol};
+    use crate::{Max7219, registers::Register};
+    use embedded_hal_mock::eh1::{
+        delay::NoopDelay,
+        spi::{Mock as SpiMock, Transaction},
+    };
+
+    /// Expected flush of a 4-device chain, given each device's rows.
+    fn flush_frame(devices: [[u8; 8]; 4]) -> Vec<Transaction<u8>> {
+        let mut transactions = Vec::new();
+        for (row, digit_register) in Register::digits().enumerate() {
+            let mut data = Vec::new();
+            // Device 0 is sent first
+            for rows in &devices {
+                data.extend([digit_register.addr(), rows[row]]);
+            }
+            transactions.push(Transaction::transaction_start());
+            transactions.push(Transaction::write_vec(data));
+            transactions.push(Transaction::transaction_end());
+        }
+        transactions
+    }
+
+    #[test]
+    fn test_layout_fixed_and_scroll() {
+        let config = ScrollConfig {
+            loop_text: false,
+            ..ScrollConfig::default()
+        };
+        let heart = *Symbol::Heart.to_buffer().data();
+        let seven = STANDARD_LED_FONT.get_char('7');
+
+        // Reference scroller for the two scroll devices
+        let mut reference = ScrollingText::new("HELLO", &STANDARD_LED_FONT, config);
+        reference.reset();
+        // The first flush powers the fresh driver on
+        let mut expected_transactions = vec![
+            Transaction::transaction_start(),
+            Transaction::write_vec([Register::Shutdown.addr(), 0x01].repeat(4)),
+            Transaction::transaction_end(),
+        ];
+        for _ in 0..2 {
+            let left = *reference.get_frame().unwrap().data();
+            let right = *reference.get_frame_at(8).unwrap().data();
+            reference.step();
+
+            expected_transactions.extend(flush_frame([heart, left, right, seven]));
+        }
+
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        let mut ticks = 0;
+        matrix
+            .layout()
+            .fixed(0, Symbol::Heart)
+            .scroll(1..3, "HELLO", config)
+            .fixed(3, '7')
+            .run(&mut NoopDelay::new(), || {
+                ticks += 1;
+                ticks == 2
+            })
+            .unwrap();
+
+        assert_eq!(ticks, 2);
+        spi.done();
+    }
+
+    #[test]
+    fn test_layout_invalid_device() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        let result = matrix
+            .layout()
+            .scroll(2..5, "HI", ScrollConfig::default())
+            .run(&mut NoopDelay::new(), || true);
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 4, count: 4 })
+        );
+
+        let result = matrix
+            .layout()
+            .fixed(9, Symbol::Heart)
+            .run(&mut NoopDelay::new(), || true);
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 9, count: 4 })
+        );
+        spi.done();
+    }
+}
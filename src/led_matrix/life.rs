@@ -0,0 +1,209 @@
+//! Conway's Game of Life on the chain-wide framebuffer
+//!
+//! The whole chain is treated as one `DEVICE_COUNT * 8` by 8 grid. The next
+//! generation is computed in place: bit 0 of each framebuffer byte holds the
+//! current state and bit 1 collects the next one, so no scratch buffer is needed
+//! however long the chain is.
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::led_matrix::LedMatrix;
+
+const ALIVE: u8 = 0b01;
+const NEXT_ALIVE: u8 = 0b10;
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Advances the framebuffer by one Game of Life generation.
+    ///
+    /// Every lit pixel is a live cell. With `wrap` set, the grid is a torus: cells
+    /// on the right edge of the last device neighbour the left edge of the first
+    /// device, and the top row neighbours the bottom row. Otherwise everything
+    /// outside the chain counts as dead.
+    ///
+    /// Only the framebuffer changes; call [`Self::flush`] to show the result.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// loop {
+    ///     matrix.step_life(true);
+    ///     matrix.flush()?;
+    ///     delay.delay_ms(200);
+    /// }
+    /// ```
+    pub fn step_life(&mut self, wrap: bool) {
+        let width = self.width_pixels();
+
+        for value in self.framebuffer.iter_mut() {
+            *value = u8::from(*value != 0);
+        }
+
+        for y in 0..8 {
+            for x in 0..width {
+                let mut neighbours = 0;
+                for (dx, dy) in NEIGHBOURS {
+                    let Some(index) = neighbour_index(x, y, dx, dy, width, wrap) else {
+                        continue;
+                    };
+                    if self
+                        .framebuffer
+                        .get(index)
+                        .is_some_and(|&value| value & ALIVE != 0)
+                    {
+                        neighbours += 1;
+                    }
+                }
+
+                let Some(value) = self.framebuffer.get_mut(pixel_index(x, y)) else {
+                    continue;
+                };
+                let alive = *value & ALIVE != 0;
+                if neighbours == 3 || (alive && neighbours == 2) {
+                    *value |= NEXT_ALIVE;
+                }
+            }
+        }
+
+        for value in self.framebuffer.iter_mut() {
+            *value >>= 1;
+        }
+    }
+}
+
+const NEIGHBOURS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+fn pixel_index(x: usize, y: usize) -> usize {
+    (x / 8) * 64 + y * 8 + x % 8
+}
+
+fn neighbour_index(
+    x: usize,
+    y: usize,
+    dx: isize,
+    dy: isize,
+    width: usize,
+    wrap: bool,
+) -> Option<usize> {
+    let nx = x as isize + dx;
+    let ny = y as isize + dy;
+    let (nx, ny) = if wrap {
+        (nx.rem_euclid(width as isize), ny.rem_euclid(8))
+    } else if (0..width as isize).contains(&nx) && (0..8).contains(&ny) {
+        (nx, ny)
+    } else {
+        return None;
+    };
+    Some(pixel_index(nx as usize, ny as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::Matrix4;
+    use embedded_hal_mock::eh1::spi::Mock as SpiMock;
+
+    const GLIDER: [(usize, usize); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    fn live_cells<SPI: SpiDevice>(matrix: &Matrix4<SPI>) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for y in 0..8 {
+            for x in 0..32 {
+                if matrix.framebuffer[pixel_index(x, y)] != 0 {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+
+    fn place<SPI: SpiDevice>(
+        matrix: &mut Matrix4<SPI>,
+        cells: &[(usize, usize)],
+        at: (usize, usize),
+    ) {
+        for &(x, y) in cells {
+            matrix.framebuffer[pixel_index((x + at.0) % 32, (y + at.1) % 8)] = 1;
+        }
+    }
+
+    fn sorted(mut cells: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        cells.sort_by_key(|&(x, y)| (y, x));
+        cells
+    }
+
+    #[test]
+    fn test_glider_generations() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        // Starts on device 0 and crosses into device 1
+        place(&mut matrix, &GLIDER, (5, 1));
+
+        matrix.step_life(false);
+        assert_eq!(
+            live_cells(&matrix),
+            [(5, 2), (7, 2), (6, 3), (7, 3), (6, 4)]
+        );
+
+        for _ in 0..3 {
+            matrix.step_life(false);
+        }
+        // After four generations the glider moved one cell right and down
+        let expected: Vec<_> = GLIDER.iter().map(|&(x, y)| (x + 6, y + 2)).collect();
+        assert_eq!(live_cells(&matrix), sorted(expected));
+        spi.done();
+    }
+
+    #[test]
+    fn test_glider_wraps_across_chain_edges() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        // Bottom-right corner of the last device
+        place(&mut matrix, &GLIDER, (29, 5));
+        for _ in 0..4 {
+            matrix.step_life(true);
+        }
+
+        let expected: Vec<_> = GLIDER
+            .iter()
+            .map(|&(x, y)| ((x + 30) % 32, (y + 6) % 8))
+            .collect();
+        assert_eq!(live_cells(&matrix), sorted(expected));
+        spi.done();
+    }
+
+    #[test]
+    fn test_dead_edges() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        // Horizontal blinker on the top row turns vertical, losing the cell above
+        place(&mut matrix, &[(0, 0), (1, 0), (2, 0)], (0, 0));
+        matrix.step_life(false);
+        assert_eq!(live_cells(&matrix), [(1, 0), (1, 1)]);
+
+        matrix.clear_buffer();
+        place(&mut matrix, &[(0, 0), (1, 0), (2, 0)], (0, 0));
+        matrix.step_life(true);
+        assert_eq!(live_cells(&matrix), [(1, 0), (1, 1), (1, 7)]);
+        spi.done();
+    }
+}
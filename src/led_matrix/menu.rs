@@ -0,0 +1,321 @@
+//! Selection menu for interactive setups
+//!
+//! [`Menu`] keeps a fixed list of items and the selected one, and redraws only
+//! what a selection change affects. The matrix is one line high, so it shows
+//! the selected item, highlighted, with a position bar in the rightmost column:
+//!
+//! ```text
+//! columns 0 .. W-3   W-2   W-1
+//!         item text  gap   position bar
+//! ```
+//!
+//! Items wider than the text area scroll by one column on every
+//! [`Menu::render`], so call it at the scroll rate.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let mut menu = Menu::new(["BRIGHTNESS", "CLOCK", "DEMO", "EXIT"]);
+//! loop {
+//!     match buttons.read() {
+//!         Some(Button::Down) => menu.select_next(),
+//!         Some(Button::Up) => menu.select_prev(),
+//!         Some(Button::Ok) => break,
+//!         None => {}
+//!     }
+//!     menu.render(&mut matrix)?;
+//!     delay.delay_ms(60);
+//! }
+//! ```
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{
+    Error, Result,
+    led_matrix::{
+        LedMatrix,
+        fonts::text_width,
+        scroll::{ScrollConfig, ScrollingText},
+        style::TextStyle,
+    },
+};
+
+/// Blank columns between the end and the start of a scrolling item.
+const SCROLL_GAP: u8 = 8;
+
+/// A list of `N` items with one of them selected.
+#[derive(Debug, Clone)]
+pub struct Menu<'a, const N: usize> {
+    items: [&'a str; N],
+    selected: usize,
+    style: TextStyle,
+    inverted: bool,
+    /// Scroll position of the selected item, `0` if it fits.
+    offset: i32,
+    /// The text area has to be redrawn.
+    text_dirty: bool,
+    /// The position bar has to be redrawn.
+    bar_dirty: bool,
+}
+
+impl<'a, const N: usize> Menu<'a, N> {
+    /// Creates a menu with the first item selected, drawn inverted: lit
+    /// background, dark text.
+    pub const fn new(items: [&'a str; N]) -> Self {
+        Self {
+            items,
+            selected: 0,
+            style: TextStyle::Normal,
+            inverted: true,
+            offset: 0,
+            text_dirty: true,
+            bar_dirty: true,
+        }
+    }
+
+    /// Sets the style of the selected item's text, e.g. [`TextStyle::Outline`].
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self.text_dirty = true;
+        self
+    }
+
+    /// Sets whether the selected item is drawn dark on a lit background.
+    pub fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self.text_dirty = true;
+        self
+    }
+
+    /// Index of the selected item.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The selected item, `None` for a menu without items.
+    pub fn selected_item(&self) -> Option<&'a str> {
+        self.items.get(self.selected).copied()
+    }
+
+    /// Selects the item at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValueOutOfRange` if there is no such item.
+    pub fn select(&mut self, index: usize) -> Result<()> {
+        if index >= N {
+            return Err(Error::ValueOutOfRange);
+        }
+        self.set_selected(index);
+        Ok(())
+    }
+
+    /// Selects the next item, wrapping around to the first.
+    pub fn select_next(&mut self) {
+        if N > 0 {
+            self.set_selected((self.selected + 1) % N);
+        }
+    }
+
+    /// Selects the previous item, wrapping around to the last.
+    pub fn select_prev(&mut self) {
+        if N > 0 {
+            self.set_selected((self.selected + N - 1) % N);
+        }
+    }
+
+    /// Selects `index`, which is in range, marking what changes as dirty.
+    fn set_selected(&mut self, index: usize) {
+        if index == self.selected {
+            return;
+        }
+        self.bar_dirty |= bar_rows(index, N) != bar_rows(self.selected, N);
+        self.selected = index;
+        self.offset = 0;
+        self.text_dirty = true;
+    }
+
+    /// Returns `true` if the next [`Self::render`] will draw anything.
+    ///
+    /// Stays `true` while the selected item scrolls.
+    pub fn is_dirty(&self) -> bool {
+        self.text_dirty || self.bar_dirty
+    }
+
+    /// Draws the parts of the menu that changed into the framebuffer and flushes
+    /// it, returning whether anything was drawn.
+    ///
+    /// Only the text area and the position bar are drawn, each only when it
+    /// changed; other pixels of the framebuffer are kept. Text uses the matrix's
+    /// default font and character map.
+    pub fn render<SPI: SpiDevice, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+    ) -> Result<bool> {
+        if !self.is_dirty() {
+            return Ok(false);
+        }
+
+        let width = matrix.width_pixels();
+        if self.text_dirty {
+            self.draw_text(matrix, width - 2)?;
+        }
+        if self.bar_dirty {
+            let (start, end) = bar_rows(self.selected, N);
+            let bits = (start..end).fold(0u8, |bits, row| bits | 1 << row);
+            matrix.write_column(width - 1, bits)?;
+            self.bar_dirty = false;
+        }
+        matrix.flush()?;
+        Ok(true)
+    }
+
+    /// Draws the selected item into columns `0..area`, advancing its scroll
+    /// position if it does not fit.
+    fn draw_text<SPI: SpiDevice, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        area: usize,
+    ) -> Result<()> {
+        let item = self.selected_item().unwrap_or("");
+        let font = matrix.default_font();
+        let scrolls = text_width(item, font, 0) > area;
+        let config = ScrollConfig {
+            loop_text: scrolls,
+            loop_padding: SCROLL_GAP,
+            ..ScrollConfig::default()
+        };
+        let mut scroller = ScrollingText::new(item, font, config)
+            .with_char_map(matrix.char_map())
+            .with_style(self.style)
+            .with_visible_width(area);
+        scroller.set_offset(self.offset);
+
+        let background = if self.inverted { 0xFF } else { 0x00 };
+        for x in (0..area).step_by(8) {
+            let frame = scroller.get_frame_at(x as i32)?;
+            for col in x..(x + 8).min(area) {
+                let mut bits = 0u8;
+                for (row, &data) in frame.data().iter().enumerate() {
+                    bits |= ((data >> (7 - (col - x))) & 1) << row;
+                }
+                matrix.write_column(col, bits ^ background)?;
+            }
+        }
+        // The gap between the text and the position bar
+        matrix.write_column(area, 0)?;
+
+        if scrolls {
+            scroller.step();
+            self.offset = scroller.offset();
+        } else {
+            self.text_dirty = false;
+        }
+        Ok(())
+    }
+}
+
+/// Rows `start..end` of the position bar for item `index` of `count`.
+///
+/// The rows of all items tile the column; with more than 8 items, neighbours
+/// share a row.
+fn bar_rows(index: usize, count: usize) -> (usize, usize) {
+    if count == 0 {
+        return (0, 0);
+    }
+    let start = index * 8 / count;
+    let end = ((index + 1) * 8 / count).max(start + 1);
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    #[cfg(feature = "font-ascii-full")]
+    use crate::led_matrix::display::Matrix4;
+    use crate::led_matrix::display::SingleMatrix;
+    use crate::test_util::FrameRecorder;
+
+    #[cfg(feature = "font-ascii-full")]
+    const ITEMS: [&str; 16] = [
+        "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P",
+    ];
+
+    #[cfg(feature = "font-ascii-full")]
+    fn columns<SPI: SpiDevice>(matrix: &Matrix4<SPI>) -> Vec<u8> {
+        (0..32).map(|x| matrix.get_column(x).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_bar_rows() {
+        assert_eq!(bar_rows(0, 4), (0, 2));
+        assert_eq!(bar_rows(3, 4), (6, 8));
+        assert_eq!(bar_rows(0, 16), (0, 1));
+        assert_eq!(bar_rows(1, 16), (0, 1));
+        assert_eq!(bar_rows(2, 16), (1, 2));
+        assert_eq!(bar_rows(2, 3), (5, 8));
+    }
+
+    #[test]
+    #[cfg(feature = "font-ascii-full")]
+    fn test_menu_redraws_changed_regions() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        let mut menu = Menu::new(ITEMS);
+
+        assert!(menu.render(&mut matrix).unwrap());
+        assert!(!menu.is_dirty());
+        assert!(!menu.render(&mut matrix).unwrap());
+        let first = columns(&matrix);
+        assert_eq!(first[31], 0b0000_0001);
+        assert_eq!(first[30], 0);
+        // Inverted: the background around the glyph is lit
+        assert_eq!(first[29], 0xFF);
+
+        // Items 0 and 1 share the top row of the bar, which is not redrawn; a
+        // pixel drawn into the bar column by hand shows this
+        matrix.write_column(31, 0b1000_0001).unwrap();
+        menu.select_next();
+        assert_eq!(menu.selected_item(), Some("B"));
+        menu.render(&mut matrix).unwrap();
+        let second = columns(&matrix);
+        assert_eq!(second[31], 0b1000_0001);
+        assert_eq!(second[30], 0);
+        assert_ne!(second[..8], first[..8]);
+        assert_eq!(second[8..30], first[8..30]);
+
+        // Item 2 moves down the bar
+        menu.select_next();
+        menu.render(&mut matrix).unwrap();
+        let third = columns(&matrix);
+        assert_eq!(third[31], 0b0000_0010);
+
+        // Moving back and forth wraps around
+        menu.select_prev();
+        menu.select_prev();
+        menu.select_prev();
+        assert_eq!(menu.selected(), 15);
+        assert_eq!(menu.select(16), Err(Error::ValueOutOfRange));
+    }
+
+    #[test]
+    fn test_menu_scrolls_long_item() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(1).unwrap();
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+        let mut menu = Menu::new(["AB", "C"]).with_inverted(false);
+
+        // Six columns of text area on a single device, too narrow for a glyph
+        menu.render(&mut matrix).unwrap();
+        assert!(menu.is_dirty());
+        let second_column = matrix.get_column(1).unwrap();
+        assert_ne!(second_column, 0);
+
+        // Each render moves the text one column to the left
+        menu.render(&mut matrix).unwrap();
+        assert_eq!(matrix.get_column(0).unwrap(), second_column);
+        assert!(menu.is_dirty());
+    }
+}
@@ -1,9 +1,51 @@
 //! LED matrix display implementation
 
+#![cfg_attr(not(test), deny(clippy::indexing_slicing))]
+
+pub mod animation;
 pub mod buffer;
+pub mod builder;
+pub mod clock;
+pub mod compressed;
+pub mod cursor;
+pub mod demo;
 pub mod display;
+pub mod effects;
 pub mod fonts;
+pub mod glyph;
+pub mod gray;
+#[cfg(feature = "graphics")]
+pub mod image;
+pub mod layout;
+mod life;
+pub mod menu;
+pub mod pages;
 pub mod scroll;
+pub mod spinner;
+mod splash;
+pub mod style;
 pub mod symbols;
+pub mod timer;
+pub mod transform;
+mod vu;
+pub mod wide;
+pub mod wizard;
 
-pub use display::LedMatrix;
+pub use animation::Animation;
+pub use builder::{LedMatrixBuilder, MatrixConfig};
+pub use cursor::CursorStyle;
+pub use demo::DemoStep;
+#[cfg(feature = "stats")]
+pub use display::FlushStats;
+pub use display::{LedMatrix, PowerPolicy};
+pub use effects::XorShift32;
+pub use glyph::Glyph;
+pub use menu::Menu;
+pub use gray::GrayLevel;
+pub use pages::{Page, Pages, Transition};
+pub use spinner::Spinner;
+pub use style::TextStyle;
+pub use timer::FrameTimer;
+pub use transform::{Orientation, PixelLayout, Rotation};
+pub use wide::WideBuffer;
+pub use wizard::{Answer, Prompt};
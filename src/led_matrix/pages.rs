@@ -0,0 +1,314 @@
+//! Status pages shown one after another
+//!
+//! [`Pages`] cycles through a fixed set of [`Page`]s, either blocking with
+//! [`Pages::run`] or from a main loop with [`Pages::tick`]. A page is a closure that
+//! draws with any [`LedMatrix`] API, or a prebuilt framebuffer.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let mut clock = |m: &mut Matrix4<_>| m.draw_text(&time_text());
+//! let mut pages = [Page::Draw(&mut clock), Page::Frame(&LOGO)];
+//! Pages::new(&mut pages)
+//!     .with_transition(Transition::Fade { step_ms: 30 })
+//!     .run(&mut matrix, &mut delay, 3000, || true)?;
+//! ```
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{Result, led_matrix::LedMatrix};
+
+/// Closure drawing a page, see [`Page::Draw`].
+pub type DrawPage<'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> =
+    &'a mut dyn FnMut(&mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>) -> Result<()>;
+
+/// One page of a [`Pages`] rotation.
+pub enum Page<'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> {
+    /// Called every time the page comes up. It must show its content itself,
+    /// e.g. with [`LedMatrix::draw_text`] or by drawing and calling
+    /// [`LedMatrix::flush`].
+    Draw(DrawPage<'a, SPI, BUFFER_LENGTH, DEVICE_COUNT>),
+    /// Copied into the framebuffer and flushed.
+    Frame(&'a [u8; BUFFER_LENGTH]),
+}
+
+/// How one page gives way to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Transition {
+    /// The next page replaces the previous one at once.
+    #[default]
+    Cut,
+    /// The intensity steps down to 0, the next page is drawn, and the intensity
+    /// steps back up to each device's level, one level every `step_ms`.
+    ///
+    /// Works with every page since only the intensity changes.
+    Fade {
+        /// Time spent on each intensity level.
+        step_ms: u32,
+    },
+}
+
+/// A rotation of [`Page`]s.
+pub struct Pages<'p, 'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> {
+    pages: &'p mut [Page<'a, SPI, BUFFER_LENGTH, DEVICE_COUNT>],
+    transition: Transition,
+    /// Page on display.
+    current: usize,
+    /// When [`Pages::tick`] last showed a page, `None` before the first tick.
+    shown_at_ms: Option<u32>,
+}
+
+impl<'p, 'a, SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    Pages<'p, 'a, SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Creates a rotation starting at the first page, switching with a cut.
+    pub fn new(pages: &'p mut [Page<'a, SPI, BUFFER_LENGTH, DEVICE_COUNT>]) -> Self {
+        Self {
+            pages,
+            transition: Transition::Cut,
+            current: 0,
+            shown_at_ms: None,
+        }
+    }
+
+    /// Sets how [`Self::run`] switches between pages.
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Index of the page on display, or of the first page before any is shown.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Shows each page for `page_ms` in turn, for as long as `keep_going` returns
+    /// `true`.
+    ///
+    /// `keep_going` is checked before every page. The first page appears with a
+    /// cut and later ones with the configured [`Transition`]. The idle hook, if
+    /// any, runs before every wait. Returns at once if there are no pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a page or a write. A fade restores the
+    /// intensities before returning.
+    pub fn run<D: DelayNs, F: FnMut() -> bool>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        delay: &mut D,
+        page_ms: u32,
+        mut keep_going: F,
+    ) -> Result<()> {
+        if self.pages.is_empty() {
+            return Ok(());
+        }
+
+        let mut first = true;
+        while keep_going() {
+            if first {
+                self.show(matrix)?;
+                first = false;
+            } else {
+                self.current = (self.current + 1) % self.pages.len();
+                self.switch(matrix, delay)?;
+            }
+            matrix.driver().idle();
+            delay.delay_ms(page_ms);
+        }
+        Ok(())
+    }
+
+    /// Non-blocking variant of [`Self::run`] for a main loop or timer.
+    ///
+    /// The first call shows the current page. Later calls switch to the next page
+    /// once `page_ms` has passed since the last switch, as measured by `now_ms`,
+    /// which may wrap. Pages always switch with a cut, since a transition would
+    /// block. Returns `true` if a page was shown.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// loop {
+    ///     pages.tick(&mut matrix, millis(), 3000)?;
+    ///     // other work
+    /// }
+    /// ```
+    pub fn tick(
+        &mut self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        now_ms: u32,
+        page_ms: u32,
+    ) -> Result<bool> {
+        if self.pages.is_empty() {
+            return Ok(false);
+        }
+
+        match self.shown_at_ms {
+            None => {}
+            Some(shown_at) if now_ms.wrapping_sub(shown_at) >= page_ms => {
+                self.current = (self.current + 1) % self.pages.len();
+            }
+            Some(_) => return Ok(false),
+        }
+        self.show(matrix)?;
+        self.shown_at_ms = Some(now_ms);
+        Ok(true)
+    }
+
+    /// Shows the current page with the configured transition.
+    fn switch<D: DelayNs>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        delay: &mut D,
+    ) -> Result<()> {
+        let Transition::Fade { step_ms } = self.transition else {
+            return self.show(matrix);
+        };
+
+        let mut target = [0; DEVICE_COUNT];
+        for (device_index, level) in target.iter_mut().enumerate() {
+            *level = matrix.driver().intensity(device_index).unwrap_or(0);
+        }
+        let faded = self.fade(matrix, delay, step_ms, &target);
+        let restored = matrix.set_intensity_profile(&target);
+        faded.and(restored)
+    }
+
+    /// Fades out, shows the current page, and fades back in to `target`.
+    fn fade<D: DelayNs>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        delay: &mut D,
+        step_ms: u32,
+        target: &[u8; DEVICE_COUNT],
+    ) -> Result<()> {
+        let peak = target.iter().copied().max().unwrap_or(0);
+
+        for level in (0..peak).rev() {
+            matrix.write_faded(level, Some(target))?;
+            matrix.driver().idle();
+            delay.delay_ms(step_ms);
+        }
+        self.show(matrix)?;
+        for level in 1..=peak {
+            matrix.driver().idle();
+            delay.delay_ms(step_ms);
+            matrix.write_faded(level, Some(target))?;
+        }
+        Ok(())
+    }
+
+    /// Draws the current page.
+    fn show(&mut self, matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>) -> Result<()> {
+        match self.pages.get_mut(self.current) {
+            Some(Page::Draw(draw)) => draw(matrix),
+            Some(Page::Frame(frame)) => {
+                matrix.framebuffer = **frame;
+                matrix.flush()
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::SingleMatrix;
+    use crate::led_matrix::fonts::STANDARD_LED_FONT;
+    use crate::registers::Register;
+    use crate::test_support::RecordingDelay;
+    use crate::test_util::{FrameRecorder, RegisterWrite};
+
+    const FULL: [u8; 64] = [1; 64];
+
+    fn rows(writes: &[RegisterWrite]) -> Vec<u8> {
+        writes
+            .iter()
+            .filter(|&&(_, register, _)| register.is_digit())
+            .map(|&(_, _, data)| data)
+            .collect()
+    }
+
+    #[test]
+    fn test_run_alternates_pages() {
+        let mut recorder = FrameRecorder::new();
+        let mut matrix = SingleMatrix::from_driver(Max7219::new(&mut recorder)).unwrap();
+        let mut draws = 0;
+        let mut letter = |m: &mut SingleMatrix<_>| {
+            draws += 1;
+            m.draw_text("A")
+        };
+        let mut pages = [Page::Draw(&mut letter), Page::Frame(&FULL)];
+
+        let mut delay = RecordingDelay::default();
+        let mut shown = 0;
+        Pages::new(&mut pages)
+            .run(&mut matrix, &mut delay, 1000, || {
+                shown += 1;
+                shown <= 4
+            })
+            .unwrap();
+        assert_eq!(draws, 2);
+
+        let a = STANDARD_LED_FONT.get_char('A');
+        let expected: Vec<u8> = [a, [0xFF; 8], a, [0xFF; 8]].concat();
+        assert_eq!(rows(&recorder.writes()), expected);
+        assert_eq!(delay.waits_ms, [1000; 4]);
+    }
+
+    #[test]
+    fn test_run_with_fade() {
+        let mut recorder = FrameRecorder::new();
+        let mut matrix = SingleMatrix::from_driver(Max7219::new(&mut recorder)).unwrap();
+        matrix.set_intensity_profile(&[2]).unwrap();
+        let mut blank = |m: &mut SingleMatrix<_>| m.clear_all();
+        let mut pages = [Page::Draw(&mut blank), Page::Frame(&FULL)];
+
+        let mut delay = RecordingDelay::default();
+        let mut shown = 0;
+        Pages::new(&mut pages)
+            .with_transition(Transition::Fade { step_ms: 20 })
+            .run(&mut matrix, &mut delay, 500, || {
+                shown += 1;
+                shown <= 2
+            })
+            .unwrap();
+
+        let intensity = |level| vec![(0, Register::Intensity, level)];
+        let frames = recorder.frames();
+        // Profile, first page (8 rows), then the fade around the second page
+        assert_eq!(frames[0], intensity(2));
+        assert_eq!(frames[9..11], [intensity(1), intensity(0)]);
+        // Power-on before the flush of the framebuffer page
+        assert_eq!(frames[11], vec![(0, Register::Shutdown, 1)]);
+        assert_eq!(frames[20..], [intensity(1), intensity(2), intensity(2)]);
+        assert_eq!(delay.waits_ms, [500, 20, 20, 20, 20, 500]);
+    }
+
+    #[test]
+    fn test_tick() {
+        let mut recorder = FrameRecorder::new();
+        let mut matrix = SingleMatrix::from_driver(Max7219::new(&mut recorder)).unwrap();
+        let mut letter = |m: &mut SingleMatrix<_>| m.draw_text("A");
+        let mut pages = [Page::Draw(&mut letter), Page::Frame(&FULL)];
+        let mut pages = Pages::new(&mut pages);
+
+        assert!(pages.tick(&mut matrix, u32::MAX - 100, 1000).unwrap());
+        assert_eq!(pages.current(), 0);
+        assert!(!pages.tick(&mut matrix, 500, 1000).unwrap());
+        // 1000 ms after the first tick, across the wrap
+        assert!(pages.tick(&mut matrix, 899, 1000).unwrap());
+        assert_eq!(pages.current(), 1);
+        assert!(pages.tick(&mut matrix, 1899, 1000).unwrap());
+        assert_eq!(pages.current(), 0);
+
+        let mut empty: [Page<'_, &mut FrameRecorder, 64, 1>; 0] = [];
+        assert!(!Pages::new(&mut empty).tick(&mut matrix, 0, 1000).unwrap());
+    }
+}
@@ -4,15 +4,30 @@
 
 use crate::{
     Result,
-    led_matrix::{buffer::MatrixBuffer, fonts::LedFont},
+    led_matrix::{
+        buffer::MatrixBuffer,
+        fonts::{CharMap, LedFont, text_width},
+        style::TextStyle,
+    },
 };
 
 /// Configuration for scrolling text behavior
-#[derive(Clone, Copy)]
+///
+/// With the `serde` feature the config can be persisted (e.g. with `postcard`).
+/// Fields missing from self-describing formats fall back to their [`Default`] values,
+/// so configs saved by older releases keep loading when fields are added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ScrollConfig {
     /// Delay between scroll steps in nanoseconds
     pub step_delay_ns: u32,
-    /// Number of pixels to scroll per step (usually 1 for smooth scrolling)
+    /// Number of pixels to scroll per step (usually 1 for smooth scrolling).
+    /// `0` is treated as `1`, so a scroll always makes progress.
     pub pixels_per_step: u8,
     /// Whether to loop the text continuously
     pub loop_text: bool,
@@ -32,12 +47,21 @@ impl Default for ScrollConfig {
 }
 
 /// Scrolling text renderer for LED matrix displays
+///
+/// The scroll offset is the text column shown at the left edge of the display.
+/// It always stays within `-visible_width..cycle_width`: from the text just
+/// off the right edge to the end of one pass, which is the text width including
+/// the loop padding when looping, and the text width plus 8 otherwise.
 pub struct ScrollingText<'a> {
     text: &'a str,
     font: &'a LedFont,
     config: ScrollConfig,
+    char_map: CharMap,
+    style: TextStyle,
     text_width: usize,
-    pub(crate) current_offset: i32,
+    /// Columns the text is shown on, see [`Self::with_visible_width`].
+    visible_width: usize,
+    current_offset: i32,
 }
 
 impl<'a> ScrollingText<'a> {
@@ -47,7 +71,10 @@ impl<'a> ScrollingText<'a> {
             text,
             font,
             config,
+            char_map: CharMap::default(),
+            style: TextStyle::Normal,
             text_width: 0,
+            visible_width: 8,
             current_offset: 0,
         };
         scroller.calculate_text_width();
@@ -59,37 +86,70 @@ impl<'a> ScrollingText<'a> {
         Self::new(text, font, ScrollConfig::default())
     }
 
+    /// Applies `char_map` to every character before it is looked up in the font.
+    pub fn with_char_map(mut self, char_map: CharMap) -> Self {
+        self.char_map = char_map;
+        self
+    }
+
+    /// Draws the text with `style`, e.g. outlined. Defaults to [`TextStyle::Normal`].
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets how many columns the text is shown on, e.g. 32 for a chain of four
+    /// devices showing [`Self::get_frame_at`] `0`, `8`, `16` and `24`.
+    ///
+    /// Widths below 8, the width of one frame, are raised to 8. The offset is
+    /// clamped to the new range.
+    pub fn with_visible_width(mut self, width: usize) -> Self {
+        self.visible_width = width.max(8);
+        self.set_offset(self.current_offset);
+        self
+    }
+
     /// Calculate the width
     fn calculate_text_width(&mut self) {
-        self.text_width = self.text.chars().count() * 8;
+        self.text_width = text_width(self.text, self.font, 0);
 
         // Add loop padding if configured
         if self.config.loop_text {
-            self.text_width += self.config.loop_padding as usize;
+            self.text_width = self
+                .text_width
+                .saturating_add(usize::from(self.config.loop_padding));
         }
     }
 
     /// Get the current 8x8 frame data based on the scroll offset.
     /// This returns what should be displayed on the LED matrix at the current scroll position.
     pub fn get_frame(&self) -> Result<MatrixBuffer> {
-        let mut buffer = MatrixBuffer::new();
+        self.get_frame_at(0)
+    }
+
+    /// Get the 8x8 frame `shift` columns right of the current scroll offset,
+    /// without moving it.
+    ///
+    /// A chain of devices shows the frames at `0`, `8`, `16` and so on.
+    pub fn get_frame_at(&self, shift: i32) -> Result<MatrixBuffer> {
+        let lit =
+            |col: isize, row: isize| (0..8).contains(&row) && self.pixel_on(col, row as usize);
 
-        for row in 0..8 {
-            let mut row_data = 0u8;
+        let mut rows = [0u8; 8];
+        for (row, row_data) in rows.iter_mut().enumerate() {
             for col in 0..8 {
-                if self.pixel_on(col, row) {
-                    row_data |= 1 << (7 - col);
+                if self.style.pixel_on(lit, shift as isize + col, row as isize) {
+                    *row_data |= 1 << (7 - col);
                 }
             }
-            buffer.set_row(row as u8, row_data)?;
         }
 
-        Ok(buffer)
+        Ok(MatrixBuffer::from_data(rows))
     }
     /// Return true if the pixel at (source_col, row) should be on
-    fn pixel_on(&self, source_col: usize, row: usize) -> bool {
+    fn pixel_on(&self, source_col: isize, row: usize) -> bool {
         // Calculate the actual column position considering the offset
-        let actual_col = self.current_offset as isize + source_col as isize;
+        let actual_col = self.current_offset as isize + source_col;
 
         // If the actual column is negative, no pixel should be on
         if actual_col < 0 {
@@ -98,6 +158,11 @@ impl<'a> ScrollingText<'a> {
 
         let col = actual_col as usize;
 
+        // Empty text without loop padding has nothing to wrap around
+        if self.text_width == 0 {
+            return false;
+        }
+
         // If outside text width and not looping, no pixel
         if col >= self.text_width && !self.config.loop_text {
             return false;
@@ -111,7 +176,7 @@ impl<'a> ScrollingText<'a> {
         };
 
         // Only actual text columns (exclude padding)
-        let text_pixels = self.text.chars().count() * 8;
+        let text_pixels = text_width(self.text, self.font, 0);
         if final_col >= text_pixels {
             return false;
         }
@@ -120,27 +185,41 @@ impl<'a> ScrollingText<'a> {
         let bit_index = final_col % 8;
 
         // Safe since char_index < char count
-        let ch = self.text.chars().nth(char_index).unwrap_or('?');
-        let bitmap = self.font.get_char(ch);
-        let row_data = bitmap[row];
+        let ch = self
+            .font
+            .visible_chars(self.text)
+            .nth(char_index)
+            .unwrap_or('?');
+        let bitmap = self.char_map.bitmap(ch, self.font);
+        let Some(&row_data) = bitmap.get(row) else {
+            return false;
+        };
 
         // Check bit (left to right)
         (row_data >> (7 - bit_index)) & 1 != 0
     }
 
     /// Advance the scroll position by the configured step size
+    ///
+    /// Returns `false` once a non-looping scroll is done; for empty text that is
+    /// the first step, as there is nothing to scroll by. Looping text always
+    /// continues, showing a blank display if the text is empty.
     pub fn step(&mut self) -> bool {
-        self.current_offset += self.config.pixels_per_step as i32;
+        if self.text.is_empty() && !self.config.loop_text {
+            return false;
+        }
+        let step = i32::from(self.config.pixels_per_step.max(1));
+        let offset = self.current_offset.saturating_add(step);
+        let cycle_width = self.cycle_width();
 
         if self.config.loop_text {
             // Reset when we've scrolled past the text width
-            if self.current_offset >= self.text_width as i32 {
-                self.current_offset = 0;
-            }
+            self.current_offset = if offset >= cycle_width { 0 } else { offset };
             true // Always continue when looping
         } else {
-            // Stop when text has completely scrolled off screen
-            self.current_offset < (self.text_width as i32 + 8)
+            // Stop when text has completely scrolled off screen, and stay there
+            self.current_offset = offset.min(cycle_width - 1);
+            offset < cycle_width
         }
     }
 
@@ -153,6 +232,50 @@ impl<'a> ScrollingText<'a> {
     pub fn offset(&self) -> i32 {
         self.current_offset
     }
+
+    /// Moves the scroll position to `offset`, kept within
+    /// `-visible_width..cycle_width`.
+    ///
+    /// Offsets left of `-visible_width` are clamped to it. Past the end, looping
+    /// text wraps around to the same point of a later pass, while other text stops
+    /// at the last, blank, position.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use max7219_display::led_matrix::{fonts::STANDARD_LED_FONT, scroll::ScrollingText};
+    ///
+    /// // "Hi" is 16 columns plus 16 of loop padding
+    /// let mut scroller = ScrollingText::new_default("Hi", &STANDARD_LED_FONT);
+    /// scroller.set_offset(40);
+    /// assert_eq!(scroller.offset(), 8);
+    /// scroller.set_offset(-100);
+    /// assert_eq!(scroller.offset(), -8);
+    /// ```
+    pub fn set_offset(&mut self, offset: i32) {
+        let cycle_width = self.cycle_width();
+        let lowest = -i32::try_from(self.visible_width).unwrap_or(i32::MAX);
+        self.current_offset = if offset < lowest {
+            lowest
+        } else if offset < cycle_width {
+            offset
+        } else if self.config.loop_text {
+            offset % cycle_width
+        } else {
+            cycle_width - 1
+        };
+    }
+
+    /// End of the offset range: one pass of looping text, or until non-looping
+    /// text is gone.
+    fn cycle_width(&self) -> i32 {
+        let text_width = i32::try_from(self.text_width).unwrap_or(i32::MAX);
+        if self.config.loop_text {
+            text_width.max(1)
+        } else {
+            text_width.saturating_add(8)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -274,17 +397,133 @@ mod tests {
         };
         let mut scroller = ScrollingText::new("01", &TEST_FONT, config);
 
-        // Text width is 24 (2 chars * 8 + 8 padding), so scrolling stops when offset >= 24
+        // Text width is 16 (2 chars * 8), so scrolling stops when offset >= 16 + 8
         scroller.current_offset = 22;
         assert!(scroller.step());
         assert_eq!(scroller.current_offset, 23);
 
         assert!(!scroller.step()); // Now completely off screen
-        assert_eq!(scroller.current_offset, 24);
+        assert_eq!(scroller.current_offset, 23);
 
-        // Test that it stays false
+        // Test that it stays false, at the last position
         assert!(!scroller.step()); // Still off screen
-        assert_eq!(scroller.current_offset, 25);
+        assert_eq!(scroller.current_offset, 23);
+    }
+
+    #[test]
+    fn test_set_offset_keeps_range() {
+        let config = ScrollConfig {
+            loop_text: false,
+            pixels_per_step: 3,
+            ..Default::default()
+        };
+        let mut scroller = ScrollingText::new("01", &TEST_FONT, config);
+        let in_range = |s: &ScrollingText<'_>| (-8..24).contains(&s.offset());
+
+        scroller.set_offset(-50);
+        assert_eq!(scroller.offset(), -8);
+        scroller.set_offset(1000);
+        assert_eq!(scroller.offset(), 23);
+        assert!(scroller.get_frame().unwrap().data().iter().all(|&r| r == 0));
+        assert!(!scroller.step());
+        assert!(in_range(&scroller));
+
+        scroller.set_offset(5);
+        while scroller.step() {
+            assert!(in_range(&scroller));
+        }
+        assert!(in_range(&scroller));
+
+        // A wider display allows the text further off its right edge
+        let mut wide = ScrollingText::new("01", &TEST_FONT, config).with_visible_width(32);
+        wide.set_offset(-50);
+        assert_eq!(wide.offset(), -32);
+    }
+
+    #[test]
+    fn test_set_offset_wraps_looping_text() {
+        // 16 columns of text and 16 of padding
+        let mut scroller = ScrollingText::new_default("01", &TEST_FONT);
+        scroller.set_offset(33);
+        assert_eq!(scroller.offset(), 1);
+        scroller.set_offset(31);
+        assert_eq!(scroller.offset(), 31);
+        assert!(scroller.step());
+        assert_eq!(scroller.offset(), 0);
+
+        scroller.set_offset(-9);
+        assert_eq!(scroller.offset(), -8);
+        for _ in 0..100 {
+            assert!(scroller.step());
+            assert!((-8..32).contains(&scroller.offset()));
+        }
+
+        // Empty looping text still has a valid position
+        let mut empty = ScrollingText::new(
+            "",
+            &TEST_FONT,
+            ScrollConfig {
+                loop_padding: 0,
+                ..Default::default()
+            },
+        );
+        empty.set_offset(7);
+        assert_eq!(empty.offset(), 0);
+        assert!(empty.step());
+        assert_eq!(empty.offset(), 0);
+    }
+
+    #[test]
+    fn test_get_frame_at() {
+        let mut scroller = ScrollingText::new_default("01", &TEST_FONT);
+        let second = scroller.get_frame_at(8).unwrap();
+        scroller.set_offset(8);
+        assert_eq!(scroller.get_frame().unwrap().data(), second.data());
+        assert_eq!(scroller.offset(), 8);
+    }
+
+    #[test]
+    fn test_empty_text() {
+        let config = ScrollConfig {
+            loop_text: false,
+            ..Default::default()
+        };
+        let mut scroller = ScrollingText::new("", &TEST_FONT, config);
+        scroller.reset();
+        assert_eq!(scroller.get_frame().unwrap().data(), &[0; 8]);
+        assert!(!scroller.step());
+    }
+
+    #[test]
+    fn test_empty_text_looping_without_padding() {
+        let config = ScrollConfig {
+            loop_padding: 0,
+            ..Default::default()
+        };
+        let mut scroller = ScrollingText::new("", &TEST_FONT, config);
+        assert_eq!(scroller.text_width, 0);
+        for _ in 0..3 {
+            assert!(scroller.step());
+            assert_eq!(scroller.get_frame().unwrap().data(), &[0; 8]);
+        }
+    }
+
+    #[test]
+    fn test_whitespace_and_unsupported_text() {
+        let config = ScrollConfig {
+            loop_text: false,
+            ..Default::default()
+        };
+        // A multi-byte space is still a single 8-column glyph
+        let scroller = ScrollingText::new(" \u{3000}", &TEST_FONT, config);
+        assert_eq!(scroller.text_width, 16);
+
+        let scroller = ScrollingText::new("é", &TEST_FONT, config);
+        assert_eq!(scroller.text_width, 8);
+        assert_eq!(
+            scroller.get_frame().unwrap().data(),
+            &crate::led_matrix::fonts::FONT8X8_UNKNOWN
+        );
     }
 
     #[test]
@@ -370,6 +609,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_combining_marks_scroll_like_plain_text() {
+        use crate::led_matrix::fonts::STANDARD_LED_FONT;
+
+        let mut plain = ScrollingText::new_default("e0", &STANDARD_LED_FONT);
+        let mut combining = ScrollingText::new_default("e\u{301}0", &STANDARD_LED_FONT);
+        assert_eq!(combining.text_width, plain.text_width);
+
+        for offset in -8..combining.cycle_width() {
+            combining.set_offset(offset);
+            plain.set_offset(offset);
+            assert_eq!(
+                combining.get_frame().unwrap().data(),
+                plain.get_frame().unwrap().data()
+            );
+        }
+    }
+
     #[test]
     fn test_get_frame() {
         let scroller = ScrollingText::new_default("0", &TEST_FONT);
@@ -418,4 +675,91 @@ mod tests {
             assert_eq!(actual_row, expected_row, "Row {row_index} mismatch");
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_scroll_config_postcard_round_trip() {
+        let config = ScrollConfig {
+            step_delay_ns: 50_000_000,
+            pixels_per_step: 2,
+            loop_text: false,
+            loop_padding: 4,
+        };
+
+        let bytes = postcard::to_allocvec(&config).expect("serialize");
+        let decoded: ScrollConfig = postcard::from_bytes(&bytes).expect("deserialize");
+        assert_eq!(decoded, config);
+
+        let default_bytes = postcard::to_allocvec(&ScrollConfig::default()).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<ScrollConfig>(&default_bytes).unwrap(),
+            ScrollConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_zero_pixels_per_step_still_scrolls() {
+        let config = ScrollConfig {
+            pixels_per_step: 0,
+            loop_text: false,
+            ..Default::default()
+        };
+        let mut scroller = ScrollingText::new("1", &TEST_FONT, config);
+        scroller.reset();
+
+        let mut steps = 1;
+        while scroller.step() {
+            steps += 1;
+            assert!(steps <= 100, "scroll never ends");
+        }
+        // One column at a time from -8 to 15, then the step that ends the scroll
+        assert_eq!(steps, 24);
+    }
+
+    #[test]
+    fn test_width_math_saturates() {
+        // A 16-bit usize overflows on 8192 characters; huge spacing and visible
+        // widths reach the same boundary on any target
+        assert_eq!(text_width("10", &TEST_FONT, usize::MAX), usize::MAX);
+
+        let config = ScrollConfig {
+            pixels_per_step: u8::MAX,
+            loop_padding: u8::MAX,
+            ..Default::default()
+        };
+        let mut scroller =
+            ScrollingText::new("1", &TEST_FONT, config).with_visible_width(usize::MAX);
+        scroller.set_offset(i32::MIN);
+        assert_eq!(scroller.offset(), -i32::MAX);
+        assert!(scroller.step());
+        scroller.set_offset(i32::MAX);
+        assert!(scroller.step());
+        assert!(scroller.offset() < 8 + 255);
+    }
+
+    #[test]
+    fn test_styled_frames() {
+        use crate::led_matrix::fonts::STANDARD_LED_FONT;
+
+        let config = ScrollConfig {
+            loop_text: false,
+            ..Default::default()
+        };
+        let style = TextStyle::Shadow { dx: 1, dy: 1 };
+        let scroller = ScrollingText::new("L", &STANDARD_LED_FONT, config).with_style(style);
+        let expected = style.render(&[STANDARD_LED_FONT.get_char('L')], 0);
+        assert_eq!(scroller.get_frame().unwrap().data(), &expected);
+
+        // The outline of the glyph's top row includes the diagonal below it
+        let scroller = ScrollingText::new("1", &TEST_FONT, config).with_style(TextStyle::Outline);
+        let frame = scroller.get_frame().unwrap();
+        assert_eq!(frame.data()[0], 0b01100100);
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format_impls() {
+        fn assert_format<T: defmt::Format>() {}
+        assert_format::<ScrollConfig>();
+    }
 }
@@ -0,0 +1,195 @@
+//! Busy indicator for one device while the application does slow work
+//!
+//! Blocking code cannot animate in the background, so the spinner is driven by
+//! hand: call [`Spinner::tick`] between chunks of work, e.g. once per retry or
+//! per received packet, then [`Spinner::finish`] with the outcome.
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{
+    Result,
+    led_matrix::{LedMatrix, buffer::MatrixBuffer, glyph::Glyph},
+};
+
+/// Top-left corners of the 2x2 blocks the spinner runs through, clockwise
+/// from the top-left.
+const POSITIONS: [(u8, u8); 8] = [
+    (1, 1),
+    (3, 1),
+    (5, 1),
+    (5, 3),
+    (5, 5),
+    (3, 5),
+    (1, 5),
+    (1, 3),
+];
+
+/// Number of distinct spinner frames before the animation repeats.
+pub const FRAME_COUNT: usize = POSITIONS.len();
+
+/// Returns the rows of spinner `frame`: a 2x2 head with a 2x2 tail behind it.
+///
+/// `frame` wraps at [`FRAME_COUNT`].
+pub fn spinner_frame(frame: usize) -> [u8; 8] {
+    let mut rows = [0; 8];
+    for position in [frame, frame + FRAME_COUNT - 1] {
+        let Some(&(x, y)) = POSITIONS.get(position % FRAME_COUNT) else {
+            continue;
+        };
+        let bits = 0b1100_0000 >> x;
+        for row in rows.iter_mut().skip(y as usize).take(2) {
+            *row |= bits;
+        }
+    }
+    rows
+}
+
+/// Busy indicator on a single device.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut spinner = Spinner::start(&mut matrix, 0)?;
+/// let result = loop {
+///     match modem.poll() {
+///         Poll::Pending => spinner.tick(&mut matrix)?,
+///         Poll::Ready(result) => break result,
+///     }
+///     delay.delay_ms(100);
+/// };
+/// let symbol = if result.is_ok() { Symbol::Checkmark } else { Symbol::XMark };
+/// spinner.finish(&mut matrix, symbol)?;
+/// ```
+#[derive(Debug)]
+#[must_use = "call finish() to replace the spinner"]
+pub struct Spinner {
+    device_index: usize,
+    frame: usize,
+}
+
+impl Spinner {
+    /// Draws the first frame on `device_index` and returns the spinner.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`LedMatrix::write_buffer`], e.g. for an invalid device.
+    pub fn start<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>(
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        device_index: usize,
+    ) -> Result<Self>
+    where
+        SPI: SpiDevice,
+    {
+        let spinner = Self {
+            device_index,
+            frame: 0,
+        };
+        spinner.draw(matrix)?;
+        Ok(spinner)
+    }
+
+    /// Advances to the next frame and draws it.
+    pub fn tick<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        self.frame = (self.frame + 1) % FRAME_COUNT;
+        self.draw(matrix)
+    }
+
+    /// Replaces the spinner with `result`, typically [`Symbol::Checkmark`] or
+    /// [`Symbol::XMark`].
+    ///
+    /// [`Symbol::Checkmark`]: crate::led_matrix::symbols::Symbol::Checkmark
+    /// [`Symbol::XMark`]: crate::led_matrix::symbols::Symbol::XMark
+    pub fn finish<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>(
+        self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+        result: impl Glyph,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        matrix.draw_symbol(self.device_index, result)
+    }
+
+    /// Index of the frame currently shown, `0..FRAME_COUNT`.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    fn draw<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>(
+        &self,
+        matrix: &mut LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let frame = MatrixBuffer::from_data(spinner_frame(self.frame));
+        matrix.draw_symbol(self.device_index, frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "symbols-basic")]
+    use crate::led_matrix::symbols::Symbol;
+    use crate::{Error, test_util::FakeMatrix};
+
+    #[test]
+    fn test_spinner_frames() {
+        assert_eq!(
+            spinner_frame(0),
+            [
+                0,
+                0b0110_0000,
+                0b0110_0000,
+                0b0110_0000,
+                0b0110_0000,
+                0,
+                0,
+                0
+            ]
+        );
+        assert_eq!(
+            spinner_frame(1),
+            [0, 0b0111_1000, 0b0111_1000, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(spinner_frame(FRAME_COUNT), spinner_frame(0));
+    }
+
+    #[test]
+    #[cfg(feature = "symbols-basic")]
+    fn test_start_tick_finish() {
+        let mut matrix = FakeMatrix::<128, 2>::new().unwrap();
+        let mut spinner = Spinner::start(&mut matrix, 1).unwrap();
+        assert_eq!(matrix.frame().device_rows(1), Some(spinner_frame(0)));
+
+        let mut shown = vec![spinner_frame(0)];
+        for expected in 1..=FRAME_COUNT {
+            spinner.tick(&mut matrix).unwrap();
+            assert_eq!(spinner.frame(), expected % FRAME_COUNT);
+            shown.push(matrix.frame().device_rows(1).unwrap());
+        }
+        let expected: Vec<_> = (0..=FRAME_COUNT).map(spinner_frame).collect();
+        assert_eq!(shown, expected);
+        assert_eq!(matrix.frame().device_rows(0), Some([0; 8]));
+
+        spinner.finish(&mut matrix, Symbol::Checkmark).unwrap();
+        assert_eq!(
+            matrix.frame().device_rows(1),
+            Some(Symbol::Checkmark.to_bitmap())
+        );
+    }
+
+    #[test]
+    fn test_start_invalid_device() {
+        let mut matrix = FakeMatrix::<64, 1>::new().unwrap();
+        let result = Spinner::start(&mut matrix, 1);
+        assert!(matches!(result, Err(Error::InvalidDeviceIndex { .. })));
+    }
+}
@@ -0,0 +1,168 @@
+//! Boot splash: a logo shown for a moment at power-up
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    Result,
+    led_matrix::{LedMatrix, buffer::MatrixBuffer},
+    registers::Register,
+};
+
+/// Time spent on each intensity level while fading.
+const FADE_STEP_MS: u32 = 40;
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Shows `image` for `hold_ms`, then clears the display.
+    ///
+    /// `image[i]` is drawn on device `i` like [`Self::write_buffer`] does, so the
+    /// framebuffer is left untouched. With `fade`, the image fades in from dark to
+    /// each device's current intensity before the hold and back out after it, one
+    /// level every 40 ms; the intensities are restored once the display is cleared,
+    /// even if a write fails on the way.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// matrix.splash(&mut delay, &LOGO, 1500, true)?;
+    /// ```
+    pub fn splash<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        image: &[[u8; 8]; DEVICE_COUNT],
+        hold_ms: u32,
+        fade: bool,
+    ) -> Result<()> {
+        if !fade {
+            return self.show_splash(delay, image, hold_ms, None);
+        }
+
+        let mut target = [0; DEVICE_COUNT];
+        for (device_index, level) in target.iter_mut().enumerate() {
+            *level = self.driver().intensity(device_index).unwrap_or(0);
+        }
+
+        let shown = self.show_splash(delay, image, hold_ms, Some(&target));
+        let restored = self.set_intensity_profile(&target);
+        shown.and(restored)
+    }
+
+    fn show_splash<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        image: &[[u8; 8]; DEVICE_COUNT],
+        hold_ms: u32,
+        fade_to: Option<&[u8; DEVICE_COUNT]>,
+    ) -> Result<()> {
+        let peak = fade_to.map_or(0, |target| target.iter().copied().max().unwrap_or(0));
+
+        if fade_to.is_some() {
+            self.write_faded(0, fade_to)?;
+        }
+        for (device_index, rows) in image.iter().enumerate() {
+            self.write_buffer(device_index, &MatrixBuffer::from_data(*rows))?;
+        }
+
+        for level in 1..=peak {
+            self.write_faded(level, fade_to)?;
+            self.driver().idle();
+            delay.delay_ms(FADE_STEP_MS);
+        }
+
+        self.driver().idle();
+        delay.delay_ms(hold_ms);
+
+        for level in (0..peak).rev() {
+            self.write_faded(level, fade_to)?;
+            self.driver().idle();
+            delay.delay_ms(FADE_STEP_MS);
+        }
+
+        self.driver().clear_all()
+    }
+
+    /// Sets every device to `level`, capped at its fade target.
+    pub(super) fn write_faded(
+        &mut self,
+        level: u8,
+        fade_to: Option<&[u8; DEVICE_COUNT]>,
+    ) -> Result<()> {
+        let Some(target) = fade_to else {
+            return Ok(());
+        };
+        let mut ops = [(Register::NoOp, 0); DEVICE_COUNT];
+        for (op, &max) in ops.iter_mut().zip(target) {
+            *op = (Register::Intensity, level.min(max));
+        }
+        self.driver().write_all_registers(&ops)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::SingleMatrix;
+    use crate::test_support::RecordingDelay;
+    use crate::test_util::{FrameRecorder, RegisterWrite};
+
+    fn intensity(levels: [u8; 2]) -> Vec<RegisterWrite> {
+        vec![
+            (0, Register::Intensity, levels[0]),
+            (1, Register::Intensity, levels[1]),
+        ]
+    }
+
+    #[test]
+    fn test_splash_with_fade() {
+        let logo = [[0x18; 8], [0x81; 8]];
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut matrix: LedMatrix<_, 128, 2> = LedMatrix::from_driver(driver).unwrap();
+        matrix.set_intensity_profile(&[2, 1]).unwrap();
+
+        let mut delay = RecordingDelay::default();
+        matrix.splash(&mut delay, &logo, 1000, true).unwrap();
+
+        let mut expected = vec![intensity([2, 1]), intensity([0, 0])];
+        for (device_index, rows) in logo.iter().enumerate() {
+            for (digit_register, &data) in Register::digits().zip(rows) {
+                expected.push(vec![(device_index, digit_register, data)]);
+            }
+        }
+        // Fade in, hold, fade out; device 1 tops out at its own level
+        expected.extend([intensity([1, 1]), intensity([2, 1])]);
+        expected.extend([intensity([1, 1]), intensity([0, 0])]);
+        for digit_register in Register::digits() {
+            expected.push(vec![(0, digit_register, 0), (1, digit_register, 0)]);
+        }
+        expected.push(intensity([2, 1]));
+
+        assert_eq!(recorder.frames(), expected);
+        assert_eq!(delay.waits_ms, [40, 40, 1000, 40, 40]);
+    }
+
+    #[test]
+    fn test_splash_without_fade() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+
+        let mut delay = RecordingDelay::default();
+        matrix.splash(&mut delay, &[[0xFF; 8]], 500, false).unwrap();
+
+        let writes = recorder.writes();
+        assert_eq!(writes.len(), 16);
+        assert!(writes[..8].iter().all(|&(_, _, data)| data == 0xFF));
+        assert!(writes[8..].iter().all(|&(_, _, data)| data == 0x00));
+        assert!(
+            writes
+                .iter()
+                .all(|&(_, register, _)| register != Register::Intensity)
+        );
+        assert_eq!(delay.waits_ms, [500]);
+    }
+}
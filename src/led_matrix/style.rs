@@ -0,0 +1,136 @@
+//! Outline and drop-shadow effects for rendered text
+//!
+//! Styles are applied after the glyphs are rasterized, to the text as a whole,
+//! so an outline or shadow crosses from one character, and one device, into
+//! the next.
+
+/// How text is drawn by [`LedMatrix::draw_text`](crate::LedMatrix::draw_text),
+/// [`LedMatrix::scroll_text`](crate::LedMatrix::scroll_text) and
+/// [`ScrollingText`](crate::led_matrix::scroll::ScrollingText).
+///
+/// Behind a diffuser thin strokes blur together; showing the outline of the
+/// glyphs or an offset shadow instead can make text easier to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextStyle {
+    /// The glyphs as they are in the font.
+    #[default]
+    Normal,
+    /// A 1-pixel outline around the glyphs, with the glyphs themselves off.
+    ///
+    /// Lights every pixel that is off in the text but touches a lit pixel,
+    /// including diagonally.
+    Outline,
+    /// The glyphs plus a copy moved `dx` columns right and `dy` rows down,
+    /// XORed together: pixels lit in both are off.
+    Shadow {
+        /// Columns the shadow is moved right, negative for left.
+        dx: i8,
+        /// Rows the shadow is moved down, negative for up.
+        dy: i8,
+    },
+}
+
+impl TextStyle {
+    /// Returns whether the pixel at `(col, row)` is on once the style is applied
+    /// to text whose pixels are given by `lit`.
+    ///
+    /// `lit` is asked about neighbouring pixels too, and must return `false` for
+    /// positions outside the text.
+    pub(crate) fn pixel_on(
+        self,
+        lit: impl Fn(isize, isize) -> bool,
+        col: isize,
+        row: isize,
+    ) -> bool {
+        match self {
+            TextStyle::Normal => lit(col, row),
+            TextStyle::Outline => {
+                !lit(col, row) && (-1..=1).any(|dy| (-1..=1).any(|dx| lit(col + dx, row + dy)))
+            }
+            TextStyle::Shadow { dx, dy } => {
+                lit(col, row) != lit(col - isize::from(dx), row - isize::from(dy))
+            }
+        }
+    }
+
+    /// Applies the style to a strip of 8x8 glyphs placed side by side, returning
+    /// the rows of the glyph at `index`.
+    pub(crate) fn render(self, glyphs: &[[u8; 8]], index: usize) -> [u8; 8] {
+        if self == TextStyle::Normal {
+            return glyphs.get(index).copied().unwrap_or([0; 8]);
+        }
+        let lit = |col: isize, row: isize| {
+            if col < 0 || !(0..8).contains(&row) {
+                return false;
+            }
+            let col = col as usize;
+            glyphs
+                .get(col / 8)
+                .and_then(|rows| rows.get(row as usize))
+                .is_some_and(|bits| (bits >> (7 - col % 8)) & 1 != 0)
+        };
+
+        let mut rows = [0u8; 8];
+        for (row, data) in rows.iter_mut().enumerate() {
+            for bit in 0..8 {
+                let col = (index * 8 + bit) as isize;
+                if self.pixel_on(lit, col, row as isize) {
+                    *data |= 1 << (7 - bit);
+                }
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::led_matrix::fonts::STANDARD_LED_FONT;
+
+    fn glyph(ch: char) -> [u8; 8] {
+        STANDARD_LED_FONT.get_char(ch)
+    }
+
+    #[test]
+    #[cfg(feature = "font-ascii-full")]
+    fn test_outline_o() {
+        let outlined = TextStyle::Outline.render(&[glyph('O')], 0);
+        assert_eq!(
+            outlined,
+            [
+                0b01100110, 0b11011011, 0b10111101, 0b10100101, 0b10111101, 0b11011011, 0b01100110,
+                0b00111100,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "font-ascii-full")]
+    fn test_shadow_l() {
+        let shadowed = TextStyle::Shadow { dx: 1, dy: 1 }.render(&[glyph('L')], 0);
+        assert_eq!(
+            shadowed,
+            [
+                0b01110000, 0b00011000, 0b00110000, 0b00110000, 0b00110000, 0b00100000, 0b01100110,
+                0b00111111,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normal_is_unchanged() {
+        assert_eq!(TextStyle::Normal.render(&[glyph('L')], 0), glyph('L'));
+    }
+
+    #[test]
+    fn test_style_crosses_glyphs() {
+        // The shadow of the first glyph's right edge lands on the second glyph
+        let glyphs = [[0x01; 8], [0x00; 8]];
+        let style = TextStyle::Shadow { dx: 1, dy: 0 };
+        assert_eq!(style.render(&glyphs, 1), [0x80; 8]);
+        assert_eq!(style.render(&glyphs, 0), [0x01; 8]);
+    }
+}
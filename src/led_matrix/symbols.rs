@@ -2,33 +2,75 @@
 
 use crate::led_matrix::buffer::MatrixBuffer;
 
+/// Rows of the up arrow, which the orientation wizard needs whatever the features.
+#[rustfmt::skip]
+pub(crate) const ARROW_UP: [u8; 8] = [
+    0b00000000,
+    0b00010000,
+    0b00111000,
+    0b01111100,
+    0b11111110,
+    0b00010000,
+    0b00010000,
+    0b00010000,
+];
+
+/// Rows of the left arrow, which the orientation wizard needs whatever the features.
+#[rustfmt::skip]
+pub(crate) const ARROW_LEFT: [u8; 8] = [
+    0b00001000,
+    0b00011000,
+    0b00111000,
+    0b01111111,
+    0b00111000,
+    0b00011000,
+    0b00001000,
+    0b00000000,
+];
+
 /// Enum representing predefined 8x8 symbols.
 ///
 /// These symbols can be displayed on an LED matrix.
 /// To convert an `Symbol` into a displayable buffer, use `.to_buffer()`.
+///
+/// The heart, the arrows, the checkmark and the X mark come with the
+/// `symbols-basic` feature, the others with `symbols-extra`. Both are on by
+/// default.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Symbol {
     /// Heart shape
+    #[cfg(feature = "symbols-basic")]
     Heart,
     /// Smiley face
+    #[cfg(feature = "symbols-extra")]
     Smiley,
     /// Sad face
+    #[cfg(feature = "symbols-extra")]
     SadFace,
     /// Upward pointing arrow
+    #[cfg(feature = "symbols-basic")]
     ArrowUp,
     /// Downward pointing arrow
+    #[cfg(feature = "symbols-basic")]
     ArrowDown,
     /// Left pointing arrow
+    #[cfg(feature = "symbols-basic")]
     ArrowLeft,
     /// Right pointing arrow
+    #[cfg(feature = "symbols-basic")]
     ArrowRight,
     /// Checkmark symbol
+    #[cfg(feature = "symbols-basic")]
     Checkmark,
     /// X mark symbol
+    #[cfg(feature = "symbols-basic")]
     XMark,
     /// Musical note symbol
+    #[cfg(feature = "symbols-extra")]
     MusicNote,
     /// circle
+    #[cfg(feature = "symbols-extra")]
     Circle,
 }
 
@@ -36,10 +78,20 @@ impl Symbol {
     /// Convert the selected symbol into a `MatrixBuffer` pattern.
     ///
     /// This returns an 8x8 matrix buffer representing the selected symbol.
-    #[rustfmt::skip]
     pub const fn to_buffer(&self) -> MatrixBuffer {
-        match self {
-            Symbol::Heart => MatrixBuffer::from_data([
+        MatrixBuffer::from_data(self.to_bitmap())
+    }
+
+    /// Returns the symbol's rows, bit 7 of each being the leftmost column.
+    ///
+    /// This is the bitmap format of [`LedFont`](crate::led_matrix::fonts::LedFont),
+    /// so symbols can be used as characters, see
+    /// [`font_with_symbols!`](crate::font_with_symbols).
+    #[rustfmt::skip]
+    pub const fn to_bitmap(&self) -> [u8; 8] {
+        match *self {
+            #[cfg(feature = "symbols-basic")]
+            Symbol::Heart => [
                 0b00000000,
                 0b01100110,
                 0b11111111,
@@ -48,8 +100,9 @@ impl Symbol {
                 0b01111110,
                 0b00111100,
                 0b00011000,
-            ]),
-            Symbol::Smiley => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-extra")]
+            Symbol::Smiley => [
                 0b00111100,
                 0b01000010,
                 0b10100101,
@@ -58,8 +111,9 @@ impl Symbol {
                 0b10011001,
                 0b01000010,
                 0b00111100,
-            ]),
-             Symbol::SadFace => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-extra")]
+            Symbol::SadFace => [
                 0b00111100, 
                 0b01000010, 
                 0b10100101, 
@@ -68,18 +122,11 @@ impl Symbol {
                 0b10100101, 
                 0b01000010, 
                 0b00111100, 
-            ]),
-            Symbol::ArrowUp => MatrixBuffer::from_data([
-                0b00000000, 
-                0b00010000,
-                0b00111000,
-                0b01111100,
-                0b11111110,
-                0b00010000,
-                0b00010000,
-                0b00010000,
-            ]),
-            Symbol::ArrowDown => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-basic")]
+            Symbol::ArrowUp => ARROW_UP,
+            #[cfg(feature = "symbols-basic")]
+            Symbol::ArrowDown => [
                 0b00010000, 
                 0b00010000,
                 0b00010000,
@@ -88,18 +135,11 @@ impl Symbol {
                 0b00111000,
                 0b00010000,
                 0b00000000
-            ]),
-            Symbol::ArrowLeft => MatrixBuffer::from_data([
-                0b00001000,
-                0b00011000,
-                0b00111000,
-                0b01111111,
-                0b00111000,
-                0b00011000,
-                0b00001000,
-                0b00000000,
-            ]),
-            Symbol::ArrowRight => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-basic")]
+            Symbol::ArrowLeft => ARROW_LEFT,
+            #[cfg(feature = "symbols-basic")]
+            Symbol::ArrowRight => [
                 0b00001000,
                 0b00001100,
                 0b00001110,
@@ -108,8 +148,9 @@ impl Symbol {
                 0b00001100,
                 0b00001000,
                 0b00000000,
-            ]),
-            Symbol::Checkmark => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-basic")]
+            Symbol::Checkmark => [
                 0b00000001,
                 0b00000010,
                 0b00000100,
@@ -118,8 +159,9 @@ impl Symbol {
                 0b00100000,
                 0b00000000,
                 0b00000000,
-            ]),
-            Symbol::XMark => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-basic")]
+            Symbol::XMark => [
                 0b10000001,
                 0b01000010,
                 0b00100100,
@@ -128,8 +170,9 @@ impl Symbol {
                 0b00100100,
                 0b01000010,
                 0b10000001,
-            ]),
-            Symbol::MusicNote => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-extra")]
+            Symbol::MusicNote => [
                 0b00011100,
                 0b00010100,
                 0b00011100,
@@ -138,8 +181,9 @@ impl Symbol {
                 0b11110100,
                 0b01110000,
                 0b00100000,
-            ]),
-            Symbol::Circle => MatrixBuffer::from_data([
+            ],
+            #[cfg(feature = "symbols-extra")]
+            Symbol::Circle => [
                 0b00111100,
                 0b01111110,
                 0b11111111,
@@ -148,7 +192,7 @@ impl Symbol {
                 0b11111111,
                 0b01111110,
                 0b00111100,
-            ]),
+            ],
         }
     }
 }
@@ -161,21 +205,32 @@ mod tests {
     #[test]
     fn test_symbol_to_buffer() {
         // Test that symbols can be converted to buffers without panic
-        let symbols = [
+        let symbols: &[Symbol] = &[
+            #[cfg(feature = "symbols-basic")]
             Symbol::Heart,
+            #[cfg(feature = "symbols-extra")]
             Symbol::Smiley,
+            #[cfg(feature = "symbols-extra")]
             Symbol::SadFace,
+            #[cfg(feature = "symbols-basic")]
             Symbol::ArrowUp,
+            #[cfg(feature = "symbols-basic")]
             Symbol::ArrowDown,
+            #[cfg(feature = "symbols-basic")]
             Symbol::ArrowLeft,
+            #[cfg(feature = "symbols-basic")]
             Symbol::ArrowRight,
+            #[cfg(feature = "symbols-basic")]
             Symbol::Checkmark,
+            #[cfg(feature = "symbols-basic")]
             Symbol::XMark,
+            #[cfg(feature = "symbols-extra")]
             Symbol::MusicNote,
+            #[cfg(feature = "symbols-extra")]
             Symbol::Circle,
         ];
 
-        for symbol in &symbols {
+        for symbol in symbols {
             let buffer = symbol.to_buffer();
             
             // Verify we can read all rows
@@ -186,6 +241,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "symbols-basic")]
     fn test_heart_symbol() {
         let buffer = Symbol::Heart.to_buffer();
         let expected_data = [
@@ -0,0 +1,140 @@
+//! Fixed-rate frame pacing
+//!
+//! Waiting a fixed delay after each frame makes every frame last the delay plus
+//! however long drawing and flushing took. [`FrameTimer`] waits only for what is
+//! left of the frame budget, measured with a microsecond clock supplied by the
+//! application, so the frames of an animation or a scroll start on a steady grid.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let mut timer = FrameTimer::new(|| timer0.now().ticks());
+//! matrix.play_timed(&BOUNCE, &mut delay, &mut timer, 3)?;
+//! ```
+
+use embedded_hal::delay::DelayNs;
+
+/// Paces frames against a monotonic clock, given in microseconds.
+///
+/// Frame `n` after [`Self::start`] is due `n` budgets after the start, however
+/// long each frame took to draw. A frame that runs past its slot does not push
+/// the following ones back: the slots it covered are skipped and the timer
+/// waits for the next one, which keeps the frames in phase with e.g. music.
+pub struct FrameTimer<C> {
+    now_us: C,
+    /// Start of the current frame's slot, `None` until started.
+    frame_start_us: Option<u64>,
+}
+
+impl<C: FnMut() -> u64> FrameTimer<C> {
+    /// Creates a timer reading the time from `now_us`, which must never go back.
+    pub fn new(now_us: C) -> Self {
+        Self {
+            now_us,
+            frame_start_us: None,
+        }
+    }
+
+    /// Starts the first frame now.
+    ///
+    /// Until then, the first [`Self::wait_for_next_frame`] waits a full budget.
+    pub fn start(&mut self) {
+        self.frame_start_us = Some((self.now_us)());
+    }
+
+    /// Waits until the slot after the current frame's starts, and makes it the
+    /// current one.
+    ///
+    /// Returns the number of slots skipped because the frame took longer than
+    /// `frame_us`: `0` when it was on time, `1` when it ran into the next slot, and
+    /// so on. A caller showing a fixed sequence can drop that many frames to catch
+    /// up.
+    pub fn wait_for_next_frame<D: DelayNs>(&mut self, delay: &mut D, frame_us: u32) -> u32 {
+        let now = (self.now_us)();
+        let start = self.frame_start_us.unwrap_or(now);
+        let budget = u64::from(frame_us.max(1));
+        let slots = now.saturating_sub(start).div_ceil(budget).max(1);
+        let next_start = start + slots * budget;
+
+        // The wait is at most one budget, so it fits in a `u32`
+        delay.delay_us((next_start - now) as u32);
+        self.frame_start_us = Some(next_start);
+        (slots - 1).try_into().unwrap_or(u32::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Advances the shared clock by every wait.
+    struct ClockDelay<'a> {
+        now_us: &'a Cell<u64>,
+        waits_us: Vec<u32>,
+    }
+
+    impl DelayNs for ClockDelay<'_> {
+        fn delay_ns(&mut self, ns: u32) {
+            self.waits_us.push(ns / 1000);
+            self.now_us.set(self.now_us.get() + u64::from(ns / 1000));
+        }
+    }
+
+    #[test]
+    fn test_waits_subtract_render_time() {
+        let now = Cell::new(1_000);
+        let mut delay = ClockDelay {
+            now_us: &now,
+            waits_us: Vec::new(),
+        };
+        let mut timer = FrameTimer::new(|| now.get());
+        timer.start();
+
+        for render_us in [8_000, 0, 49_000, 20_000] {
+            now.set(now.get() + render_us);
+            assert_eq!(timer.wait_for_next_frame(&mut delay, 50_000), 0);
+        }
+        assert_eq!(delay.waits_us, [42_000, 50_000, 1_000, 30_000]);
+        assert_eq!(now.get(), 1_000 + 4 * 50_000);
+    }
+
+    #[test]
+    fn test_overrun_skips_slots() {
+        let now = Cell::new(0);
+        let mut delay = ClockDelay {
+            now_us: &now,
+            waits_us: Vec::new(),
+        };
+        let mut timer = FrameTimer::new(|| now.get());
+        timer.start();
+
+        // 58 ms of rendering runs 8 ms into the second slot
+        now.set(58_000);
+        assert_eq!(timer.wait_for_next_frame(&mut delay, 50_000), 1);
+        assert_eq!(now.get(), 100_000);
+
+        // The grid is kept: the next frame is on time again
+        now.set(now.get() + 10_000);
+        assert_eq!(timer.wait_for_next_frame(&mut delay, 50_000), 0);
+        assert_eq!(now.get(), 150_000);
+
+        // Ending exactly on a slot boundary starts that slot without waiting
+        now.set(now.get() + 150_000);
+        assert_eq!(timer.wait_for_next_frame(&mut delay, 50_000), 2);
+        assert_eq!(delay.waits_us, [42_000, 40_000, 0]);
+        assert_eq!(now.get(), 300_000);
+    }
+
+    #[test]
+    fn test_unstarted_timer_waits_full_budget() {
+        let now = Cell::new(500);
+        let mut delay = ClockDelay {
+            now_us: &now,
+            waits_us: Vec::new(),
+        };
+        let mut timer = FrameTimer::new(|| now.get());
+        assert_eq!(timer.wait_for_next_frame(&mut delay, 20_000), 0);
+        assert_eq!(delay.waits_us, [20_000]);
+    }
+}
@@ -0,0 +1,245 @@
+//! Rotation and mirroring of 8x8 row patterns for modules mounted at an angle,
+//! and transposition for modules wired column by column
+
+/// Clockwise rotation applied to every 8x8 device before it is written.
+///
+/// Row patterns use the same layout as the digit registers: one byte per row,
+/// with bit 7 as the leftmost column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rotation {
+    /// No rotation.
+    #[default]
+    None,
+    /// Rotate 90 degrees clockwise.
+    Cw90,
+    /// Rotate 180 degrees.
+    Cw180,
+    /// Rotate 270 degrees clockwise (90 degrees counter-clockwise).
+    Cw270,
+}
+
+impl Rotation {
+    /// Returns `rows` rotated by this rotation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // A line along the top edge ends up along the right edge.
+    /// let rows = Rotation::Cw90.apply([0xFF, 0, 0, 0, 0, 0, 0, 0]);
+    /// assert_eq!(rows, [0x01; 8]);
+    /// ```
+    pub const fn apply(self, rows: [u8; 8]) -> [u8; 8] {
+        let pixels = u64::from_be_bytes(rows);
+        let mut out = 0u64;
+        let mut row = 0;
+        while row < 8 {
+            let mut col = 0;
+            while col < 8 {
+                // Source pixel (src_row, src_col) that lands on (row, col)
+                let (src_row, src_col) = match self {
+                    Rotation::None => (row, col),
+                    Rotation::Cw90 => (7 - col, row),
+                    Rotation::Cw180 => (7 - row, 7 - col),
+                    Rotation::Cw270 => (col, 7 - row),
+                };
+                if pixels & pixel_bit(src_row, src_col) != 0 {
+                    out |= pixel_bit(row, col);
+                }
+                col += 1;
+            }
+            row += 1;
+        }
+        out.to_be_bytes()
+    }
+}
+
+/// How the chain is mounted: per-device rotation and mirroring, and device order.
+///
+/// Each device's rows are mirrored first (left and right swapped), then rotated.
+/// With `reversed`, device `i` is drawn on the device at the other end of the chain,
+/// `DEVICE_COUNT - 1 - i`. [`LedMatrix::orientation_wizard`] finds the right
+/// settings interactively; with the `serde` feature they can be persisted.
+///
+/// [`LedMatrix::orientation_wizard`]: crate::led_matrix::LedMatrix::orientation_wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Orientation {
+    /// Rotation applied to every device.
+    pub rotation: Rotation,
+    /// Swap left and right within every device.
+    pub mirrored: bool,
+    /// Reverse the order of the devices in the chain.
+    pub reversed: bool,
+}
+
+impl Orientation {
+    /// Returns `rows` mirrored if needed, then rotated.
+    pub const fn apply(self, rows: [u8; 8]) -> [u8; 8] {
+        let rows = if self.mirrored {
+            // Row 0 starts in the low byte; reversing all 64 bits moves it back
+            // to the front with its bits mirrored
+            u64::from_le_bytes(rows).reverse_bits().to_be_bytes()
+        } else {
+            rows
+        };
+        self.rotation.apply(rows)
+    }
+
+    /// Maps a device index to the device it is drawn on in a chain of `device_count`.
+    pub const fn device_index(self, device_index: usize, device_count: usize) -> usize {
+        if self.reversed {
+            device_count - 1 - device_index
+        } else {
+            device_index
+        }
+    }
+}
+
+/// How a module wires the digit registers to its LEDs.
+///
+/// Set per device with [`LedMatrix::set_pixel_layout`]. The layout is applied
+/// last, after the [`Orientation`]'s mirroring and rotation: those describe how
+/// the module is mounted, this one how it is wired.
+///
+/// [`LedMatrix::set_pixel_layout`]: crate::led_matrix::LedMatrix::set_pixel_layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelLayout {
+    /// Each digit register drives one row, bit 7 being the leftmost column, as
+    /// on FC-16 modules.
+    #[default]
+    RowMajor,
+    /// Each digit register drives one column, bit 7 being the top row. Digit 0
+    /// is the leftmost column.
+    ColumnMajor,
+}
+
+impl PixelLayout {
+    /// Returns `rows` as register bytes for this layout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // The top row becomes the leftmost column
+    /// let columns = PixelLayout::ColumnMajor.apply([0xFF, 0, 0, 0, 0, 0, 0, 0]);
+    /// assert_eq!(columns, [0x80; 8]);
+    /// ```
+    pub const fn apply(self, rows: [u8; 8]) -> [u8; 8] {
+        if let PixelLayout::RowMajor = self {
+            return rows;
+        }
+        let pixels = u64::from_be_bytes(rows);
+        let mut out = 0u64;
+        let mut row = 0;
+        while row < 8 {
+            let mut col = 0;
+            while col < 8 {
+                if pixels & pixel_bit(row, col) != 0 {
+                    out |= pixel_bit(col, row);
+                }
+                col += 1;
+            }
+            row += 1;
+        }
+        out.to_be_bytes()
+    }
+}
+
+/// Bit of pixel `(row, col)` in the 8 rows packed into a big-endian `u64`.
+///
+/// `slice::get` is not `const`, so the `const fn`s above work on the packed
+/// rows rather than indexing the array.
+const fn pixel_bit(row: usize, col: usize) -> u64 {
+    1 << (63 - (row * 8 + col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARROW: [u8; 8] = [
+        0b00010000, 0b00111000, 0b01111100, 0b11111110, 0b00010000, 0b00010000, 0b00010000,
+        0b00000000,
+    ];
+
+    #[test]
+    fn test_rotate_none() {
+        assert_eq!(Rotation::None.apply(ARROW), ARROW);
+    }
+
+    #[test]
+    fn test_rotate_top_row() {
+        let top = [0xFF, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(Rotation::Cw90.apply(top), [0x01; 8]);
+        assert_eq!(Rotation::Cw180.apply(top), [0, 0, 0, 0, 0, 0, 0, 0xFF]);
+        assert_eq!(Rotation::Cw270.apply(top), [0x80; 8]);
+    }
+
+    #[test]
+    fn test_rotate_single_pixel() {
+        // Top-left pixel travels clockwise around the corners
+        let pixel = [0x80, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(Rotation::Cw90.apply(pixel), [0x01, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(Rotation::Cw180.apply(pixel), [0, 0, 0, 0, 0, 0, 0, 0x01]);
+        assert_eq!(Rotation::Cw270.apply(pixel), [0, 0, 0, 0, 0, 0, 0, 0x80]);
+    }
+
+    #[test]
+    fn test_rotations_compose() {
+        let twice = Rotation::Cw90.apply(Rotation::Cw90.apply(ARROW));
+        assert_eq!(twice, Rotation::Cw180.apply(ARROW));
+
+        let full = Rotation::Cw270.apply(Rotation::Cw90.apply(ARROW));
+        assert_eq!(full, ARROW);
+    }
+
+    #[test]
+    fn test_orientation_mirrors_before_rotating() {
+        let pixel = [0x80, 0, 0, 0, 0, 0, 0, 0];
+        let mirrored = Orientation {
+            mirrored: true,
+            ..Orientation::default()
+        };
+        assert_eq!(mirrored.apply(pixel), [0x01, 0, 0, 0, 0, 0, 0, 0]);
+
+        // Top-right after mirroring, then down to the bottom-right corner
+        let both = Orientation {
+            rotation: Rotation::Cw90,
+            ..mirrored
+        };
+        assert_eq!(both.apply(pixel), [0, 0, 0, 0, 0, 0, 0, 0x01]);
+        assert_eq!(Orientation::default().apply(ARROW), ARROW);
+    }
+
+    #[test]
+    fn test_orientation_device_index() {
+        let reversed = Orientation {
+            reversed: true,
+            ..Orientation::default()
+        };
+        assert_eq!(reversed.device_index(0, 4), 3);
+        assert_eq!(reversed.device_index(3, 4), 0);
+        assert_eq!(Orientation::default().device_index(1, 4), 1);
+    }
+
+    #[test]
+    fn test_pixel_layout() {
+        assert_eq!(PixelLayout::RowMajor.apply(ARROW), ARROW);
+
+        let pixel = [0x80 >> 5, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            PixelLayout::ColumnMajor.apply(pixel),
+            [0, 0, 0, 0, 0, 0x80, 0, 0]
+        );
+        let twice = PixelLayout::ColumnMajor.apply(PixelLayout::ColumnMajor.apply(ARROW));
+        assert_eq!(twice, ARROW);
+    }
+}
@@ -0,0 +1,157 @@
+//! Vertical level meters with peak hold, one per device
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{Error, Result, led_matrix::LedMatrix};
+
+/// Highest level of a bar: all 8 rows lit.
+const FULL_SCALE: u8 = 8;
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Draws one level meter per device and flushes the framebuffer.
+    ///
+    /// Device `i` shows a bar `device_values[i]` rows high, `0` to `8`, filling
+    /// the module's width from the bottom. `peaks[i]` is the peak marker of that
+    /// device, kept by the caller between frames: a level above it moves it up,
+    /// otherwise it falls by `decay_per_frame` rows per call, never below the
+    /// level. The marker is the single row at the peak's height, so it is only
+    /// seen above the bar.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDeviceCount` unless both slices have one entry per
+    /// device, or `Error::ValueOutOfRange` if a level or peak is above `8`. Both
+    /// are checked before the peaks or the framebuffer change.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut peaks = [0; 4];
+    /// loop {
+    ///     let levels = spectrum.bands().map(|band| band / 32);
+    ///     matrix.draw_vu(&levels, &mut peaks, 1)?;
+    ///     delay.delay_ms(30);
+    /// }
+    /// ```
+    pub fn draw_vu(
+        &mut self,
+        device_values: &[u8],
+        peaks: &mut [u8],
+        decay_per_frame: u8,
+    ) -> Result<()> {
+        if device_values.len() != DEVICE_COUNT || peaks.len() != DEVICE_COUNT {
+            return Err(Error::InvalidDeviceCount);
+        }
+        if device_values
+            .iter()
+            .chain(peaks.iter())
+            .any(|&level| level > FULL_SCALE)
+        {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        for (device_index, (&value, peak)) in device_values.iter().zip(peaks).enumerate() {
+            *peak = peak.saturating_sub(decay_per_frame).max(value);
+            self.blit_rows(device_index, &meter_rows(value, *peak));
+        }
+        self.flush()
+    }
+}
+
+/// Rows of a meter at `value` with its marker at `peak`, both `0..=8`.
+fn meter_rows(value: u8, peak: u8) -> [u8; 8] {
+    let mut rows = [0u8; 8];
+    for (row, data) in rows.iter_mut().enumerate() {
+        let height = FULL_SCALE - row as u8;
+        if height <= value || height == peak {
+            *data = 0xFF;
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::Matrix4;
+    use crate::test_util::FrameRecorder;
+
+    fn device_rows<SPI: SpiDevice>(matrix: &Matrix4<SPI>, device_index: usize) -> [u8; 8] {
+        let mut rows = [0u8; 8];
+        let x0 = device_index * 8;
+        for (x, bit) in (x0..x0 + 8).zip((0..8).rev()) {
+            let column = matrix.get_column(x).unwrap();
+            for (row, data) in rows.iter_mut().enumerate() {
+                *data |= ((column >> row) & 1) << bit;
+            }
+        }
+        rows
+    }
+
+    #[test]
+    fn test_meter_rows() {
+        assert_eq!(meter_rows(0, 0), [0; 8]);
+        assert_eq!(meter_rows(2, 2), [0, 0, 0, 0, 0, 0, 0xFF, 0xFF]);
+        assert_eq!(meter_rows(1, 4), [0, 0, 0, 0, 0xFF, 0, 0, 0xFF]);
+        assert_eq!(meter_rows(8, 8), [0xFF; 8]);
+    }
+
+    #[test]
+    fn test_vu_peaks_decay() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        let mut peaks = [0; 4];
+
+        matrix.draw_vu(&[6, 0, 3, 8], &mut peaks, 2).unwrap();
+        assert_eq!(peaks, [6, 0, 3, 8]);
+        assert_eq!(
+            device_rows(&matrix, 0),
+            [0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+
+        // The peaks fall two rows while the levels drop
+        matrix.draw_vu(&[1, 0, 4, 2], &mut peaks, 2).unwrap();
+        assert_eq!(peaks, [4, 0, 4, 6]);
+        assert_eq!(device_rows(&matrix, 0), [0, 0, 0, 0, 0xFF, 0, 0, 0xFF]);
+        assert_eq!(device_rows(&matrix, 1), [0; 8]);
+        assert_eq!(
+            device_rows(&matrix, 2),
+            [0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(device_rows(&matrix, 3), [0, 0, 0xFF, 0, 0, 0, 0xFF, 0xFF]);
+
+        // A peak stops at the level
+        matrix.draw_vu(&[3, 0, 0, 2], &mut peaks, 2).unwrap();
+        assert_eq!(peaks, [3, 0, 2, 4]);
+        assert_eq!(device_rows(&matrix, 0), [0, 0, 0, 0, 0, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(device_rows(&matrix, 3), [0, 0, 0, 0, 0xFF, 0, 0xFF, 0xFF]);
+
+        // Every draw is one flush after the power-on
+        assert_eq!(recorder.frames().len(), 1 + 3 * 8);
+    }
+
+    #[test]
+    fn test_vu_rejects_bad_input() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        let mut peaks = [5; 4];
+
+        assert_eq!(
+            matrix.draw_vu(&[1, 2, 3], &mut peaks, 1),
+            Err(Error::InvalidDeviceCount)
+        );
+        assert_eq!(
+            matrix.draw_vu(&[1, 2, 3, 9], &mut peaks, 1),
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(peaks, [5; 4]);
+        assert!(recorder.frames().is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+//! Chain-wide images built at compile time
+//!
+//! A [`WideBuffer`] holds one 8x8 image per device, left to right, and can be
+//! parsed from ASCII art in a `const` item, so a splash screen costs no parsing
+//! or RAM at runtime. Art that does not fit the chain fails the build.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use max7219_display::wide_buffer;
+//! use max7219_display::led_matrix::wide::WideBuffer;
+//!
+//! const LOGO: WideBuffer<2> = wide_buffer!(include_str!("logo.txt"));
+//! matrix.show(&LOGO)?;
+//! ```
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{Result, led_matrix::LedMatrix};
+
+/// One 8x8 image per device of a `DEVICES` long chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WideBuffer<const DEVICES: usize> {
+    rows: [[u8; 8]; DEVICES],
+}
+
+impl<const DEVICES: usize> WideBuffer<DEVICES> {
+    /// Creates a blank image.
+    pub const fn new() -> Self {
+        Self {
+            rows: [[0; 8]; DEVICES],
+        }
+    }
+
+    /// Creates an image from the rows of each 8-column block, left to right,
+    /// bit 7 being the leftmost column.
+    pub const fn from_rows(rows: [[u8; 8]; DEVICES]) -> Self {
+        Self { rows }
+    }
+
+    /// Parses ASCII art: 8 lines of `DEVICES * 8` pixels, `#` for a lit pixel
+    /// and `.` for a dark one.
+    ///
+    /// Spaces, tabs and blank lines are ignored, so the art can be indented and
+    /// the devices set apart. Meant for `const` items, see [`wide_buffer!`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not 8 lines, a line does not have `DEVICES * 8`
+    /// pixels, or it holds another character. In a `const` item this is a build
+    /// error.
+    ///
+    /// [`wide_buffer!`]: crate::wide_buffer
+    #[allow(
+        clippy::indexing_slicing,
+        reason = "`slice::get` is not const; every index is checked against the art's size first"
+    )]
+    pub const fn from_ascii_art(art: &str) -> Self {
+        let bytes = art.as_bytes();
+        let mut rows = [[0u8; 8]; DEVICES];
+        let mut row = 0;
+        let mut col = 0;
+        let mut i = 0;
+        while i <= bytes.len() {
+            let byte = if i < bytes.len() { bytes[i] } else { b'\n' };
+            match byte {
+                b' ' | b'\t' | b'\r' => {}
+                b'\n' => {
+                    if col != 0 {
+                        if col != DEVICES * 8 {
+                            panic!("ASCII art line does not match the chain width");
+                        }
+                        row += 1;
+                        col = 0;
+                    }
+                }
+                b'#' | b'.' => {
+                    if row >= 8 {
+                        panic!("ASCII art has more than 8 lines");
+                    }
+                    if col >= DEVICES * 8 {
+                        panic!("ASCII art line does not match the chain width");
+                    }
+                    if byte == b'#' {
+                        rows[col / 8][row] |= 0x80 >> (col % 8);
+                    }
+                    col += 1;
+                }
+                _ => panic!("ASCII art may only hold '#', '.' and whitespace"),
+            }
+            i += 1;
+        }
+        if row != 8 {
+            panic!("ASCII art has fewer than 8 lines");
+        }
+        Self { rows }
+    }
+
+    /// Rows of each 8-column block, left to right.
+    pub const fn rows(&self) -> &[[u8; 8]; DEVICES] {
+        &self.rows
+    }
+}
+
+impl<const DEVICES: usize> Default for WideBuffer<DEVICES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses ASCII art into a [`WideBuffer`] at compile time.
+///
+/// Expands to an inline `const` block, so art that does not match the chain
+/// fails the build even outside a `const` item. See
+/// [`WideBuffer::from_ascii_art`] for the format.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use max7219_display::wide_buffer;
+/// use max7219_display::led_matrix::wide::WideBuffer;
+///
+/// const ARROW: WideBuffer<1> = wide_buffer!(
+///     "
+///     ...#....
+///     ....#...
+///     .....#..
+///     ########
+///     .....#..
+///     ....#...
+///     ...#....
+///     ........
+///     "
+/// );
+/// ```
+#[macro_export]
+macro_rules! wide_buffer {
+    ($art:expr) => {
+        const { $crate::led_matrix::wide::WideBuffer::from_ascii_art($art) }
+    };
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Copies `image` into the framebuffer and flushes it.
+    ///
+    /// The leftmost block of the image lands in framebuffer columns `0..8`, like
+    /// pixels drawn with [`Self::set_pixel`], and the orientation applies as
+    /// usual.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// const SPLASH: WideBuffer<4> = wide_buffer!(include_str!("splash.txt"));
+    /// matrix.show(&SPLASH)?;
+    /// ```
+    pub fn show(&mut self, image: &WideBuffer<DEVICE_COUNT>) -> Result<()> {
+        for (block, rows) in image.rows().iter().enumerate() {
+            self.blit_rows(block, rows);
+        }
+        self.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::Matrix4;
+    use crate::test_util::FrameRecorder;
+
+    const SPLASH: WideBuffer<4> = wide_buffer!(
+        "
+        #....... ........ ........ .......#
+        .#...... ........ ........ ......#.
+        ..#..... ........ ........ .....#..
+        ...#.... ######## ######## ....#...
+        ...#.... ######## ######## ....#...
+        ..#..... ........ ........ .....#..
+        .#...... ........ ........ ......#.
+        #....... ........ ........ .......#
+        "
+    );
+
+    #[test]
+    fn test_from_ascii_art() {
+        let rows = SPLASH.rows();
+        assert_eq!(rows[0], [0x80, 0x40, 0x20, 0x10, 0x10, 0x20, 0x40, 0x80]);
+        assert_eq!(rows[1], [0, 0, 0, 0xFF, 0xFF, 0, 0, 0]);
+        assert_eq!(rows[2], rows[1]);
+        assert_eq!(rows[3], [0x01, 0x02, 0x04, 0x08, 0x08, 0x04, 0x02, 0x01]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chain width")]
+    fn test_from_ascii_art_rejects_width() {
+        WideBuffer::<2>::from_ascii_art("#.......\n".repeat(8).as_str());
+    }
+
+    #[test]
+    #[should_panic(expected = "fewer than 8 lines")]
+    fn test_from_ascii_art_rejects_height() {
+        WideBuffer::<1>::from_ascii_art("#.......\n".repeat(7).as_str());
+    }
+
+    #[test]
+    fn test_show_flushes_image() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+        matrix.power_on().unwrap();
+        matrix.show(&SPLASH).unwrap();
+
+        // Device i shows block i
+        let frames = recorder.frames();
+        for (digit, frame) in frames[1..].iter().enumerate() {
+            for &(device, register, data) in frame {
+                assert_eq!(register.digit_index(), Some(digit as u8));
+                assert_eq!(data, SPLASH.rows()[device][digit]);
+            }
+        }
+        assert_eq!(frames.len(), 9);
+    }
+}
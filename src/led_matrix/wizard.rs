@@ -0,0 +1,281 @@
+//! Interactive calibration of rotation, mirroring and device order
+//!
+//! After assembling a chain it is rarely obvious how the modules are mounted.
+//! [`LedMatrix::orientation_wizard`] shows a few test patterns and asks yes/no
+//! questions about them, e.g. through a button or a serial console, and works out
+//! the [`Orientation`] from the answers.
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    Result,
+    error::Error,
+    led_matrix::{
+        LedMatrix,
+        buffer::MatrixBuffer,
+        symbols::{ARROW_LEFT, ARROW_UP},
+        transform::{Orientation, Rotation},
+    },
+};
+
+/// Time each column stays lit during the sweep.
+const SWEEP_STEP_MS: u32 = 60;
+
+/// Question asked by [`LedMatrix::orientation_wizard`] about what is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Prompt {
+    /// Every device shows an arrow: does it point up?
+    ArrowPointsUp,
+    /// Every device shows an arrow: does it point left?
+    ArrowPointsLeft,
+    /// One device is fully lit: is it the leftmost one? Skipped for a single device.
+    MarkedDeviceIsLeftmost,
+    /// A vertical line swept across the chain: did it move from left to right?
+    SweepMovesLeftToRight,
+}
+
+/// Answer to a [`Prompt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Answer {
+    /// The pattern looks as described.
+    Yes,
+    /// The pattern looks different.
+    No,
+}
+
+impl<SPI, const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<SPI, BUFFER_LENGTH, DEVICE_COUNT>
+where
+    SPI: SpiDevice,
+{
+    /// Finds and applies the [`Orientation`] of the chain by asking `ask` about test patterns.
+    ///
+    /// 1. An arrow is shown on every device in each rotation in turn until it points up.
+    /// 2. An arrow pointing left tells whether the devices are mirrored.
+    /// 3. Lighting device 0 tells whether the device order is reversed.
+    /// 4. A line sweeping across the chain, one column every 60 ms, confirms the result.
+    ///
+    /// `ask` is called once per question and may block until the user answers. The
+    /// display is cleared afterwards and the new orientation is applied and returned,
+    /// so the application can store it and pass it to [`Self::with_orientation`] on
+    /// the next boot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::CalibrationFailed` if no rotation shows an upward arrow or the
+    /// final sweep is not confirmed; the previous orientation is kept. Returns an SPI
+    /// error if drawing fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let orientation = matrix.orientation_wizard(&mut delay, |prompt| {
+    ///     println!("{prompt:?} Press the button within 3 s for yes.");
+    ///     if button_pressed_within_3s() { Answer::Yes } else { Answer::No }
+    /// })?;
+    /// settings.save(orientation);
+    /// ```
+    pub fn orientation_wizard<D, F>(&mut self, delay: &mut D, mut ask: F) -> Result<Orientation>
+    where
+        D: DelayNs,
+        F: FnMut(Prompt) -> Answer,
+    {
+        let previous = self.orientation();
+        let found = self.find_orientation(delay, &mut ask);
+        let cleared = self.clear_all();
+
+        match found {
+            Ok(orientation) => {
+                self.set_orientation(orientation);
+                cleared.map(|()| orientation)
+            }
+            Err(error) => {
+                self.set_orientation(previous);
+                Err(error)
+            }
+        }
+    }
+
+    fn find_orientation<D, F>(&mut self, delay: &mut D, ask: &mut F) -> Result<Orientation>
+    where
+        D: DelayNs,
+        F: FnMut(Prompt) -> Answer,
+    {
+        let mut orientation = Orientation::default();
+
+        let mut found_up = false;
+        for rotation in [
+            Rotation::None,
+            Rotation::Cw90,
+            Rotation::Cw180,
+            Rotation::Cw270,
+        ] {
+            orientation.rotation = rotation;
+            self.set_orientation(orientation);
+            self.fill_devices(&MatrixBuffer::from_data(ARROW_UP))?;
+            if ask(Prompt::ArrowPointsUp) == Answer::Yes {
+                found_up = true;
+                break;
+            }
+        }
+        if !found_up {
+            return Err(Error::CalibrationFailed);
+        }
+
+        // The up arrow looks the same mirrored, so the rotation is right either way
+        self.fill_devices(&MatrixBuffer::from_data(ARROW_LEFT))?;
+        orientation.mirrored = ask(Prompt::ArrowPointsLeft) == Answer::No;
+        self.set_orientation(orientation);
+
+        if DEVICE_COUNT > 1 {
+            self.clear_all()?;
+            self.write_buffer(0, &MatrixBuffer::from_data([0xFF; 8]))?;
+            orientation.reversed = ask(Prompt::MarkedDeviceIsLeftmost) == Answer::No;
+            self.set_orientation(orientation);
+        }
+
+        self.clear_all()?;
+        for x in 0..self.width_pixels() {
+            if x % 8 == 0 && x > 0 {
+                self.write_buffer(x / 8 - 1, &MatrixBuffer::new())?;
+            }
+            let column = MatrixBuffer::from_data([0x80 >> (x % 8); 8]);
+            self.write_buffer(x / 8, &column)?;
+            self.driver().idle();
+            delay.delay_ms(SWEEP_STEP_MS);
+        }
+        if ask(Prompt::SweepMovesLeftToRight) == Answer::No {
+            return Err(Error::CalibrationFailed);
+        }
+
+        Ok(orientation)
+    }
+
+    /// Draws the same buffer on every device.
+    fn fill_devices(&mut self, buffer: &MatrixBuffer) -> Result<()> {
+        for device_index in 0..DEVICE_COUNT {
+            self.write_buffer(device_index, buffer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+    use crate::led_matrix::display::{Matrix4, SingleMatrix};
+    use crate::test_util::FrameRecorder;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+
+    /// Answers the prompts with `answers`, in order.
+    fn scripted(answers: &[Answer]) -> impl FnMut(Prompt) -> Answer + '_ {
+        let mut answers = answers.iter();
+        move |_| *answers.next().expect("more prompts than scripted answers")
+    }
+
+    #[test]
+    fn test_wizard_upside_down_mirrored_reversed() {
+        use Answer::{No, Yes};
+
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver).unwrap();
+
+        let mut prompts = Vec::new();
+        let mut answers = scripted(&[No, No, Yes, No, No, Yes]);
+        let orientation = matrix
+            .orientation_wizard(&mut NoopDelay::new(), |prompt| {
+                prompts.push(prompt);
+                answers(prompt)
+            })
+            .unwrap();
+
+        let expected = Orientation {
+            rotation: Rotation::Cw180,
+            mirrored: true,
+            reversed: true,
+        };
+        assert_eq!(orientation, expected);
+        assert_eq!(matrix.orientation(), expected);
+        assert_eq!(
+            prompts,
+            [
+                Prompt::ArrowPointsUp,
+                Prompt::ArrowPointsUp,
+                Prompt::ArrowPointsUp,
+                Prompt::ArrowPointsLeft,
+                Prompt::MarkedDeviceIsLeftmost,
+                Prompt::SweepMovesLeftToRight,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wizard_single_device_skips_order() {
+        use Answer::Yes;
+
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut matrix = SingleMatrix::from_driver(driver).unwrap();
+
+        let orientation = matrix
+            .orientation_wizard(&mut NoopDelay::new(), scripted(&[Yes, Yes, Yes]))
+            .unwrap();
+        assert_eq!(orientation, Orientation::default());
+
+        // Ends with the display cleared
+        let writes = recorder.writes();
+        assert!(
+            writes[writes.len() - 8..]
+                .iter()
+                .all(|&(_, _, data)| data == 0)
+        );
+    }
+
+    #[test]
+    fn test_wizard_failure_keeps_orientation() {
+        use Answer::{No, Yes};
+
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver)
+            .unwrap()
+            .with_rotation(Rotation::Cw90);
+
+        let result = matrix.orientation_wizard(&mut NoopDelay::new(), scripted(&[No; 4]));
+        assert_eq!(result, Err(Error::CalibrationFailed));
+        assert_eq!(matrix.rotation(), Rotation::Cw90);
+
+        let result =
+            matrix.orientation_wizard(&mut NoopDelay::new(), scripted(&[Yes, Yes, Yes, No]));
+        assert_eq!(result, Err(Error::CalibrationFailed));
+        assert_eq!(matrix.rotation(), Rotation::Cw90);
+    }
+
+    #[test]
+    fn test_orientation_applies_to_direct_writes() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(4).unwrap();
+        let mut matrix = Matrix4::from_driver(driver)
+            .unwrap()
+            .with_orientation(Orientation {
+                rotation: Rotation::None,
+                mirrored: true,
+                reversed: true,
+            });
+        matrix
+            .write_buffer(0, &MatrixBuffer::from_data([0x80; 8]))
+            .unwrap();
+
+        let writes = recorder.writes();
+        assert_eq!(writes.len(), 8);
+        assert!(
+            writes
+                .iter()
+                .all(|&(device, _, data)| device == 3 && data == 0x01)
+        );
+    }
+}
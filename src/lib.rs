@@ -6,11 +6,17 @@
 
 pub mod driver;
 pub mod error;
+pub mod prelude;
 pub mod registers;
 
+#[cfg(test)]
+mod test_support;
+
 // Re-exports
 pub use driver::Max7219;
-pub use error::Error;
+#[cfg(feature = "async")]
+pub use driver::AsyncMax7219;
+pub use error::{Error, ErrorKind};
 pub use registers::{DecodeMode, Register};
 
 // Additional Feature specific modules and re-exports
@@ -26,14 +32,52 @@ pub use seven_segment::SevenSegment;
 #[cfg(feature = "led-matrix")]
 pub use led_matrix::LedMatrix;
 
-/// Maximum number of daisy-chained displays supported
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
+/// Maximum number of daisy-chained displays supported by default
+///
+/// This is the chain length of [`Max7219::new`] and of the display types. Longer
+/// chains need a driver created with [`Max7219::new_chain`].
 pub const MAX_DISPLAYS: usize = 8;
 
 /// Number of digits (0 to 7) controlled by one MAX7219
 pub const NUM_DIGITS: u8 = 8;
 
-/// Crate-local `Result` type used throughout the MAX7219 driver.
+/// `Result` type returned by every fallible operation of the crate.
+///
+/// The error type defaults to the crate's [`Error`], which already carries the
+/// [`ErrorKind`](embedded_hal::spi::ErrorKind) of a failed SPI transfer, so no
+/// SPI type parameter is needed. Use it to name your own helpers that call into
+/// the driver:
+///
+/// ```rust
+/// use embedded_hal::spi::SpiDevice;
+/// use max7219_display::{Max7219, Result};
+/// # use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction};
+///
+/// fn dim_all<SPI: SpiDevice>(driver: &mut Max7219<SPI>) -> Result<()> {
+///     for device_index in 0..driver.device_count() {
+///         driver.set_intensity(device_index, 0x01)?;
+///     }
+///     Ok(())
+/// }
+///
+/// # let expected = [
+/// #     Transaction::transaction_start(),
+/// #     Transaction::write_vec(vec![max7219_display::Register::Intensity.addr(), 0x01]),
+/// #     Transaction::transaction_end(),
+/// # ];
+/// # let mut spi = SpiMock::new(&expected);
+/// let mut driver = Max7219::new(&mut spi);
+/// dim_all(&mut driver)?;
+/// # spi.done();
+/// # Ok::<(), max7219_display::Error>(())
+/// ```
 ///
-/// This alias simplifies function signatures by defaulting the error type
-/// to the crate's custom [`Error`] enum.
-pub(crate) type Result<T> = core::result::Result<T, crate::error::Error>;
+/// The second parameter lets the alias stand in for `core::result::Result`
+/// after a glob import of the [`prelude`], e.g. `Result<u8, MyError>`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -0,0 +1,47 @@
+//! Commonly used types, re-exported for a single glob import.
+//!
+//! ```rust
+//! use max7219_display::prelude::*;
+//! # use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction};
+//!
+//! fn brighten<SPI: embedded_hal::spi::SpiDevice>(driver: &mut Max7219<SPI>) -> Result<()> {
+//!     driver.set_intensity(0, 0x0F)
+//! }
+//!
+//! # let expected = [
+//! #     Transaction::transaction_start(),
+//! #     Transaction::write_vec(vec![Register::Intensity.addr(), 0x0F]),
+//! #     Transaction::transaction_end(),
+//! # ];
+//! # let mut spi = SpiMock::new(&expected);
+//! let mut driver = Max7219::new(&mut spi);
+//! brighten(&mut driver).unwrap();
+//! # spi.done();
+//! ```
+//!
+//! The driver types are always exported. Matrix, scrolling and symbol types are
+//! added with the `led-matrix` feature, and [`SevenSegment`](crate::SevenSegment)
+//! with its font types with the `seven-segment` feature.
+
+pub use crate::driver::{
+    BrightnessController, BrightnessCurve, ChainOrder, ClampPolicy, DeviceConfig, InitConfig,
+};
+pub use crate::{
+    DecodeMode, Error, ErrorKind, MAX_DISPLAYS, Max7219, NUM_DIGITS, Register, Result,
+};
+
+#[cfg(feature = "led-matrix")]
+pub use crate::led_matrix::{
+    Animation, CursorStyle, FrameTimer, Glyph, GrayLevel, LedMatrix, MatrixConfig, PowerPolicy,
+    Spinner, TextStyle, WideBuffer,
+    buffer::MatrixBuffer,
+    display::{Matrix4, Matrix8, SingleMatrix},
+    fonts::{CharMap, LedFont, STANDARD_LED_FONT},
+    scroll::ScrollConfig,
+    symbols::Symbol,
+    transform::{Orientation, PixelLayout, Rotation},
+    wizard::{Answer, Prompt},
+};
+
+#[cfg(feature = "seven-segment")]
+pub use crate::seven_segment::{Font, Indicator, STANDARD_FONT, SevenSegment, SevenSegmentConfig};
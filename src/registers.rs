@@ -11,6 +11,7 @@ use crate::{Error, Result};
 /// This enum is typically used when sending 16-bit data packets to the MAX7219,
 /// where the upper byte specifies the target register.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Register {
     /// No-op register
@@ -60,7 +61,31 @@ impl Register {
             5 => Ok(Register::Digit5),
             6 => Ok(Register::Digit6),
             7 => Ok(Register::Digit7),
-            _ => Err(Error::InvalidDigit),
+            _ => Err(Error::InvalidDigit { digit }),
+        }
+    }
+
+    /// Returns `true` for the eight digit registers (`Digit0` to `Digit7`).
+    pub const fn is_digit(self) -> bool {
+        matches!(
+            self,
+            Register::Digit0
+                | Register::Digit1
+                | Register::Digit2
+                | Register::Digit3
+                | Register::Digit4
+                | Register::Digit5
+                | Register::Digit6
+                | Register::Digit7
+        )
+    }
+
+    /// Returns the digit position (0-7) of a digit register, or `None` for control registers.
+    pub const fn digit_index(self) -> Option<u8> {
+        if self.is_digit() {
+            Some(self.addr() - Register::Digit0.addr())
+        } else {
+            None
         }
     }
 
@@ -83,6 +108,42 @@ impl Register {
     }
 }
 
+/// Converts a raw register address back into a [`Register`].
+///
+/// Useful when decoding captured SPI frames. Addresses without a register
+/// (`0x0D`, `0x0E` and anything above `0x0F`) return [`Error::InvalidRegister`].
+impl TryFrom<u8> for Register {
+    type Error = Error;
+
+    fn try_from(addr: u8) -> Result<Self> {
+        match addr {
+            0x00 => Ok(Register::NoOp),
+            0x01..=0x08 => Register::try_digit(addr - 1),
+            0x09 => Ok(Register::DecodeMode),
+            0x0A => Ok(Register::Intensity),
+            0x0B => Ok(Register::ScanLimit),
+            0x0C => Ok(Register::Shutdown),
+            0x0F => Ok(Register::DisplayTest),
+            _ => Err(Error::InvalidRegister),
+        }
+    }
+}
+
+/// Formats the register with its datasheet name, e.g. `Digit 3` or `Scan Limit`.
+impl core::fmt::Display for Register {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Register::NoOp => write!(f, "No-Op"),
+            Register::DecodeMode => write!(f, "Decode Mode"),
+            Register::Intensity => write!(f, "Intensity"),
+            Register::ScanLimit => write!(f, "Scan Limit"),
+            Register::Shutdown => write!(f, "Shutdown"),
+            Register::DisplayTest => write!(f, "Display Test"),
+            digit => write!(f, "Digit {}", digit.addr() - Register::Digit0.addr()),
+        }
+    }
+}
+
 /// Decode mode configuration for the MAX7219 display driver.
 ///
 /// Code B decoding allows the driver to automatically convert certain values
@@ -92,6 +153,8 @@ impl Register {
 /// Use this to configure which digits should use Code B decoding and which
 /// should remain in raw segment mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum DecodeMode {
     /// Disable Code B decoding for all digits (DIG0 to DIG7).
@@ -123,6 +186,24 @@ impl DecodeMode {
     }
 }
 
+/// Converts a raw Decode Mode register value back into a [`DecodeMode`].
+///
+/// The chip accepts any bit pattern, one bit per digit, but only the patterns of
+/// the variants convert; the rest return [`Error::ValueOutOfRange`].
+impl TryFrom<u8> for DecodeMode {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0x00 => Ok(DecodeMode::NoDecode),
+            0x01 => Ok(DecodeMode::Digit0),
+            0x0F => Ok(DecodeMode::Digits0To3),
+            0xFF => Ok(DecodeMode::AllDigits),
+            _ => Err(Error::ValueOutOfRange),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,8 +234,27 @@ mod tests {
 
     #[test]
     fn test_try_digit_invalid() {
-        assert_eq!(Register::try_digit(8), Err(Error::InvalidDigit));
-        assert_eq!(Register::try_digit(255), Err(Error::InvalidDigit));
+        assert_eq!(
+            Register::try_digit(8),
+            Err(Error::InvalidDigit { digit: 8 })
+        );
+        assert_eq!(
+            Register::try_digit(255),
+            Err(Error::InvalidDigit { digit: 255 })
+        );
+    }
+
+    #[test]
+    fn test_decode_mode_try_from() {
+        for mode in [
+            DecodeMode::NoDecode,
+            DecodeMode::Digit0,
+            DecodeMode::Digits0To3,
+            DecodeMode::AllDigits,
+        ] {
+            assert_eq!(DecodeMode::try_from(mode.value()), Ok(mode));
+        }
+        assert_eq!(DecodeMode::try_from(0x03), Err(Error::ValueOutOfRange));
     }
 
     #[test]
@@ -173,6 +273,63 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_try_from_round_trip() {
+        let all = [
+            Register::NoOp,
+            Register::Digit0,
+            Register::Digit1,
+            Register::Digit2,
+            Register::Digit3,
+            Register::Digit4,
+            Register::Digit5,
+            Register::Digit6,
+            Register::Digit7,
+            Register::DecodeMode,
+            Register::Intensity,
+            Register::ScanLimit,
+            Register::Shutdown,
+            Register::DisplayTest,
+        ];
+        for register in all {
+            assert_eq!(Register::try_from(register.addr()), Ok(register));
+        }
+
+        let valid = (0..=u8::MAX)
+            .filter(|&addr| Register::try_from(addr).is_ok())
+            .count();
+        assert_eq!(valid, all.len());
+    }
+
+    #[test]
+    fn test_try_from_invalid() {
+        assert_eq!(Register::try_from(0x0D), Err(Error::InvalidRegister));
+        assert_eq!(Register::try_from(0x0E), Err(Error::InvalidRegister));
+        assert_eq!(Register::try_from(0x10), Err(Error::InvalidRegister));
+        assert_eq!(Register::try_from(0xFF), Err(Error::InvalidRegister));
+    }
+
+    #[test]
+    fn test_digit_helpers() {
+        for (index, register) in Register::digits().enumerate() {
+            assert!(register.is_digit());
+            assert_eq!(register.digit_index(), Some(index as u8));
+        }
+        assert!(!Register::NoOp.is_digit());
+        assert!(!Register::Intensity.is_digit());
+        assert_eq!(Register::DisplayTest.digit_index(), None);
+    }
+
+    #[test]
+    fn test_register_display() {
+        assert_eq!(format!("{}", Register::NoOp), "No-Op");
+        assert_eq!(format!("{}", Register::Digit0), "Digit 0");
+        assert_eq!(format!("{}", Register::Digit7), "Digit 7");
+        assert_eq!(format!("{}", Register::DecodeMode), "Decode Mode");
+        assert_eq!(format!("{}", Register::ScanLimit), "Scan Limit");
+        assert_eq!(format!("{}", Register::DisplayTest), "Display Test");
+    }
+
     #[test]
     fn test_decode_mode_value() {
         assert_eq!(DecodeMode::NoDecode.value(), 0x00);
@@ -180,4 +337,12 @@ mod tests {
         assert_eq!(DecodeMode::Digits0To3.value(), 0x0F);
         assert_eq!(DecodeMode::AllDigits.value(), 0xFF);
     }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format_impls() {
+        fn assert_format<T: defmt::Format>() {}
+        assert_format::<Register>();
+        assert_format::<DecodeMode>();
+    }
 }
@@ -0,0 +1,342 @@
+//! Cycling a 7-segment display through several readings
+//!
+//! [`SevenSegment::cycle`] shows a list of [`DisplayItem`]s one after another,
+//! blocking, while [`ItemCycle`] does the same from a cooperative main loop. Text
+//! too long for the chain scrolls through one digit at a time before its hold.
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{Result, seven_segment::SevenSegment};
+
+/// One reading of a display cycle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DisplayItem<'a> {
+    /// A right-aligned integer, see [`SevenSegment::write_integer`].
+    Integer(i32),
+    /// A right-aligned decimal number, see [`SevenSegment::write_float`].
+    Float {
+        /// The number to show.
+        value: f32,
+        /// Digits after the decimal point.
+        decimals: u8,
+    },
+    /// Left-aligned text, see [`SevenSegment::write_text`].
+    ///
+    /// If it does not fit, it scrolls left one digit every `scroll_ms` until its
+    /// end is in view.
+    Text {
+        /// The text to show.
+        text: &'a str,
+        /// Time each scroll step is shown.
+        scroll_ms: u32,
+    },
+    /// `HHMMSS` on the first six digits, see [`SevenSegment::write_time`]. The
+    /// other digits are blanked.
+    Time {
+        /// Hours, 0 to 23.
+        hours: u8,
+        /// Minutes, 0 to 59.
+        minutes: u8,
+        /// Seconds, 0 to 59.
+        seconds: u8,
+    },
+}
+
+impl DisplayItem<'_> {
+    /// Number of scroll steps the item is shown in, 1 unless it is scrolling text.
+    fn steps<SPI: SpiDevice>(&self, display: &SevenSegment<SPI>) -> usize {
+        let Self::Text { mut text, .. } = *self else {
+            return 1;
+        };
+        let mut steps = 1;
+        while display.display_len(text) > display.digit_count() {
+            text = scrolled(text);
+            steps += 1;
+        }
+        steps
+    }
+
+    /// How long to wait after showing `step` before moving on.
+    fn wait_ms(&self, step: usize, steps: usize, item_ms: u32) -> u32 {
+        match *self {
+            Self::Text { scroll_ms, .. } if step + 1 < steps => scroll_ms,
+            _ => item_ms,
+        }
+    }
+
+    /// Shows scroll step `step` of the item.
+    fn show<SPI: SpiDevice>(&self, display: &mut SevenSegment<SPI>, step: usize) -> Result<()> {
+        match *self {
+            Self::Integer(value) => display.write_integer(value),
+            Self::Float { value, decimals } => display.write_float(value, decimals),
+            Self::Text { mut text, .. } => {
+                for _ in 0..step {
+                    text = scrolled(text);
+                }
+                display.write_text(text)
+            }
+            Self::Time {
+                hours,
+                minutes,
+                seconds,
+            } => {
+                display.write_time(hours, minutes, seconds)?;
+                display.blank_from(6)
+            }
+        }
+    }
+}
+
+/// Drops the first digit of `text`, including a decimal point merged into it.
+fn scrolled(text: &str) -> &str {
+    let mut chars = text.chars();
+    let first = chars.next();
+    let rest = chars.as_str();
+    match rest.strip_prefix('.') {
+        Some(after_dot) if first != Some('.') => after_dot,
+        _ => rest,
+    }
+}
+
+impl<SPI> SevenSegment<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Shows `items` in turn, each for `item_ms`, going through the list `loops`
+    /// times.
+    ///
+    /// Scrolling text is held for `item_ms` once its end is in view. The idle
+    /// hook, if any, runs before every wait.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of an item, e.g. `Error::ValueOutOfRange` for a
+    /// number that does not fit, and stops there.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let items = [
+    ///     DisplayItem::Time { hours: 12, minutes: 30, seconds: 0 },
+    ///     DisplayItem::Float { value: 21.5, decimals: 1 },
+    ///     DisplayItem::Text { text: "OUTSIDE TEMP", scroll_ms: 300 },
+    /// ];
+    /// display.cycle(&mut delay, &items, 2000, 1)?;
+    /// ```
+    pub fn cycle<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        items: &[DisplayItem<'_>],
+        item_ms: u32,
+        loops: u32,
+    ) -> Result<()> {
+        for _ in 0..loops {
+            for item in items {
+                let steps = item.steps(self);
+                for step in 0..steps {
+                    item.show(self, step)?;
+                    self.driver().idle();
+                    delay.delay_ms(item.wait_ms(step, steps, item_ms));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Non-blocking counterpart of [`SevenSegment::cycle`] that loops forever.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut cycle = ItemCycle::new(&items, 2000);
+/// loop {
+///     cycle.tick(&mut display, millis())?;
+///     // other work
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ItemCycle<'i, 'a> {
+    items: &'i [DisplayItem<'a>],
+    item_ms: u32,
+    /// Item on display.
+    index: usize,
+    /// Scroll step of that item.
+    step: usize,
+    /// When the current step was shown, `None` before the first tick.
+    shown_at_ms: Option<u32>,
+}
+
+impl<'i, 'a> ItemCycle<'i, 'a> {
+    /// Creates a cycle starting at the first item, each item held for `item_ms`.
+    pub fn new(items: &'i [DisplayItem<'a>], item_ms: u32) -> Self {
+        Self {
+            items,
+            item_ms,
+            index: 0,
+            step: 0,
+            shown_at_ms: None,
+        }
+    }
+
+    /// Index of the item on display, or of the first item before any is shown.
+    pub fn current(&self) -> usize {
+        self.index
+    }
+
+    /// Shows the next item or scroll step once its time has come.
+    ///
+    /// The first call shows the first item. `now_ms` may wrap. Returns `true` if
+    /// something was written.
+    pub fn tick<SPI: SpiDevice>(
+        &mut self,
+        display: &mut SevenSegment<SPI>,
+        now_ms: u32,
+    ) -> Result<bool> {
+        let Some(item) = self.items.get(self.index) else {
+            return Ok(false);
+        };
+
+        if let Some(shown_at) = self.shown_at_ms {
+            let steps = item.steps(display);
+            if now_ms.wrapping_sub(shown_at) < item.wait_ms(self.step, steps, self.item_ms) {
+                return Ok(false);
+            }
+            if self.step + 1 < steps {
+                self.step += 1;
+            } else {
+                self.step = 0;
+                self.index = (self.index + 1) % self.items.len();
+            }
+        }
+
+        let Some(item) = self.items.get(self.index) else {
+            return Ok(false);
+        };
+        item.show(display, self.step)?;
+        self.shown_at_ms = Some(now_ms);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::RecordingDelay;
+    use crate::{Error, Max7219, Register, seven_segment::STANDARD_FONT, test_util::FrameRecorder};
+
+    /// Segment patterns of `text`, one per digit, with spaces for blanks.
+    fn digits(text: &str) -> Vec<u8> {
+        text.chars().map(|ch| STANDARD_FONT.get_char(ch)).collect()
+    }
+
+    fn shown(recorder: &FrameRecorder) -> Vec<u8> {
+        recorder.writes().iter().map(|&(_, _, data)| data).collect()
+    }
+
+    #[test]
+    fn test_cycle_three_items() {
+        let mut recorder = FrameRecorder::new();
+        let mut display = SevenSegment::new(Max7219::new(&mut recorder));
+        let items = [
+            DisplayItem::Integer(-42),
+            DisplayItem::Text {
+                text: "HI",
+                scroll_ms: 100,
+            },
+            DisplayItem::Time {
+                hours: 9,
+                minutes: 5,
+                seconds: 30,
+            },
+        ];
+
+        let mut delay = RecordingDelay::default();
+        display.cycle(&mut delay, &items, 1000, 1).unwrap();
+
+        let expected = [digits("     -42"), digits("HI      "), digits("090530  ")].concat();
+        assert_eq!(shown(&recorder), expected);
+        assert_eq!(delay.waits_ms, [1000, 1000, 1000]);
+        let registers: Vec<_> = recorder.writes()[..8].iter().map(|w| w.1).collect();
+        assert_eq!(registers, Register::digits().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_cycle_scrolls_long_text() {
+        let mut recorder = FrameRecorder::new();
+        let mut display = SevenSegment::new(Max7219::new(&mut recorder));
+        let items = [DisplayItem::Text {
+            text: "ABCDEF.GHI",
+            scroll_ms: 200,
+        }];
+
+        let mut delay = RecordingDelay::default();
+        display.cycle(&mut delay, &items, 1000, 2).unwrap();
+
+        // Nine digits: the decimal point shares a digit with the "F"
+        let mut dotted_f = digits("ABCDEFGH");
+        dotted_f[5] |= 0x80;
+        let mut second = digits("BCDEFGHI");
+        second[4] |= 0x80;
+        let first_pass = [dotted_f, second].concat();
+        assert_eq!(shown(&recorder), [first_pass.clone(), first_pass].concat());
+        assert_eq!(delay.waits_ms, [200, 1000, 200, 1000]);
+    }
+
+    #[test]
+    fn test_cycle_stops_on_error() {
+        let mut recorder = FrameRecorder::new();
+        let mut display = SevenSegment::new(Max7219::new(&mut recorder));
+        let items = [
+            DisplayItem::Float {
+                value: -1.5,
+                decimals: 2,
+            },
+            DisplayItem::Integer(123_456_789),
+        ];
+
+        let mut delay = RecordingDelay::default();
+        let result = display.cycle(&mut delay, &items, 1000, 1);
+        assert_eq!(result, Err(Error::ValueOutOfRange));
+
+        let mut expected = digits("    -150");
+        expected[5] |= 0x80;
+        assert_eq!(shown(&recorder), expected);
+    }
+
+    #[test]
+    fn test_item_cycle_tick() {
+        let mut recorder = FrameRecorder::new();
+        let mut display = SevenSegment::new(Max7219::new(&mut recorder));
+        let items = [
+            DisplayItem::Integer(1),
+            DisplayItem::Text {
+                text: "ABCDEFGHI",
+                scroll_ms: 100,
+            },
+        ];
+        let mut cycle = ItemCycle::new(&items, 1000);
+
+        assert!(cycle.tick(&mut display, 0).unwrap());
+        assert!(!cycle.tick(&mut display, 999).unwrap());
+        assert!(cycle.tick(&mut display, 1000).unwrap());
+        assert_eq!(cycle.current(), 1);
+        // The scroll step comes after scroll_ms, the next item after item_ms
+        assert!(!cycle.tick(&mut display, 1099).unwrap());
+        assert!(cycle.tick(&mut display, 1100).unwrap());
+        assert_eq!(cycle.current(), 1);
+        assert!(!cycle.tick(&mut display, 2099).unwrap());
+        assert!(cycle.tick(&mut display, 2100).unwrap());
+        assert_eq!(cycle.current(), 0);
+
+        let expected = [
+            digits("       1"),
+            digits("ABCDEFGH"),
+            digits("BCDEFGHI"),
+            digits("       1"),
+        ]
+        .concat();
+        assert_eq!(shown(&recorder), expected);
+    }
+}
@@ -1,12 +1,65 @@
 //! 7-segment display implementation
 
-use embedded_hal::spi::SpiDevice;
+use core::fmt::{self, Write};
 
-use crate::{Error, Max7219, Result, seven_segment::Font};
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    Error, MAX_DISPLAYS, Max7219, NUM_DIGITS, Result,
+    driver::{BrightnessCurve, InitConfig, SelfTestReport},
+    seven_segment::{Font, STANDARD_FONT},
+};
+
+/// Settings applied by [`SevenSegment::from_spi_with_config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SevenSegmentConfig {
+    /// Keep what the digits show instead of clearing them, see
+    /// [`Max7219::init_preserve_display`].
+    pub preserve_display: bool,
+    /// Where each indicator LED is wired, see [`SevenSegment::set_special_indicators`].
+    pub special_indicators: &'static [(Indicator, u8, u8)],
+    /// Steps of the initialization sequence, e.g. Code B decoding or a shorter
+    /// scan limit. `preserve_display` skips the clear as well.
+    pub init: InitConfig,
+}
+
+/// Extra LEDs wired to a digit's segment lines, such as the colons of clock modules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Indicator {
+    /// First colon, usually between hours and minutes.
+    Colon1,
+    /// Second colon, usually between minutes and seconds.
+    Colon2,
+    /// Degree sign.
+    Degree,
+    /// Alarm LED.
+    Alarm,
+}
+
+impl Indicator {
+    /// Bit of this indicator in [`SevenSegment`]'s set of lit indicators.
+    const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
 
 /// A high-level abstraction for controlling a 7-segment display using the MAX7219 driver.
 pub struct SevenSegment<SPI> {
     driver: Max7219<SPI>,
+    /// Characters replaced before font lookup, see [`Self::set_char_map`].
+    substitutions: &'static [(char, char)],
+    /// Segment patterns used instead of the font, see [`Self::set_custom_glyphs`].
+    glyphs: &'static [(char, u8)],
+    /// Font of the helpers that take none, see [`Self::set_default_font`].
+    default_font: &'static Font,
+    /// Indicator wiring as `(indicator, digit, segment bit)` on device 0.
+    indicators: &'static [(Indicator, u8, u8)],
+    /// Lit indicators, one [`Indicator::mask`] bit each.
+    lit_indicators: u8,
+    /// Last value written to each digit, without indicator bits.
+    shadow: [[u8; NUM_DIGITS as usize]; MAX_DISPLAYS],
 }
 
 impl<SPI> SevenSegment<SPI>
@@ -28,7 +81,15 @@ where
     /// let mut display = SevenSegment::new(driver);
     /// ```
     pub fn new(driver: Max7219<SPI>) -> Self {
-        Self { driver }
+        Self {
+            driver,
+            substitutions: &[],
+            glyphs: &[],
+            default_font: &STANDARD_FONT,
+            indicators: &[],
+            lit_indicators: 0,
+            shadow: [[0; NUM_DIGITS as usize]; MAX_DISPLAYS],
+        }
     }
 
     /// Simplifies initialization by creating a new `SevenSegment` instance
@@ -44,7 +105,7 @@ where
     ///
     /// # Returns
     ///
-    /// Returns a `SevenSegment` instance on success, or an error if the display count is invalid
+    /// Returns a `SevenSegment` instance on success, or an error if the device count is invalid
     /// or if the MAX7219 initialization fails.
     ///
     /// # Example
@@ -54,9 +115,37 @@ where
     /// let mut display = SevenSegment::from_spi(spi, 4).unwrap();
     /// ```
     pub fn from_spi(spi: SPI, device_count: usize) -> Result<Self> {
+        Self::from_spi_with_config(spi, device_count, SevenSegmentConfig::default())
+    }
+
+    /// Creates and initializes a `SevenSegment` like [`Self::from_spi`], applying
+    /// `config`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Keep the version number the bootloader left on the display
+    /// let config = SevenSegmentConfig {
+    ///     preserve_display: true,
+    ///     ..Default::default()
+    /// };
+    /// let mut display = SevenSegment::from_spi_with_config(spi, 1, config)?;
+    /// ```
+    pub fn from_spi_with_config(
+        spi: SPI,
+        device_count: usize,
+        config: SevenSegmentConfig,
+    ) -> Result<Self> {
         let mut driver = Max7219::new(spi).with_device_count(device_count)?;
-        driver.init()?;
-        Ok(Self { driver })
+        let init = if config.preserve_display {
+            config.init.skip_clear(true)
+        } else {
+            config.init
+        };
+        driver.init_with(&init)?;
+        let mut display = Self::new(driver);
+        display.set_special_indicators(config.special_indicators)?;
+        Ok(display)
     }
 
     /// Provides mutable access to the underlying MAX7219 driver.
@@ -66,6 +155,280 @@ where
         &mut self.driver
     }
 
+    /// Consumes the display and returns the driver, e.g. to
+    /// [`release`](Max7219::release) the SPI device.
+    pub fn into_driver(self) -> Max7219<SPI> {
+        self.driver
+    }
+
+    /// Number of MAX7219 devices in the chain.
+    pub fn device_count(&self) -> usize {
+        self.driver.device_count()
+    }
+
+    /// Runs the driver's self test, see [`Max7219::self_test`].
+    ///
+    /// Devices that pass are left blank. Lit indicators come back with the next
+    /// write to their digit.
+    pub fn self_test<D: DelayNs>(&mut self, delay: &mut D) -> Result<SelfTestReport> {
+        self.shadow = [[0; NUM_DIGITS as usize]; MAX_DISPLAYS];
+        self.driver.self_test(delay)
+    }
+
+    /// Flashes every device at `peak` intensity for `hold_ms`, then restores the
+    /// previous levels. See [`Max7219::flash_brightness`].
+    pub fn flash_brightness<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        peak: u8,
+        hold_ms: u32,
+    ) -> Result<()> {
+        self.driver.flash_brightness(delay, peak, hold_ms)
+    }
+
+    /// Follows an ambient light reading, see [`Max7219::auto_brightness_tick`].
+    ///
+    /// Returns whether a new intensity was written.
+    pub fn auto_brightness_tick(
+        &mut self,
+        ambient: u16,
+        curve: &BrightnessCurve,
+        hysteresis: u8,
+    ) -> Result<bool> {
+        self.driver.auto_brightness_tick(ambient, curve, hysteresis)
+    }
+
+    /// Puts a device into an intensity group, see [`Max7219::assign_group`].
+    pub fn assign_group(&mut self, device_index: usize, group: u8) -> Result<()> {
+        self.driver.assign_group(device_index, group)
+    }
+
+    /// Sets the intensity of every device in a group, see [`Max7219::set_group_intensity`].
+    pub fn set_group_intensity(&mut self, group: u8, intensity: u8) -> Result<()> {
+        self.driver.set_group_intensity(group, intensity)
+    }
+
+    /// Changes the number of devices at runtime, initializing any added ones.
+    ///
+    /// The digits last written to each device move with it, see
+    /// [`Max7219::set_device_count`].
+    pub fn set_device_count(&mut self, count: usize) -> Result<()> {
+        let old_count = self.driver.device_count();
+        let result = self.driver.set_device_count(count);
+        let count = self.driver.device_count();
+        self.driver.chain_order().resize_records(
+            &mut self.shadow,
+            old_count,
+            count,
+            [0; NUM_DIGITS as usize],
+        );
+        result
+    }
+
+    /// Installs a hook called before every wait in blocking helpers such as
+    /// [`Self::flash_brightness`], see [`Max7219::set_idle_hook`].
+    pub fn set_idle_hook(&mut self, hook: Option<fn()>) {
+        self.driver.set_idle_hook(hook);
+    }
+
+    /// Replaces characters before font lookup, e.g. `'µ'` by `'u'`.
+    ///
+    /// Applies to [`Self::write_char`], [`Self::write_char_to_device`],
+    /// [`Self::write_bcd_char`] and [`Self::splash`]. Only the first matching entry
+    /// is used.
+    pub fn set_char_map(&mut self, substitutions: &'static [(char, char)]) {
+        self.substitutions = substitutions;
+    }
+
+    /// Writes `ch` with a custom segment pattern instead of the font's.
+    ///
+    /// Custom patterns are looked up after [`Self::set_char_map`] substitutions and
+    /// use the same bit layout as [`Font`] (`DP G F E D C B A`).
+    pub fn set_custom_glyphs(&mut self, glyphs: &'static [(char, u8)]) {
+        self.glyphs = glyphs;
+    }
+
+    /// Declares which segment lines drive the module's indicator LEDs.
+    ///
+    /// Each entry is `(indicator, digit, bit)` on device 0, with `bit` in the
+    /// font's `DP G F E D C B A` layout, so the colon of most 6-digit clock modules
+    /// is `(Indicator::Colon1, 6, 7)`. An indicator may be listed more than once to
+    /// light several LEDs. All indicators start off.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidDigit` if a digit is above 7 and `Error::OutOfBounds`
+    /// if a bit is above 7. Nothing changes in that case.
+    pub fn set_special_indicators(
+        &mut self,
+        indicators: &'static [(Indicator, u8, u8)],
+    ) -> Result<()> {
+        for &(_, digit, bit) in indicators {
+            if digit >= NUM_DIGITS {
+                return Err(Error::InvalidDigit { digit });
+            }
+            if bit > 7 {
+                return Err(Error::OutOfBounds {
+                    x: u16::from(bit),
+                    y: 0,
+                });
+            }
+        }
+        self.indicators = indicators;
+        self.lit_indicators = 0;
+        Ok(())
+    }
+
+    /// Turns an indicator LED on or off.
+    ///
+    /// The state is kept across later writes: the indicator bits are merged into
+    /// every value written to their digits, so showing a new time does not wipe
+    /// the colon. Digits are rewritten from what this display last wrote to them;
+    /// changes made through [`Self::driver`] are not seen. An indicator without an
+    /// entry in [`Self::set_special_indicators`] only has its state recorded.
+    pub fn set_indicator(&mut self, indicator: Indicator, on: bool) -> Result<()> {
+        if on {
+            self.lit_indicators |= indicator.mask();
+        } else {
+            self.lit_indicators &= !indicator.mask();
+        }
+
+        for &(_, digit, _) in self.indicators.iter().filter(|&&(i, ..)| i == indicator) {
+            let value = self
+                .shadow
+                .first()
+                .and_then(|digits| digits.get(digit as usize))
+                .copied()
+                .unwrap_or(0);
+            self.write_digit(0, digit, value)?;
+        }
+        Ok(())
+    }
+
+    /// Whether an indicator is currently lit.
+    pub fn indicator(&self, indicator: Indicator) -> bool {
+        self.lit_indicators & indicator.mask() != 0
+    }
+
+    /// Shows `hours`, `minutes` and `seconds` as `HHMMSS` on digits 0 to 5 of
+    /// device 0, using the default font.
+    ///
+    /// Lit indicators stay lit, see [`Self::set_indicator`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidTime` if `hours` is above 23 or `minutes` or
+    /// `seconds` above 59, before anything is written.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// display.set_special_indicators(&[(Indicator::Colon1, 6, 7)])?;
+    /// display.set_indicator(Indicator::Colon1, true)?;
+    /// display.write_time(12, 34, 56)?;
+    /// ```
+    pub fn write_time(&mut self, hours: u8, minutes: u8, seconds: u8) -> Result<()> {
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return Err(Error::InvalidTime);
+        }
+
+        for (pair, value) in [hours, minutes, seconds].into_iter().enumerate() {
+            let digit = pair as u8 * 2;
+            let tens = self.default_font.get_char((b'0' + value / 10) as char);
+            let ones = self.default_font.get_char((b'0' + value % 10) as char);
+            self.write_digit(0, digit, tens)?;
+            self.write_digit(0, digit + 1, ones)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `value` to a digit with the lit indicators merged in and records it
+    /// in the shadow buffer.
+    fn write_digit(&mut self, device_index: usize, digit: u8, value: u8) -> Result<()> {
+        let mut data = value;
+        if device_index == 0 {
+            for &(indicator, indicator_digit, bit) in self.indicators {
+                if indicator_digit == digit && self.indicator(indicator) {
+                    data |= 1 << bit;
+                }
+            }
+        }
+
+        self.driver.write_raw_digit(device_index, digit, data)?;
+        if let Some(shadow) = self
+            .shadow
+            .get_mut(device_index)
+            .and_then(|digits| digits.get_mut(digit as usize))
+        {
+            *shadow = value;
+        }
+        Ok(())
+    }
+
+    /// Sets the font used by [`Self::splash`] and [`Self::write_time`].
+    ///
+    /// Defaults to [`STANDARD_FONT`]. Methods that take a font keep using the one
+    /// they are given.
+    pub fn set_default_font(&mut self, font: &'static Font) {
+        self.default_font = font;
+    }
+
+    /// Applies the character map to `ch`.
+    fn substitute(&self, ch: char) -> char {
+        self.substitutions
+            .iter()
+            .find(|&&(from, _)| from == ch)
+            .map_or(ch, |&(_, to)| to)
+    }
+
+    /// Number of digits `text` takes up on the display.
+    ///
+    /// A `'.'` following another character is shown as that digit's decimal point
+    /// and takes no digit of its own, so `"12.5"` needs three digits while `".5"`
+    /// and `"1..5"` need two and three. The character map is applied first.
+    pub fn display_len(&self, text: &str) -> usize {
+        let mut len = 0;
+        let mut can_merge = false;
+        for ch in text.chars().map(|ch| self.substitute(ch)) {
+            if ch == '.' && can_merge {
+                can_merge = false;
+                continue;
+            }
+            len += 1;
+            can_merge = ch != '.';
+        }
+        len
+    }
+
+    /// Shows `text` for `hold_ms`, then clears the display.
+    ///
+    /// Characters are written with the default font from digit 0 of device 0
+    /// onwards, continuing on the next device after eight digits. Text beyond the
+    /// last digit of the chain is ignored and unused digits stay blank. Indicators
+    /// are blanked too and come back with the next write to their digit. Expects
+    /// `DecodeMode::NoDecode`, as set by [`Self::from_spi`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// display.splash(&mut delay, "HELLO", 1500)?;
+    /// ```
+    pub fn splash<D: DelayNs>(&mut self, delay: &mut D, text: &str, hold_ms: u32) -> Result<()> {
+        self.driver.clear_all()?;
+
+        for (position, ch) in text.chars().take(self.digit_count()).enumerate() {
+            let device_index = position / NUM_DIGITS as usize;
+            let digit = (position % NUM_DIGITS as usize) as u8;
+            self.write_char_to_device(device_index, digit, ch, self.default_font)?;
+        }
+
+        self.driver.idle();
+        delay.delay_ms(hold_ms);
+        self.driver.clear_all()?;
+        self.shadow = [[0; NUM_DIGITS as usize]; MAX_DISPLAYS];
+        Ok(())
+    }
+
     /// Writes a character to a specific 7-segment display (i.e., a `Digit`) on the first MAX7219 device.
     ///
     /// This is a convenience method for single-device setups.
@@ -134,10 +497,192 @@ where
         ch: char,
         font: &Font,
     ) -> Result<()> {
-        let data = font.get_char(ch);
+        let ch = self.substitute(ch);
+        let data = self.segments(ch, font);
+        self.write_digit(device_index, digit, data)
+    }
 
-        self.driver.write_raw_digit(device_index, digit, data)?;
+    /// Segment pattern of an already substituted character, custom glyphs first.
+    fn segments(&self, ch: char, font: &Font) -> u8 {
+        self.glyphs
+            .iter()
+            .find(|&&(c, _)| c == ch)
+            .map_or_else(|| font.get_char(ch), |&(_, segments)| segments)
+    }
+
+    /// Number of digits in the whole chain, 8 per device.
+    pub fn digit_count(&self) -> usize {
+        self.device_count() * NUM_DIGITS as usize
+    }
+
+    /// Shows `text` with the default font from digit 0 of device 0 onwards and
+    /// blanks every other digit of the chain.
+    ///
+    /// A `'.'` following another character lights that digit's decimal point, as
+    /// counted by [`Self::display_len`]. Text beyond the last digit is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// display.write_text("HELLO")?;
+    /// ```
+    pub fn write_text(&mut self, text: &str) -> Result<()> {
+        self.write_text_at(0, text)
+    }
+
+    /// Shows `value` right-aligned on the chain like [`Self::write_text`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValueOutOfRange` if it needs more digits than the chain
+    /// has, before anything is written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "test-util")]
+    /// # fn main() -> max7219_display::Result<()> {
+    /// # use max7219_display::{Max7219, Register, SevenSegment, test_util::FrameRecorder};
+    /// # let mut recorder = FrameRecorder::new();
+    /// # let mut display = SevenSegment::new(Max7219::new(&mut recorder).with_device_count(1)?);
+    /// display.write_integer(-42)?;
+    /// assert_eq!(display.write_integer(123_456_789), Err(max7219_display::Error::ValueOutOfRange));
+    /// # drop(display);
+    /// # assert_eq!(recorder.writes().len(), 8);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "test-util"))]
+    /// # fn main() {}
+    /// ```
+    pub fn write_integer(&mut self, value: i32) -> Result<()> {
+        let mut text = TextBuffer::new();
+        write!(text, "{value}").map_err(|_| Error::ValueOutOfRange)?;
+        self.write_right_aligned(text.as_str())
+    }
+
+    /// Shows `value` with `decimals` digits after the decimal point, rounded and
+    /// right-aligned like [`Self::write_integer`]. The decimal point shares a
+    /// digit with the number before it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValueOutOfRange` if it needs more digits than the chain
+    /// has, before anything is written.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// display.write_float(21.456, 1)?; // "    21.5"
+    /// ```
+    pub fn write_float(&mut self, value: f32, decimals: u8) -> Result<()> {
+        let mut text = TextBuffer::new();
+        write!(text, "{value:.*}", usize::from(decimals)).map_err(|_| Error::ValueOutOfRange)?;
+        self.write_right_aligned(text.as_str())
+    }
+
+    /// Shows a reading given in thousandths, e.g. millivolts, with a fixed number
+    /// of decimals and an optional unit letter in the last digit.
+    ///
+    /// The value is rounded to `decimals` digits, `0` to `3`, and zero-padded so
+    /// the decimal point stays in the same digit whatever the magnitude: with two
+    /// decimals and a unit, `12340` shows as `"12.34V"` and `50` as `"0.05V"`,
+    /// right-aligned like [`Self::write_integer`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValueOutOfRange` if `decimals > 3` or the reading needs
+    /// more digits than the chain has, before anything is written.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// display.write_measurement(supply.millivolts(), 2, Some('V'))?; // "  12.34V"
+    /// ```
+    pub fn write_measurement(
+        &mut self,
+        value_milli: i32,
+        decimals: u8,
+        unit: Option<char>,
+    ) -> Result<()> {
+        if decimals > 3 {
+            return Err(Error::ValueOutOfRange);
+        }
+        let step = 10i64.pow(u32::from(3 - decimals));
+        let divisor = 10i64.pow(u32::from(decimals));
+        // Round half away from zero to the digits shown
+        let magnitude = (i64::from(value_milli).abs() + step / 2) / step;
+        let sign = if value_milli < 0 && magnitude != 0 {
+            "-"
+        } else {
+            ""
+        };
+
+        let mut text = TextBuffer::new();
+        write!(text, "{sign}{}", magnitude / divisor).map_err(|_| Error::ValueOutOfRange)?;
+        if decimals > 0 {
+            write!(
+                text,
+                ".{:0width$}",
+                magnitude % divisor,
+                width = usize::from(decimals)
+            )
+            .map_err(|_| Error::ValueOutOfRange)?;
+        }
+        if let Some(unit) = unit {
+            write!(text, "{unit}").map_err(|_| Error::ValueOutOfRange)?;
+        }
+        self.write_right_aligned(text.as_str())
+    }
+
+    fn write_right_aligned(&mut self, text: &str) -> Result<()> {
+        let len = self.display_len(text);
+        let width = self.digit_count();
+        if len > width {
+            return Err(Error::ValueOutOfRange);
+        }
+        self.write_text_at(width - len, text)
+    }
+
+    /// Writes `text` from chain position `start` onwards and blanks every other
+    /// digit.
+    pub(super) fn write_text_at(&mut self, start: usize, text: &str) -> Result<()> {
+        let width = self.digit_count();
+        let mut values = [0u8; MAX_DISPLAYS * NUM_DIGITS as usize];
+        let mut position = start;
+        let mut can_merge = false;
+        for ch in text.chars().map(|ch| self.substitute(ch)) {
+            if ch == '.' && can_merge {
+                if let Some(previous) = position.checked_sub(1).and_then(|p| values.get_mut(p)) {
+                    *previous |= 0x80;
+                }
+                can_merge = false;
+                continue;
+            }
+            if position >= width {
+                break;
+            }
+            if let Some(value) = values.get_mut(position) {
+                *value = self.segments(ch, self.default_font);
+            }
+            position += 1;
+            can_merge = ch != '.';
+        }
+
+        for (position, &value) in values.iter().take(width).enumerate() {
+            let device_index = position / NUM_DIGITS as usize;
+            let digit = (position % NUM_DIGITS as usize) as u8;
+            self.write_digit(device_index, digit, value)?;
+        }
+        Ok(())
+    }
 
+    /// Blanks every digit of the chain from position `start` onwards.
+    pub(super) fn blank_from(&mut self, start: usize) -> Result<()> {
+        for position in start..self.digit_count() {
+            let device_index = position / NUM_DIGITS as usize;
+            let digit = (position % NUM_DIGITS as usize) as u8;
+            self.write_digit(device_index, digit, 0)?;
+        }
         Ok(())
     }
 
@@ -151,6 +696,7 @@ where
     ///
     /// Returns an error if the character is not supported in BCD mode.
     pub fn write_bcd_char(&mut self, digit: u8, ch: char) -> Result<()> {
+        let ch = self.substitute(ch);
         let data = match ch {
             '0'..='9' => ch as u8 - b'0',
             '-' => 0x0A,
@@ -162,8 +708,35 @@ where
             _ => return Err(Error::UnsupportedChar),
         };
 
-        self.driver.write_raw_digit(0, digit, data)?;
+        self.write_digit(0, digit, data)
+    }
+}
+
+/// Fixed-capacity text for formatting numbers without allocating.
+struct TextBuffer {
+    bytes: [u8; MAX_DISPLAYS * NUM_DIGITS as usize * 2],
+    len: usize,
+}
+
+impl TextBuffer {
+    fn new() -> Self {
+        Self {
+            bytes: [0; MAX_DISPLAYS * NUM_DIGITS as usize * 2],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(self.bytes.get(..self.len).unwrap_or(&[])).unwrap_or("")
+    }
+}
 
+impl Write for TextBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let target = self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?;
+        target.copy_from_slice(s.as_bytes());
+        self.len = end;
         Ok(())
     }
 }
@@ -171,10 +744,12 @@ where
 #[cfg(test)]
 mod tests {
     use crate::{
-        Error, Max7219, Register,
-        seven_segment::{STANDARD_FONT, SevenSegment, fonts},
+        DecodeMode, Error, ErrorKind, Max7219, Register,
+        driver::{BrightnessCurve, InitConfig},
+        seven_segment::{Indicator, STANDARD_FONT, SevenSegment, SevenSegmentConfig, fonts},
+        test_util::FrameRecorder,
     };
-    use embedded_hal_mock::eh1::{spi::Mock as SpiMock, spi::Transaction};
+    use embedded_hal_mock::eh1::{delay::NoopDelay, spi::Mock as SpiMock, spi::Transaction};
 
     #[test]
     fn test_from_spi() {
@@ -217,6 +792,15 @@ mod tests {
                 crate::registers::DecodeMode::NoDecode as u8,
             ]),
             Transaction::transaction_end(),
+            // set_intensity_all(DEFAULT_INITIAL_INTENSITY) (2 devices)
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::Intensity.addr(),
+                crate::driver::DEFAULT_INITIAL_INTENSITY,
+                Register::Intensity.addr(),
+                crate::driver::DEFAULT_INITIAL_INTENSITY,
+            ]),
+            Transaction::transaction_end(),
             // clear_all() - 8 transactions for 8 digits, each affecting 2 devices
             // Digit0
             Transaction::transaction_start(),
@@ -368,7 +952,10 @@ mod tests {
         let mut display = SevenSegment::new(driver);
 
         let result = display.write_char_to_device(1, 0, 'A', &STANDARD_FONT); // Index 1 is invalid for device_count=1
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex { index: 1, count: 1 })
+        );
         spi.done();
     }
 
@@ -380,7 +967,7 @@ mod tests {
 
         let result = display.write_char(8, 'A', &STANDARD_FONT); // Digit 8 is invalid
         // This will fail inside Max7219::write_raw_digit -> Register::try_digit
-        assert_eq!(result, Err(Error::InvalidDigit));
+        assert_eq!(result, Err(Error::InvalidDigit { digit: 8 }));
         spi.done();
     }
 
@@ -433,7 +1020,7 @@ mod tests {
         let mut display = SevenSegment::new(driver);
 
         let result = display.write_bcd_char(0, 'X'); // 'X' is not supported in BCD mode
-        assert_eq!(result, Err(Error::UnsupportedChar));
+        assert_eq!(result.unwrap_err(), ErrorKind::UnsupportedChar);
         spi.done();
     }
 
@@ -445,7 +1032,7 @@ mod tests {
 
         let result = display.write_bcd_char(8, '0'); // Digit 8 is invalid
         // This will fail inside Max7219::write_raw_digit -> Register::try_digit
-        assert_eq!(result, Err(Error::InvalidDigit));
+        assert_eq!(result, Err(Error::InvalidDigit { digit: 8 }));
         spi.done();
     }
 
@@ -467,4 +1054,421 @@ mod tests {
 
         spi.done();
     }
+
+    #[test]
+    fn test_group_intensity_passthrough() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::NoOp.addr(),
+                0x00,
+                Register::Intensity.addr(),
+                0x03,
+            ]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut display = SevenSegment::new(driver);
+
+        display.assign_group(1, 2).unwrap();
+        display.set_group_intensity(2, 0x03).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_into_driver_and_rebuild() {
+        let write = |register: Register, data: u8| {
+            [
+                Transaction::transaction_start(),
+                Transaction::write_vec(vec![register.addr(), data]),
+                Transaction::transaction_end(),
+            ]
+        };
+        let mut expected_transactions = write(Register::Digit0, 0x30).to_vec();
+        expected_transactions.extend(write(Register::Shutdown, 0x00));
+        expected_transactions.extend(write(Register::Digit0, 0x6D));
+        let spi = SpiMock::new(&expected_transactions);
+
+        let mut display = SevenSegment::new(Max7219::new(spi));
+        display.driver().write_raw_digit(0, 0, 0x30).unwrap();
+        let spi = display
+            .into_driver()
+            .shutdown_and_release()
+            .map_err(|(_, error)| error)
+            .unwrap();
+
+        let mut display = SevenSegment::new(Max7219::new(spi));
+        display.driver().write_raw_digit(0, 0, 0x6D).unwrap();
+        let mut spi = display.into_driver().release();
+        spi.done();
+    }
+
+    #[test]
+    fn test_auto_brightness_tick() {
+        let curve = BrightnessCurve::new(&[(0, 0x01), (1000, 0x0D)]).unwrap();
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut display = SevenSegment::new(driver);
+
+        let written: Vec<bool> = [900, 950, 100, 120]
+            .into_iter()
+            .map(|ambient| display.auto_brightness_tick(ambient, &curve, 0).unwrap())
+            .collect();
+        assert_eq!(written, [true, false, true, false]);
+        assert_eq!(
+            recorder.writes(),
+            [
+                (0, Register::Intensity, 0x0C),
+                (0, Register::Intensity, 0x02)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_splash() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut display = SevenSegment::new(driver);
+
+        display
+            .splash(&mut NoopDelay::new(), "HELLO WORLD", 1000)
+            .unwrap();
+
+        let writes = recorder.writes();
+        let blank: Vec<_> = Register::digits()
+            .flat_map(|digit_register| [(0, digit_register, 0), (1, digit_register, 0)])
+            .collect();
+        let shown: Vec<_> = "HELLO WORLD"
+            .chars()
+            .enumerate()
+            .map(|(position, ch)| {
+                let digit_register = Register::try_from(position as u8 % 8 + 1).unwrap();
+                (position / 8, digit_register, STANDARD_FONT.get_char(ch))
+            })
+            .collect();
+
+        assert_eq!(writes[..16], blank);
+        assert_eq!(writes[16..27], shown);
+        assert_eq!(writes[27..], blank);
+    }
+
+    #[test]
+    fn test_char_map() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut display = SevenSegment::new(driver);
+        display.set_char_map(&[('µ', 'U'), ('o', '0')]);
+        display.set_custom_glyphs(&[('Ω', 0b0011_1110), ('0', 0b0001_1101)]);
+
+        display.write_char(0, 'µ', &STANDARD_FONT).unwrap();
+        display.write_char(1, 'Ω', &STANDARD_FONT).unwrap();
+        // Substituted first, then the custom pattern wins over the font
+        display.write_char(2, 'o', &STANDARD_FONT).unwrap();
+        display.write_char(3, 'A', &STANDARD_FONT).unwrap();
+        display.splash(&mut NoopDelay::new(), "µ", 0).unwrap();
+
+        let writes = recorder.writes();
+        assert_eq!(
+            writes[..4],
+            [
+                (0, Register::Digit0, STANDARD_FONT.get_char('U')),
+                (0, Register::Digit1, 0b0011_1110),
+                (0, Register::Digit2, 0b0001_1101),
+                (0, Register::Digit3, STANDARD_FONT.get_char('A')),
+            ]
+        );
+        assert_eq!(
+            writes[12],
+            (0, Register::Digit0, STANDARD_FONT.get_char('U'))
+        );
+    }
+
+    #[test]
+    fn test_flash_brightness_passthrough() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Intensity.addr(), 0x0F]),
+            Transaction::transaction_end(),
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Intensity.addr(), 0x00]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let driver = Max7219::new(&mut spi);
+        let mut display = SevenSegment::new(driver);
+
+        display
+            .flash_brightness(
+                &mut embedded_hal_mock::eh1::delay::NoopDelay::new(),
+                0x0F,
+                100,
+            )
+            .unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn test_display_len() {
+        let mut spi = SpiMock::new(&[]);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+
+        assert_eq!(display.display_len(""), 0);
+        assert_eq!(display.display_len("1234"), 4);
+        assert_eq!(display.display_len("12.5"), 3);
+        assert_eq!(display.display_len("1.2.3."), 3);
+        assert_eq!(display.display_len(".5"), 2);
+        assert_eq!(display.display_len("1..5"), 3);
+
+        display.set_char_map(&[(',', '.')]);
+        assert_eq!(display.display_len("12,5"), 3);
+        spi.done();
+    }
+
+    #[test]
+    fn test_set_device_count() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut display = SevenSegment::new(driver);
+        display
+            .write_char_to_device(0, 0, '1', &STANDARD_FONT)
+            .unwrap();
+        display
+            .write_char_to_device(1, 0, '2', &STANDARD_FONT)
+            .unwrap();
+
+        // The new devices are at the far end, indices 0 and 1
+        display.set_device_count(4).unwrap();
+        assert_eq!(display.shadow[2][0], STANDARD_FONT.get_char('1'));
+        assert_eq!(display.shadow[3][0], STANDARD_FONT.get_char('2'));
+        assert_eq!(display.shadow[0], [0; 8]);
+        display
+            .write_char_to_device(3, 0, '8', &STANDARD_FONT)
+            .unwrap();
+
+        display.set_device_count(3).unwrap();
+        assert_eq!(display.shadow[1][0], STANDARD_FONT.get_char('1'));
+        assert_eq!(display.shadow[2][0], STANDARD_FONT.get_char('8'));
+
+        let writes = recorder.writes();
+        assert!(
+            writes[2..writes.len() - 1]
+                .iter()
+                .all(|&(device_index, _, _)| device_index < 2)
+        );
+        assert_eq!(
+            writes.last(),
+            Some(&(3, Register::Digit0, STANDARD_FONT.get_char('8')))
+        );
+    }
+
+    #[test]
+    fn test_from_spi_preserving_display() {
+        let mut recorder = FrameRecorder::new();
+        let config = SevenSegmentConfig {
+            preserve_display: true,
+            ..Default::default()
+        };
+        SevenSegment::from_spi_with_config(&mut recorder, 2, config).unwrap();
+
+        let writes = recorder.writes();
+        assert!(!writes.is_empty());
+        assert!(writes.iter().all(|&(_, register, _)| !register.is_digit()));
+    }
+
+    #[test]
+    fn test_from_spi_with_init_config() {
+        let mut recorder = FrameRecorder::new();
+        let config = SevenSegmentConfig {
+            init: InitConfig::new()
+                .decode_mode(DecodeMode::Digits0To3)
+                .scan_limit(4),
+            ..Default::default()
+        };
+        let mut display = SevenSegment::from_spi_with_config(&mut recorder, 1, config).unwrap();
+        assert_eq!(
+            display.driver().decode_mode(0),
+            Some(DecodeMode::Digits0To3)
+        );
+
+        let writes = recorder.writes();
+        assert_eq!(writes[2], (0, Register::ScanLimit, 3));
+        assert_eq!(writes[3], (0, Register::DecodeMode, 0x0F));
+        assert_eq!(writes.len(), 13);
+    }
+
+    #[test]
+    fn test_colon_survives_write_time() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut display = SevenSegment::new(driver);
+        display
+            .set_special_indicators(&[(Indicator::Colon1, 6, 7), (Indicator::Colon2, 3, 7)])
+            .unwrap();
+
+        display.write_time(12, 34, 56).unwrap();
+        display.set_indicator(Indicator::Colon1, true).unwrap();
+        display.set_indicator(Indicator::Colon2, true).unwrap();
+        display.write_time(12, 35, 0).unwrap();
+        display.set_indicator(Indicator::Colon2, false).unwrap();
+        assert!(display.indicator(Indicator::Colon1));
+        assert!(!display.indicator(Indicator::Colon2));
+
+        let digit = |ch| STANDARD_FONT.get_char(ch);
+        let writes = recorder.writes();
+        assert_eq!(writes.len(), 6 + 2 + 6 + 1);
+        assert_eq!(writes[6], (0, Register::Digit6, 0x80));
+        assert_eq!(writes[7], (0, Register::Digit3, digit('4') | 0x80));
+        // The new time keeps the colon on the rewritten digit 3
+        assert_eq!(
+            writes[8..14],
+            [
+                (0, Register::Digit0, digit('1')),
+                (0, Register::Digit1, digit('2')),
+                (0, Register::Digit2, digit('3')),
+                (0, Register::Digit3, digit('5') | 0x80),
+                (0, Register::Digit4, digit('0')),
+                (0, Register::Digit5, digit('0')),
+            ]
+        );
+        assert_eq!(writes[14], (0, Register::Digit3, digit('5')));
+    }
+
+    #[test]
+    fn test_write_measurement() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut display = SevenSegment::new(driver);
+        let digit = |ch| STANDARD_FONT.get_char(ch);
+
+        display.write_measurement(12_340, 2, Some('V')).unwrap();
+        display.write_measurement(50, 2, Some('A')).unwrap();
+        display.write_measurement(-1_249, 2, None).unwrap();
+
+        let data: Vec<u8> = recorder.writes().iter().map(|&(_, _, data)| data).collect();
+        assert_eq!(
+            data[..8],
+            [
+                0,
+                0,
+                0,
+                digit('1'),
+                digit('2') | 0x80,
+                digit('3'),
+                digit('4'),
+                digit('V'),
+            ]
+        );
+        // Zero-padded so the decimal point stays in digit 4
+        assert_eq!(
+            data[8..16],
+            [
+                0,
+                0,
+                0,
+                0,
+                digit('0') | 0x80,
+                digit('0'),
+                digit('5'),
+                digit('A'),
+            ]
+        );
+        assert_eq!(
+            data[16..],
+            [
+                0,
+                0,
+                0,
+                0,
+                digit('-'),
+                digit('1') | 0x80,
+                digit('2'),
+                digit('5'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_measurement_over_range() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut display = SevenSegment::new(driver);
+
+        assert_eq!(
+            display.write_measurement(123_456_789, 3, Some('V')),
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            display.write_measurement(1_000, 4, None),
+            Err(Error::ValueOutOfRange)
+        );
+
+        // Seven digits plus the unit still fit, and are the only writes
+        display.write_measurement(12_345_678, 0, Some('V')).unwrap();
+        assert_eq!(recorder.writes().len(), 8);
+    }
+
+    #[test]
+    fn test_invalid_time_and_indicators() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut display = SevenSegment::new(driver);
+
+        assert_eq!(display.write_time(24, 0, 0), Err(Error::InvalidTime));
+        assert_eq!(display.write_time(0, 60, 0), Err(Error::InvalidTime));
+        assert_eq!(
+            display.set_special_indicators(&[(Indicator::Degree, 8, 0)]),
+            Err(Error::InvalidDigit { digit: 8 })
+        );
+        assert_eq!(
+            display.set_special_indicators(&[(Indicator::Alarm, 0, 8)]),
+            Err(Error::OutOfBounds { x: 8, y: 0 })
+        );
+        // Unmapped indicators only record their state
+        display.set_indicator(Indicator::Alarm, true).unwrap();
+        assert!(display.indicator(Indicator::Alarm));
+        assert!(recorder.writes().is_empty());
+    }
+
+    #[test]
+    fn test_device_and_digit_count() {
+        let mut recorder = FrameRecorder::new();
+        let display = SevenSegment::new(Max7219::new(&mut recorder));
+        assert_eq!(display.device_count(), 1);
+        assert_eq!(display.digit_count(), 8);
+
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+        let display = SevenSegment::new(driver);
+        assert_eq!(display.device_count(), 3);
+        assert_eq!(display.digit_count(), 24);
+    }
+
+    #[test]
+    fn test_default_font() {
+        static FONT: fonts::Font = fonts::Font::new(&[('1', 0b0100_1001)]);
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder);
+        let mut display = SevenSegment::new(driver);
+        display.set_default_font(&FONT);
+
+        display.splash(&mut NoopDelay::new(), "1", 0).unwrap();
+        display.write_time(11, 0, 0).unwrap();
+
+        let writes = recorder.writes();
+        assert_eq!(writes[8], (0, Register::Digit0, 0b0100_1001));
+        assert_eq!(writes[17], (0, Register::Digit0, 0b0100_1001));
+        assert_eq!(writes[18], (0, Register::Digit1, 0b0100_1001));
+    }
+
+    #[test]
+    fn test_self_test_passthrough() {
+        let mut recorder = FrameRecorder::new();
+        let driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+        let mut display = SevenSegment::new(driver);
+
+        let report = display.self_test(&mut NoopDelay::new()).unwrap();
+        assert!(report.passed());
+        assert_eq!(recorder.writes().len(), 2 * 74);
+    }
 }
@@ -1,7 +1,11 @@
 //! 7-segment display implementation
 
+#![cfg_attr(not(test), deny(clippy::indexing_slicing))]
+
+pub mod cycle;
 pub mod display;
 pub mod fonts;
 
-pub use display::SevenSegment;
+pub use cycle::{DisplayItem, ItemCycle};
+pub use display::{Indicator, SevenSegment, SevenSegmentConfig};
 pub use fonts::{Font, STANDARD_FONT};
@@ -0,0 +1,205 @@
+//! Test doubles shared by the crate's unit tests
+
+use core::cell::RefCell;
+use embedded_hal::{
+    delay::DelayNs,
+    spi::{ErrorKind, ErrorType, Operation, SpiDevice},
+};
+use std::collections::VecDeque;
+
+use crate::registers::Register;
+
+/// SPI device that records every write and fails the write with a given number.
+///
+/// Unlike the `embedded-hal-mock` SPI mock it can inject errors, which is needed
+/// to test how the driver recovers from a failed transfer.
+#[derive(Debug, Default)]
+pub(crate) struct FailingSpi {
+    /// Every write that was attempted, including the failing one.
+    pub(crate) writes: Vec<Vec<u8>>,
+    /// Zero-based index of the write that fails, if any.
+    pub(crate) fail_on: Option<usize>,
+}
+
+impl FailingSpi {
+    pub(crate) fn failing_on(write_index: usize) -> Self {
+        Self {
+            writes: Vec::new(),
+            fail_on: Some(write_index),
+        }
+    }
+}
+
+impl ErrorType for FailingSpi {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for FailingSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        for op in operations {
+            if let Operation::Write(data) = op {
+                self.writes.push(data.to_vec());
+                if self.fail_on == Some(self.writes.len() - 1) {
+                    return Err(ErrorKind::Other);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An SPI write or a wait, in the order they happened.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Event {
+    Write(Vec<u8>),
+    /// A wait in nanoseconds.
+    Wait(u32),
+}
+
+/// Delay recording the length of every wait in milliseconds.
+#[cfg(any(feature = "led-matrix", feature = "seven-segment"))]
+#[derive(Debug, Default)]
+pub(crate) struct RecordingDelay {
+    pub(crate) waits_ms: Vec<u32>,
+}
+
+#[cfg(any(feature = "led-matrix", feature = "seven-segment"))]
+impl DelayNs for RecordingDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.waits_ms.push(ns / 1_000_000);
+    }
+}
+
+/// SPI device logging its writes to a shared event log.
+pub(crate) struct LoggingSpi<'a>(pub(crate) &'a RefCell<Vec<Event>>);
+
+impl ErrorType for LoggingSpi<'_> {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for LoggingSpi<'_> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        for op in operations {
+            if let Operation::Write(data) = op {
+                self.0.borrow_mut().push(Event::Write(data.to_vec()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Delay logging its waits to the same log as a [`LoggingSpi`].
+pub(crate) struct LoggingDelay<'a>(pub(crate) &'a RefCell<Vec<Event>>);
+
+impl DelayNs for LoggingDelay<'_> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.borrow_mut().push(Event::Wait(ns));
+    }
+}
+
+/// The shift registers of a chain of MAX7219s, written through a [`ChainSpi`].
+///
+/// Every byte enters the device nearest the MCU and pushes the oldest byte of
+/// the chain out of the far end. When the transaction ends, each device latches
+/// the packet left in its shift register. Devices are counted from the MCU, `0`
+/// being the nearest.
+pub(crate) struct ShiftChain {
+    /// The bytes held by the chain, the far end first.
+    shift: VecDeque<u8>,
+    /// Latched register values of each device, indexed by address.
+    registers: Vec<[u8; 16]>,
+}
+
+impl ShiftChain {
+    /// A chain of `devices` devices whose shift registers hold no-ops.
+    pub(crate) fn new(devices: usize) -> Self {
+        Self::with_garbage(devices, [0x00, 0x00])
+    }
+
+    /// Adds `devices` freshly powered devices at the far end of the chain.
+    pub(crate) fn plug_in(&mut self, devices: usize) {
+        for _ in 0..devices * 2 {
+            self.shift.push_front(0x00);
+        }
+        self.registers.extend((0..devices).map(|_| [0; 16]));
+    }
+
+    /// Removes `devices` devices from the far end of the chain.
+    pub(crate) fn unplug(&mut self, devices: usize) {
+        self.shift.drain(..devices * 2);
+        self.registers.truncate(self.registers.len() - devices);
+    }
+
+    /// A chain whose shift registers all hold `packet`, e.g. after a reset in
+    /// the middle of a transfer.
+    pub(crate) fn with_garbage(devices: usize, packet: [u8; 2]) -> Self {
+        Self {
+            shift: packet.into_iter().cycle().take(devices * 2).collect(),
+            registers: vec![[0; 16]; devices],
+        }
+    }
+
+    /// The value `device` last latched into `register`.
+    pub(crate) fn register(&self, device: usize, register: Register) -> u8 {
+        self.registers[device][usize::from(register.addr())]
+    }
+
+    /// The devices in display test mode, counted from the MCU.
+    pub(crate) fn lit(&self) -> Vec<usize> {
+        (0..self.registers.len())
+            .filter(|&device| self.register(device, Register::DisplayTest) & 0x01 != 0)
+            .collect()
+    }
+
+    fn latch(&mut self) {
+        let len = self.shift.len();
+        for (device, registers) in self.registers.iter_mut().enumerate() {
+            let start = len - 2 * (device + 1);
+            let (addr, data) = (self.shift[start], self.shift[start + 1]);
+            if addr != Register::NoOp.addr() {
+                registers[usize::from(addr & 0x0F)] = data;
+            }
+        }
+    }
+}
+
+/// SPI device shifting its writes into a shared [`ShiftChain`], so the chain can
+/// be inspected while the driver owns the device.
+pub(crate) struct ChainSpi<'a>(pub(crate) &'a RefCell<ShiftChain>);
+
+impl ErrorType for ChainSpi<'_> {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for ChainSpi<'_> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let mut chain = self.0.borrow_mut();
+        for op in operations {
+            if let Operation::Write(data) = op {
+                for &byte in data.iter() {
+                    chain.shift.push_back(byte);
+                    chain.shift.pop_front();
+                }
+            }
+        }
+        chain.latch();
+        Ok(())
+    }
+}
+
+/// Runs a future to completion on the current thread.
+///
+/// The SPI mocks complete every transfer at once, so the future never has to be
+/// woken up and a no-op waker is enough.
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use core::task::{Context, Poll, Waker};
+
+    let mut future = core::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! In-memory LED matrix with flush recording
+
+use std::{
+    cell::RefCell,
+    convert::Infallible,
+    fmt,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    string::String,
+    vec::Vec,
+};
+
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+use super::decode_frame;
+use crate::{MAX_DISPLAYS, Max7219, Register, Result, led_matrix::LedMatrix};
+
+/// What every device of the chain currently shows, one byte per row.
+type DisplayRam = [[u8; 8]; MAX_DISPLAYS];
+
+/// SPI device backing a [`FakeMatrix`].
+///
+/// It applies digit register writes to a model of the chain's display RAM and
+/// ignores every other register.
+#[derive(Debug, Clone, Default)]
+pub struct FakeSpi {
+    display: Rc<RefCell<DisplayRam>>,
+}
+
+impl ErrorType for FakeSpi {
+    type Error = Infallible;
+}
+
+impl SpiDevice for FakeSpi {
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), Self::Error> {
+        let mut display = self.display.borrow_mut();
+        for op in operations {
+            if let Operation::Write(data) = op {
+                for (device_index, register, data) in decode_frame(data) {
+                    let digit = register.addr().wrapping_sub(Register::Digit0.addr());
+                    if let (Some(rows), true) = (display.get_mut(device_index), digit < 8) {
+                        rows[digit as usize] = data;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An [`LedMatrix`] drawing into memory, with a recorder of every flush.
+///
+/// It dereferences to the wrapped [`LedMatrix`], so every drawing method is
+/// available. Calling [`Self::flush`] on the fake also records the frame shown
+/// afterwards; use [`Self::record`] to capture frames drawn by helpers that write
+/// to the chain directly, such as [`LedMatrix::draw_text`].
+pub struct FakeMatrix<const BUFFER_LENGTH: usize = 64, const DEVICE_COUNT: usize = 1> {
+    matrix: LedMatrix<FakeSpi, BUFFER_LENGTH, DEVICE_COUNT>,
+    display: Rc<RefCell<DisplayRam>>,
+    frames: Vec<Frame>,
+}
+
+impl<const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    FakeMatrix<BUFFER_LENGTH, DEVICE_COUNT>
+{
+    /// Creates a blank fake chain of `DEVICE_COUNT` devices.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`LedMatrix::from_driver`] if `DEVICE_COUNT` or `BUFFER_LENGTH`
+    /// is invalid.
+    pub fn new() -> Result<Self> {
+        let spi = FakeSpi::default();
+        let display = Rc::clone(&spi.display);
+        let driver = Max7219::new(spi).with_device_count(DEVICE_COUNT)?;
+        Ok(Self {
+            matrix: LedMatrix::from_driver(driver)?,
+            display,
+            frames: Vec::new(),
+        })
+    }
+
+    /// Flushes the framebuffer like [`LedMatrix::flush`] and records the result.
+    pub fn flush(&mut self) -> Result<()> {
+        self.matrix.flush()?;
+        self.record();
+        Ok(())
+    }
+
+    /// Returns what the chain shows right now.
+    pub fn frame(&self) -> Frame {
+        let display = self.display.borrow();
+        Frame {
+            devices: display[..DEVICE_COUNT].to_vec(),
+        }
+    }
+
+    /// Appends the current frame to the recording.
+    pub fn record(&mut self) {
+        let frame = self.frame();
+        self.frames.push(frame);
+    }
+
+    /// Returns every recorded frame, oldest first.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Returns the recorded frames and starts a new recording.
+    pub fn take_frames(&mut self) -> Vec<Frame> {
+        core::mem::take(&mut self.frames)
+    }
+}
+
+impl<const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize>
+    LedMatrix<FakeSpi, BUFFER_LENGTH, DEVICE_COUNT>
+{
+    /// Creates a matrix over a fake chain, without initializing it.
+    ///
+    /// For examples and tests that only look at the framebuffer, e.g. with
+    /// [`LedMatrix::get_column`]. Use a [`FakeMatrix`] to check what the chain
+    /// shows.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`LedMatrix::from_driver`] if `DEVICE_COUNT` or `BUFFER_LENGTH`
+    /// is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use max7219_display::led_matrix::display::Matrix4;
+    ///
+    /// let mut matrix = Matrix4::for_tests()?;
+    /// matrix.write_column(31, 0xFF)?;
+    /// assert_eq!(matrix.get_column(31), Ok(0xFF));
+    /// # Ok::<(), max7219_display::Error>(())
+    /// ```
+    pub fn for_tests() -> Result<Self> {
+        let driver = Max7219::new(FakeSpi::default()).with_device_count(DEVICE_COUNT)?;
+        LedMatrix::from_driver(driver)
+    }
+}
+
+impl<const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> Deref
+    for FakeMatrix<BUFFER_LENGTH, DEVICE_COUNT>
+{
+    type Target = LedMatrix<FakeSpi, BUFFER_LENGTH, DEVICE_COUNT>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.matrix
+    }
+}
+
+impl<const BUFFER_LENGTH: usize, const DEVICE_COUNT: usize> DerefMut
+    for FakeMatrix<BUFFER_LENGTH, DEVICE_COUNT>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.matrix
+    }
+}
+
+/// The picture shown by a whole chain at one moment.
+///
+/// Formats as ASCII art, see the [module documentation](super).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    devices: Vec<[u8; 8]>,
+}
+
+impl Frame {
+    /// Returns the rows of one device, bit 7 being the leftmost column.
+    pub fn device_rows(&self, device_index: usize) -> Option<[u8; 8]> {
+        self.devices.get(device_index).copied()
+    }
+
+    /// Returns whether the LED at `(x, y)` is lit, `x` running across the chain.
+    pub fn is_lit(&self, x: usize, y: usize) -> bool {
+        match self.devices.get(x / 8) {
+            Some(rows) if y < 8 => rows[y] & (0x80 >> (x % 8)) != 0,
+            _ => false,
+        }
+    }
+
+    /// Renders one row of the chain as `#` and `.` characters.
+    fn ascii_row(&self, y: usize) -> String {
+        (0..self.devices.len() * 8)
+            .map(|x| if self.is_lit(x, y) { '#' } else { '.' })
+            .collect()
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..8 {
+            writeln!(f, "{}", self.ascii_row(y))?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares a frame with ASCII art, returning a readable diff if they differ.
+///
+/// This is the check behind [`assert_frame_eq!`](crate::assert_frame_eq). Rows
+/// that differ are marked with `>` in the diff.
+pub fn frame_diff(frame: &Frame, expected: &str) -> Option<String> {
+    let expected: Vec<&str> = expected
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let actual: Vec<String> = (0..8).map(|y| frame.ascii_row(y)).collect();
+
+    if expected.len() == actual.len() && expected.iter().zip(&actual).all(|(e, a)| e == a) {
+        return None;
+    }
+
+    let width = actual[0]
+        .len()
+        .max(expected.iter().map(|e| e.len()).max().unwrap_or(0));
+    let mut diff = String::from("frames differ\n");
+    diff += &std::format!("  {:width$}   actual\n", "expected");
+    for y in 0..expected.len().max(actual.len()) {
+        let e = expected.get(y).copied().unwrap_or("");
+        let a = actual.get(y).map(String::as_str).unwrap_or("");
+        let marker = if e == a { ' ' } else { '>' };
+        diff += &std::format!("{marker} {e:width$}   {a}\n");
+    }
+    Some(diff)
+}
+
+/// Asserts that a [`Frame`](crate::test_util::Frame) matches ASCII art.
+///
+/// Panics with a side-by-side diff on mismatch. See the
+/// [`test_util`](crate::test_util) module for the format.
+#[macro_export]
+macro_rules! assert_frame_eq {
+    ($frame:expr, $expected:expr $(,)?) => {
+        if let Some(diff) = $crate::test_util::frame_diff(&$frame, $expected) {
+            panic!("{}", diff);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "symbols-basic")]
+    use crate::led_matrix::symbols::Symbol;
+
+    #[test]
+    fn test_for_tests_checks_sizes() {
+        let matrix = LedMatrix::<FakeSpi, 128, 2>::for_tests().unwrap();
+        assert_eq!(matrix.device_count(), 2);
+        assert_eq!(
+            LedMatrix::<FakeSpi, 64, 2>::for_tests().err(),
+            Some(crate::Error::BufferSizeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_flush_is_recorded() {
+        let mut matrix = FakeMatrix::<128, 2>::new().unwrap();
+        matrix.write_column(0, 0xFF).unwrap();
+        matrix.flush().unwrap();
+        matrix.clear_buffer();
+        matrix.flush().unwrap();
+
+        let frames = matrix.take_frames();
+        assert_eq!(frames.len(), 2);
+        // flush shows framebuffer block 0 on device 0, where draw_text puts its
+        // first character
+        assert!(frames[0].is_lit(0, 0) && frames[0].is_lit(0, 7));
+        assert!(!frames[0].is_lit(8, 0));
+        assert!(!frames[1].is_lit(0, 0));
+        assert!(matrix.frames().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "symbols-basic")]
+    fn test_assert_frame_eq_direct_writes() {
+        let mut matrix = FakeMatrix::<64, 1>::new().unwrap();
+        matrix.draw_symbol(0, Symbol::Heart).unwrap();
+        matrix.record();
+
+        assert_eq!(matrix.frames().len(), 1);
+        assert_eq!(
+            matrix.frame().device_rows(0),
+            Some(*Symbol::Heart.to_buffer().data())
+        );
+        assert_frame_eq!(
+            matrix.frame(),
+            "
+            ........
+            .##..##.
+            ########
+            ########
+            ########
+            .######.
+            ..####..
+            ...##...
+            "
+        );
+    }
+
+    #[test]
+    fn test_frame_diff_marks_rows() {
+        let matrix = FakeMatrix::<64, 1>::new().unwrap();
+        let expected = "#.......\n".to_owned() + &"........\n".repeat(7);
+
+        let diff = frame_diff(&matrix.frame(), &expected).unwrap();
+        assert!(diff.contains("> #.......   ........"));
+        assert!(diff.contains("  ........   ........"));
+        assert!(frame_diff(&matrix.frame(), &"........\n".repeat(8)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "frames differ")]
+    fn test_assert_frame_eq_panics() {
+        let matrix = FakeMatrix::<64, 1>::new().unwrap();
+        assert_frame_eq!(matrix.frame(), "#");
+    }
+}
@@ -0,0 +1,89 @@
+//! Helpers for testing code that drives MAX7219 displays, on the host
+//!
+//! # Screen layouts
+//!
+//! [`FakeMatrix`] is a real [`LedMatrix`](crate::LedMatrix) whose SPI bus feeds an
+//! in-memory model of the chain instead of hardware, so screen layouts can be unit
+//! tested without writing byte-level SPI expectations. Compare what is shown against
+//! ASCII art with [`assert_frame_eq!`](crate::assert_frame_eq):
+//!
+//! ```rust
+//! use max7219_display::{assert_frame_eq, test_util::FakeMatrix};
+//!
+//! let mut matrix = FakeMatrix::<64, 1>::new().unwrap();
+//! matrix.write_column(0, 0b1000_0001).unwrap();
+//! matrix.flush().unwrap();
+//!
+//! assert_frame_eq!(
+//!     matrix.frame(),
+//!     "
+//!     #.......
+//!     ........
+//!     ........
+//!     ........
+//!     ........
+//!     ........
+//!     ........
+//!     #.......
+//!     "
+//! );
+//! ```
+//!
+//! Frames show every device side by side, driver device 0 on the left, one `#` per
+//! lit LED and one `.` per dark one. Whitespace around each line of the expectation
+//! and blank lines are ignored, so it can be indented like the code around it.
+//!
+//! # Register writes
+//!
+//! For driver-level code, [`FrameRecorder`] stands in for the SPI device and decodes
+//! everything sent to the chain into `(device, register, data)` triples:
+//!
+//! ```rust
+//! use max7219_display::{Max7219, Register, test_util::FrameRecorder};
+//!
+//! let mut recorder = FrameRecorder::new();
+//! let mut driver = Max7219::new(&mut recorder).with_device_count(2).unwrap();
+//! driver.set_intensity(1, 0x08).unwrap();
+//!
+//! assert_eq!(recorder.writes(), [(1, Register::Intensity, 0x08)]);
+//! ```
+//!
+//! Requires the `test-util` feature, which pulls in `std`.
+//!
+//! The crate's own examples for drawing, scrolling and seven-segment output run
+//! against these helpers, so they are only executed with the feature enabled:
+//! `cargo test --doc --all-features`.
+
+#[cfg(feature = "led-matrix")]
+mod fake_matrix;
+mod recorder;
+
+#[cfg(feature = "led-matrix")]
+pub use fake_matrix::{FakeMatrix, FakeSpi, Frame, frame_diff};
+pub use recorder::{FrameRecorder, RegisterWrite};
+
+use crate::Register;
+
+/// Splits one chain frame into its packets, skipping no-op padding.
+///
+/// The first packet goes to device 0, the one furthest down the chain, whatever
+/// the driver's [`ChainOrder`](crate::driver::ChainOrder).
+///
+/// # Panics
+///
+/// Panics if the frame has an odd length or a packet addresses an unknown
+/// register; the driver never sends either.
+fn decode_frame(data: &[u8]) -> impl Iterator<Item = RegisterWrite> + '_ {
+    assert!(
+        data.len().is_multiple_of(2),
+        "frame of {} bytes is not a whole number of packets",
+        data.len()
+    );
+    data.chunks_exact(2)
+        .enumerate()
+        .filter_map(|(device_index, packet)| {
+            let register = Register::try_from(packet[0])
+                .unwrap_or_else(|_| panic!("unknown register address {:#04x}", packet[0]));
+            (register != Register::NoOp).then_some((device_index, register, packet[1]))
+        })
+}
@@ -0,0 +1,106 @@
+//! SPI shim that records decoded register writes
+
+use std::{convert::Infallible, vec::Vec};
+
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+use super::decode_frame;
+use crate::Register;
+
+/// One decoded packet: `(device_index, register, data)`.
+///
+/// Device 0 is the first packet of the frame, the device furthest from the
+/// microcontroller. This is the driver's device 0 with the default
+/// [`ChainOrder`](crate::driver::ChainOrder), and its last device with
+/// `ChainOrder::NearestFirst`.
+pub type RegisterWrite = (usize, Register, u8);
+
+/// SPI device that decodes everything the driver sends.
+///
+/// Each SPI write is one frame for the whole chain. The recorder splits it into
+/// packets and keeps the ones that are not no-op padding, so tests can assert on
+/// registers instead of raw bytes. Pass it by mutable reference to
+/// [`Max7219::new`](crate::Max7219::new) and inspect it afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct FrameRecorder {
+    frames: Vec<Vec<RegisterWrite>>,
+}
+
+impl FrameRecorder {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the decoded writes of every frame, one entry per SPI write.
+    ///
+    /// Frames that only carried no-ops show up as empty entries.
+    pub fn frames(&self) -> &[Vec<RegisterWrite>] {
+        &self.frames
+    }
+
+    /// Returns every decoded write in the order it was sent.
+    pub fn writes(&self) -> Vec<RegisterWrite> {
+        self.frames.iter().flatten().copied().collect()
+    }
+
+    /// Forgets everything recorded so far.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+impl ErrorType for FrameRecorder {
+    type Error = Infallible;
+}
+
+impl SpiDevice for FrameRecorder {
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), Self::Error> {
+        for op in operations {
+            if let Operation::Write(data) = op {
+                self.frames.push(decode_frame(data).collect());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Max7219;
+
+    #[test]
+    fn test_records_decoded_frames() {
+        let mut recorder = FrameRecorder::new();
+        let mut driver = Max7219::new(&mut recorder).with_device_count(3).unwrap();
+        driver.write_raw_digit(2, 4, 0xAA).unwrap();
+        driver.set_intensity_all(0x05).unwrap();
+
+        assert_eq!(
+            recorder.frames(),
+            [
+                vec![(2, Register::Digit4, 0xAA)],
+                vec![
+                    (0, Register::Intensity, 0x05),
+                    (1, Register::Intensity, 0x05),
+                    (2, Register::Intensity, 0x05),
+                ],
+            ]
+        );
+        assert_eq!(recorder.writes().len(), 4);
+
+        recorder.clear();
+        assert!(recorder.writes().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown register address 0x10")]
+    fn test_unknown_register_panics() {
+        let mut recorder = FrameRecorder::new();
+        recorder.write(&[0x10, 0x00]).unwrap();
+    }
+}